@@ -379,6 +379,60 @@ pub enum InternalState {
     Present(PresentSubstate),
 }
 
+/// Maximum number of samples that current and voltage can be averaged over, see [`State::set_averaging_window`].
+pub const MAX_AVERAGING_WINDOW: usize = 16;
+
+/// Moving average of the most recent current and voltage readings.
+#[derive(Clone, Copy, Default)]
+struct MeasurementWindow {
+    /// Number of samples to average over, averaging is disabled when this is 0 or 1.
+    size: usize,
+    /// Number of valid samples, at most `size`.
+    len: usize,
+    /// Index the next sample is written to.
+    next: usize,
+    current: [MilliAmpsSigned; MAX_AVERAGING_WINDOW],
+    voltage: [MilliVolts; MAX_AVERAGING_WINDOW],
+}
+
+impl MeasurementWindow {
+    fn is_enabled(&self) -> bool {
+        self.size > 1
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+
+    fn push(&mut self, current: MilliAmpsSigned, voltage: MilliVolts) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if let (Some(c), Some(v)) = (self.current.get_mut(self.next), self.voltage.get_mut(self.next)) {
+            *c = current;
+            *v = voltage;
+        }
+        self.next = (self.next + 1) % self.size;
+        self.len = (self.len + 1).min(self.size);
+    }
+
+    fn average_current(&self) -> Option<MilliAmpsSigned> {
+        let samples = self.current.get(..self.len).filter(|s| !s.is_empty())?;
+        let sum: i32 = samples.iter().map(|&c| i32::from(c)).sum();
+        // The average of i16 values always fits in an i16
+        Some((sum / samples.len() as i32) as MilliAmpsSigned)
+    }
+
+    fn average_voltage(&self) -> Option<MilliVolts> {
+        let samples = self.voltage.get(..self.len).filter(|s| !s.is_empty())?;
+        let sum: u32 = samples.iter().map(|&v| u32::from(v)).sum();
+        // The average of u16 values always fits in a u16
+        Some((sum / samples.len() as u32) as MilliVolts)
+    }
+}
+
 /// Fuel gauge state, owned by the driver (OEM) and managed via the `on_*` transition methods.
 ///
 /// This holds both the fuel gauge state machine state and the cached static and
@@ -394,6 +448,7 @@ pub struct State<S: StaticBatteryData = StaticBatteryMsgs, D: DynamicBatteryData
     state: InternalState,
     static_cache: S,
     dynamic_cache: D,
+    averaging: MeasurementWindow,
 }
 
 impl<S: StaticBatteryData, D: DynamicBatteryData> State<S, D> {
@@ -471,6 +526,44 @@ impl<S: StaticBatteryData, D: DynamicBatteryData> State<S, D> {
     /// `Present(Operational(Polling))` state.
     pub fn on_dynamic_data(&mut self, update: impl FnOnce(&mut D)) {
         update(&mut self.dynamic_cache);
+        let standard = self.dynamic_cache.standard();
+        self.averaging.push(standard.current, standard.voltage);
+    }
+
+    /// Set the number of dynamic data updates that [`Self::averaged_current`] and
+    /// [`Self::averaged_voltage`] average over.
+    ///
+    /// Averaging smooths noisy readings before they're reported to the host. A
+    /// window of 0 or 1 disables averaging, and windows larger than
+    /// [`MAX_AVERAGING_WINDOW`] are clamped. Changing the window discards
+    /// previously collected samples. The instantaneous readings remain available
+    /// through [`Self::dynamic_cache`].
+    pub fn set_averaging_window(&mut self, samples: usize) {
+        self.averaging.size = samples.min(MAX_AVERAGING_WINDOW);
+        self.averaging.clear();
+    }
+
+    /// The number of dynamic data updates current and voltage are averaged over.
+    pub fn averaging_window(&self) -> usize {
+        self.averaging.size
+    }
+
+    /// Battery current in mA, averaged over the configured window.
+    ///
+    /// Returns the instantaneous current if averaging is disabled or no samples have been collected yet.
+    pub fn averaged_current(&self) -> MilliAmpsSigned {
+        self.averaging
+            .average_current()
+            .unwrap_or(self.dynamic_cache.standard().current)
+    }
+
+    /// Battery voltage in mV, averaged over the configured window.
+    ///
+    /// Returns the instantaneous voltage if averaging is disabled or no samples have been collected yet.
+    pub fn averaged_voltage(&self) -> MilliVolts {
+        self.averaging
+            .average_voltage()
+            .unwrap_or(self.dynamic_cache.standard().voltage)
     }
 
     /// Handle a communication timeout.
@@ -483,6 +576,21 @@ impl<S: StaticBatteryData, D: DynamicBatteryData> State<S, D> {
         }
     }
 
+    /// Handle the battery being removed.
+    ///
+    /// Transitions to `NotPresent` from any state and returns `true` if the
+    /// battery was previously present, so the driver can notify listeners of the
+    /// change. Should be called by the driver when a presence pin deasserts or
+    /// the battery stops responding on the bus. A reinserted battery is brought
+    /// back up through [`FuelGauge::initialize`]. Averaged current and voltage
+    /// samples are discarded.
+    pub fn on_removed(&mut self) -> bool {
+        let was_present = self.is_present();
+        self.state = InternalState::NotPresent;
+        self.averaging.clear();
+        was_present
+    }
+
     /// Handle recovery after re-establishing communication.
     ///
     /// Transitions `Present(NotOperational)` back to `Present(Operational(Init))`.
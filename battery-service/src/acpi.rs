@@ -1,9 +1,9 @@
 #![allow(dead_code)]
 
 use battery_service_interface::BatteryError;
-use battery_service_interface::fuel_gauge::{DynamicBatteryData, FuelGauge, StaticBatteryData};
+use battery_service_interface::fuel_gauge::{DynamicBatteryData, FuelGauge, State, StaticBatteryData};
 use embedded_batteries_async::acpi::{PowerSourceState, PowerUnit};
-use embedded_batteries_async::smart_battery::CapacityModeValue;
+use embedded_batteries_async::smart_battery::{BatteryStatusFields, CapacityModeValue};
 use embedded_services::sync::Lockable;
 use embedded_services::{info, trace};
 
@@ -35,9 +35,14 @@ fn capacity_raw(value: CapacityModeValue) -> u32 {
     }
 }
 
+/// Returns true if the smart battery status reports the battery as discharging.
+fn is_discharging(battery_status: u16) -> bool {
+    BatteryStatusFields::from(battery_status).discharging()
+}
+
 pub(crate) fn compute_bst<D: DynamicBatteryData>(cache: &D) -> embedded_batteries_async::acpi::BstReturn {
     let cache = cache.standard();
-    let charging = if cache.battery_status & (1 << 6) == 0 {
+    let charging = if !is_discharging(cache.battery_status) {
         embedded_batteries_async::acpi::BatteryState::CHARGING
     } else {
         embedded_batteries_async::acpi::BatteryState::DISCHARGING
@@ -52,6 +57,27 @@ pub(crate) fn compute_bst<D: DynamicBatteryData>(cache: &D) -> embedded_batterie
     }
 }
 
+/// `_BST` with the present rate and voltage averaged over the state's averaging window, to smooth noisy readings.
+pub(crate) fn compute_bst_averaged<S: StaticBatteryData, D: DynamicBatteryData>(
+    state: &State<S, D>,
+) -> embedded_batteries_async::acpi::BstReturn {
+    embedded_batteries_async::acpi::BstReturn {
+        battery_present_rate: state.averaged_current().unsigned_abs().into(),
+        battery_present_voltage: state.averaged_voltage().into(),
+        ..compute_bst(state.dynamic_cache())
+    }
+}
+
+/// `_BST` reported while no battery is present, so stale cached capacity isn't reported to the host.
+pub(crate) fn compute_bst_absent() -> embedded_batteries_async::acpi::BstReturn {
+    embedded_batteries_async::acpi::BstReturn {
+        battery_state: embedded_batteries_async::acpi::BatteryState::empty(),
+        battery_remaining_capacity: 0,
+        battery_present_rate: 0,
+        battery_present_voltage: 0,
+    }
+}
+
 pub(crate) fn compute_bix<S: StaticBatteryData, D: DynamicBatteryData>(
     static_cache: &S,
     dynamic_cache: &D,
@@ -153,27 +179,91 @@ pub(crate) fn compute_bmd<S: StaticBatteryData, D: DynamicBatteryData>(
     }
 }
 
+/// `_BCT`/`_BTM` return value indicating the time can't be estimated.
+const TIME_UNKNOWN_S: u32 = u32::MAX;
+
+/// Present charge or discharge rate in the units of `capacity`.
+///
+/// A capacity in mAh takes the current in mA as is. A capacity in cWh needs a rate in cW, so the current is
+/// converted to power using the present voltage.
+fn present_rate(capacity: CapacityModeValue, current: i16, voltage_mv: u16) -> u32 {
+    let current_ma = u32::from(current.unsigned_abs());
+    match capacity {
+        CapacityModeValue::MilliAmpUnsigned(_) => current_ma,
+        // mA * mV is in uW, and 1 cW is 10,000 uW
+        CapacityModeValue::CentiWattUnsigned(_) => current_ma * u32::from(voltage_mv) / 10_000,
+    }
+}
+
+/// Seconds needed to move `capacity` at `rate`, saturated below [`TIME_UNKNOWN_S`].
+///
+/// Capacity and rate are in the same units reported by `_BST` (mAh and mA, or their centiWatt
+/// equivalents), so their ratio is a time in hours.
+fn capacity_time_s(capacity: u32, rate: u32) -> u32 {
+    if rate == 0 {
+        return TIME_UNKNOWN_S;
+    }
+
+    let seconds = u64::from(capacity) * 3600 / u64::from(rate);
+    u32::try_from(seconds).unwrap_or(TIME_UNKNOWN_S).min(TIME_UNKNOWN_S - 1)
+}
+
+/// Returns the estimated time in seconds to charge to the requested level, or [`TIME_UNKNOWN_S`].
+///
+/// Returns 0 if the battery is already at or above the requested level.
 pub(crate) fn compute_bct<D: DynamicBatteryData>(
     payload: &embedded_batteries_async::acpi::Bct,
-    _dynamic_cache: &D,
+    dynamic_cache: &D,
 ) -> embedded_batteries_async::acpi::BctReturnResult {
-    // Just echo back charge level for now
-    // TODO: Actually compute time from charge level
-    embedded_batteries_async::acpi::BctReturnResult::from(payload.charge_level_percent)
+    let cache = dynamic_cache.standard();
+    if payload.charge_level_percent == 0 || payload.charge_level_percent > 100 {
+        return embedded_batteries_async::acpi::BctReturnResult::from(TIME_UNKNOWN_S);
+    }
+
+    let full = u64::from(capacity_raw(cache.full_charge_capacity));
+    let target = u32::try_from(full * u64::from(payload.charge_level_percent) / 100).unwrap_or(u32::MAX);
+    let remaining = capacity_raw(cache.remaining_capacity);
+    if remaining >= target {
+        return embedded_batteries_async::acpi::BctReturnResult::from(0);
+    }
+
+    // Only estimate while charging, otherwise the target will never be reached
+    let charging = !is_discharging(cache.battery_status) && cache.current > 0;
+    let rate = if charging {
+        present_rate(cache.remaining_capacity, cache.current, cache.voltage)
+    } else {
+        0
+    };
+    embedded_batteries_async::acpi::BctReturnResult::from(capacity_time_s(target - remaining, rate))
 }
 
+/// Returns the estimated time in seconds to discharge completely, or [`TIME_UNKNOWN_S`].
+///
+/// A discharge rate of 0 requests an estimate at the present discharge rate.
 pub(crate) fn compute_btm<D: DynamicBatteryData>(
     payload: &embedded_batteries_async::acpi::Btm,
-    _dynamic_cache: &D,
+    dynamic_cache: &D,
 ) -> embedded_batteries_async::acpi::BtmReturnResult {
-    // Just echo back charge level for now
-    // TODO: Actually compute time from charge level
-    embedded_batteries_async::acpi::BtmReturnResult::from(payload.discharge_rate)
+    let cache = dynamic_cache.standard();
+    let rate = if payload.discharge_rate != 0 {
+        payload.discharge_rate
+    } else if is_discharging(cache.battery_status) {
+        present_rate(cache.remaining_capacity, cache.current, cache.voltage)
+    } else {
+        // Not discharging, so there's no present rate to estimate from
+        0
+    };
+
+    embedded_batteries_async::acpi::BtmReturnResult::from(capacity_time_s(capacity_raw(cache.remaining_capacity), rate))
 }
 
-pub(crate) fn compute_sta() -> embedded_batteries_async::acpi::StaReturn {
-    // TODO: Grab real state values
-    embedded_batteries_async::acpi::StaReturn::all()
+pub(crate) fn compute_sta(present: bool) -> StaReturn {
+    // TODO: Grab real state values for the remaining bits
+    let mut sta = StaReturn::all();
+    if !present {
+        sta.remove(StaReturn::BATTERY_PRESENT);
+    }
+    sta
 }
 
 pub(crate) fn compute_psr(psu_state: &PsuState) -> embedded_batteries_async::acpi::PsrReturn {
@@ -334,7 +424,10 @@ impl<'hw, Reg: crate::registration::Registration<'hw>> crate::Service<'hw, Reg>
         fuel_gauge: &mut <Reg::FuelGauge as Lockable>::Inner,
     ) -> Result<BstReturn, BatteryError> {
         trace!("Battery service: got BST command!");
-        Ok(compute_bst(fuel_gauge.state().dynamic_cache()))
+        if !fuel_gauge.state().is_present() {
+            return Ok(compute_bst_absent());
+        }
+        Ok(compute_bst_averaged(fuel_gauge.state()))
     }
 
     /// Queries the estimated time remaining until the battery is fully discharged at the current discharge rate. Corresponds to ACPI's _BTM method.
@@ -381,10 +474,10 @@ impl<'hw, Reg: crate::registration::Registration<'hw>> crate::Service<'hw, Reg>
     /// Queries the battery's status. Corresponds to ACPI's _STA method.
     pub fn device_status(
         &self,
-        _fuel_gauge: &mut <Reg::FuelGauge as Lockable>::Inner,
+        fuel_gauge: &mut <Reg::FuelGauge as Lockable>::Inner,
     ) -> Result<StaReturn, BatteryError> {
         trace!("Battery service: got STA command!");
-        Ok(compute_sta())
+        Ok(compute_sta(fuel_gauge.state().is_present()))
     }
 }
 
@@ -392,11 +485,15 @@ impl<'hw, Reg: crate::registration::Registration<'hw>> crate::Service<'hw, Reg>
 mod tests {
     #![allow(clippy::expect_used)]
 
-    use embedded_batteries_async::smart_battery::CapacityModeValue;
+    use embedded_batteries_async::acpi::{Bct, Btm, StaReturn};
+    use embedded_batteries_async::smart_battery::{BatteryStatusFields, CapacityModeValue};
 
-    use super::{compute_bix, compute_bpc, compute_bst};
+    use super::{
+        TIME_UNKNOWN_S, compute_bct, compute_bix, compute_bpc, compute_bst, compute_bst_absent, compute_bst_averaged,
+        compute_btm, compute_sta,
+    };
     use battery_service_interface::fuel_gauge::{
-        DynamicBatteryData, DynamicBatteryMsgs, StaticBatteryData, StaticBatteryMsgs,
+        DynamicBatteryData, DynamicBatteryMsgs, MAX_AVERAGING_WINDOW, State, StaticBatteryData, StaticBatteryMsgs,
     };
 
     /// An OEM dynamic data type that embeds the standard messages and extends
@@ -483,4 +580,216 @@ mod tests {
         );
         assert_eq!(oem_static.oem_part_number, 0xABCD);
     }
+
+    /// Dynamic data for a 5000 mAh battery at `remaining_mah` with the given current.
+    fn time_estimate_cache(remaining_mah: u16, current: i16) -> DynamicBatteryMsgs {
+        DynamicBatteryMsgs {
+            full_charge_capacity: CapacityModeValue::MilliAmpUnsigned(5000),
+            remaining_capacity: CapacityModeValue::MilliAmpUnsigned(remaining_mah),
+            battery_status: BatteryStatusFields::new().with_discharging(current < 0).into(),
+            current,
+            ..Default::default()
+        }
+    }
+
+    /// `_BTM` estimates runtime from the present or requested discharge rate.
+    #[test]
+    fn compute_btm_time_to_empty() {
+        let cache = time_estimate_cache(2500, -1000);
+
+        // 2500 mAh at the present 1000 mA is 2.5 hours
+        let btm = compute_btm(&Btm { discharge_rate: 0 }, &cache);
+        assert_eq!(u32::from(btm), 9000);
+
+        // 2500 mAh at a requested 500 mA is 5 hours
+        let btm = compute_btm(&Btm { discharge_rate: 500 }, &cache);
+        assert_eq!(u32::from(btm), 18000);
+    }
+
+    /// `_BTM` at the present rate is unknown when the battery isn't discharging.
+    #[test]
+    fn compute_btm_zero_rate() {
+        let btm = compute_btm(&Btm { discharge_rate: 0 }, &time_estimate_cache(2500, 0));
+        assert_eq!(u32::from(btm), TIME_UNKNOWN_S);
+
+        let btm = compute_btm(&Btm { discharge_rate: 0 }, &time_estimate_cache(2500, 1000));
+        assert_eq!(u32::from(btm), TIME_UNKNOWN_S);
+    }
+
+    /// `_BCT` estimates the time to reach a charge level at the present charge rate.
+    #[test]
+    fn compute_bct_time_to_full() {
+        let cache = time_estimate_cache(2500, 1250);
+
+        // 2500 mAh remaining to full at 1250 mA is 2 hours
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 100,
+            },
+            &cache,
+        );
+        assert_eq!(u32::from(bct), 7200);
+
+        // 1500 mAh remaining to 80% at 1250 mA is 1.2 hours
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 80,
+            },
+            &cache,
+        );
+        assert_eq!(u32::from(bct), 4320);
+    }
+
+    /// `_BTM` and `_BCT` convert the present current to power when capacity is reported in cWh.
+    #[test]
+    fn compute_time_estimates_centiwatt() {
+        let cache = |remaining_cwh, current, voltage| DynamicBatteryMsgs {
+            full_charge_capacity: CapacityModeValue::CentiWattUnsigned(5000),
+            remaining_capacity: CapacityModeValue::CentiWattUnsigned(remaining_cwh),
+            voltage,
+            ..time_estimate_cache(0, current)
+        };
+
+        // 2500 cWh at 1000 mA and 12 V (1200 cW) is 2 hours 5 minutes
+        let btm = compute_btm(&Btm { discharge_rate: 0 }, &cache(2500, -1000, 12000));
+        assert_eq!(u32::from(btm), 7500);
+
+        // 2500 cWh remaining to full at 1250 mA and 12 V (1500 cW) is 1 hour 40 minutes
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 100,
+            },
+            &cache(2500, 1250, 12000),
+        );
+        assert_eq!(u32::from(bct), 6000);
+
+        // Without a voltage reading there's no power to estimate from
+        let btm = compute_btm(&Btm { discharge_rate: 0 }, &cache(2500, -1000, 0));
+        assert_eq!(u32::from(btm), TIME_UNKNOWN_S);
+    }
+
+    /// `_BCT` handles an already-reached level, a battery that isn't charging, and invalid levels.
+    #[test]
+    fn compute_bct_edge_cases() {
+        // Already full
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 100,
+            },
+            &time_estimate_cache(5000, 0),
+        );
+        assert_eq!(u32::from(bct), 0);
+
+        // Already above the requested level
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 40,
+            },
+            &time_estimate_cache(2500, 1000),
+        );
+        assert_eq!(u32::from(bct), 0);
+
+        // Not charging
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 80,
+            },
+            &time_estimate_cache(2500, -1000),
+        );
+        assert_eq!(u32::from(bct), TIME_UNKNOWN_S);
+
+        // Invalid level
+        let bct = compute_bct(
+            &Bct {
+                charge_level_percent: 0,
+            },
+            &time_estimate_cache(2500, 1000),
+        );
+        assert_eq!(u32::from(bct), TIME_UNKNOWN_S);
+    }
+
+    /// Removing the battery (e.g. it stops responding on the bus) clears presence and stops capacity reporting.
+    #[test]
+    fn battery_removal() {
+        let mut state: State = State::default();
+        assert!(!state.on_removed());
+
+        state.on_initialized();
+        state.on_dynamic_data(|cache| *cache = time_estimate_cache(2500, -1000));
+        assert!(state.is_present());
+        assert!(compute_sta(state.is_present()).contains(StaReturn::BATTERY_PRESENT));
+        assert_eq!(compute_bst(state.dynamic_cache()).battery_remaining_capacity, 2500);
+
+        // Bus error, the driver reports the battery as removed
+        assert!(state.on_removed());
+        assert!(!state.is_present());
+        assert!(!state.on_removed());
+        assert!(!compute_sta(state.is_present()).contains(StaReturn::BATTERY_PRESENT));
+
+        // The stale cache is still there but isn't reported
+        assert_eq!(
+            state.dynamic_cache().remaining_capacity,
+            CapacityModeValue::MilliAmpUnsigned(2500)
+        );
+        let bst = compute_bst_absent();
+        assert_eq!(bst.battery_remaining_capacity, 0);
+        assert_eq!(bst.battery_present_rate, 0);
+        assert_eq!(bst.battery_present_voltage, 0);
+
+        // Reinsertion brings the battery back through initialization
+        state.on_initialized();
+        assert!(state.is_present());
+    }
+
+    /// Averaging smooths noisy current and voltage readings in `_BST`, while the instantaneous readings stay cached.
+    #[test]
+    fn bst_averaging() {
+        let mut state: State = State::default();
+        state.on_initialized();
+        let sample = |current, voltage| {
+            move |cache: &mut DynamicBatteryMsgs| {
+                cache.current = current;
+                cache.voltage = voltage;
+            }
+        };
+
+        // Averaging is disabled by default
+        state.on_dynamic_data(sample(-1000, 12000));
+        state.on_dynamic_data(sample(-3000, 11000));
+        let bst = compute_bst_averaged(&state);
+        assert_eq!(bst.battery_present_rate, 3000);
+        assert_eq!(bst.battery_present_voltage, 11000);
+
+        // Noisy readings around -2000 mA and 11500 mV are smoothed
+        state.set_averaging_window(4);
+        assert_eq!(state.averaging_window(), 4);
+        for (current, voltage) in [(-1000, 12000), (-3000, 11000), (-1500, 11800), (-2500, 11200)] {
+            state.on_dynamic_data(sample(current, voltage));
+        }
+        let bst = compute_bst_averaged(&state);
+        assert_eq!(bst.battery_present_rate, 2000);
+        assert_eq!(bst.battery_present_voltage, 11500);
+
+        // Instantaneous readings are still available
+        assert_eq!(state.dynamic_cache().current, -2500);
+        assert_eq!(compute_bst(state.dynamic_cache()).battery_present_rate, 2500);
+
+        // Only the most recent window is averaged
+        for _ in 0..4 {
+            state.on_dynamic_data(sample(-4000, 10000));
+        }
+        let bst = compute_bst_averaged(&state);
+        assert_eq!(bst.battery_present_rate, 4000);
+        assert_eq!(bst.battery_present_voltage, 10000);
+
+        // Oversized windows are clamped, and removal discards collected samples
+        state.set_averaging_window(usize::MAX);
+        assert_eq!(state.averaging_window(), MAX_AVERAGING_WINDOW);
+        state.on_dynamic_data(sample(-100, 9000));
+        state.on_removed();
+        state.on_initialized();
+        state.on_dynamic_data(sample(-200, 8000));
+        assert_eq!(state.averaged_current(), -200);
+        assert_eq!(state.averaged_voltage(), 8000);
+    }
 }
@@ -0,0 +1,248 @@
+//! Charging profile selection.
+//!
+//! A charging profile trades charge speed against battery longevity. The OEM registers the parameters for each
+//! profile supported by their battery through a [`ChargeProfileSelector`], which programs the charger when a profile
+//! is selected.
+use embedded_batteries_async::charger::{Charger, MilliAmps, MilliVolts};
+use embedded_services::info;
+
+use crate::charger::ChargeCurrentControl;
+
+/// Name of a charging profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChargeProfileName {
+    /// Balanced charge speed and battery longevity.
+    Standard,
+    /// Prioritizes battery longevity, typically by charging slower and stopping short of full.
+    Adaptive,
+    /// Prioritizes charge speed.
+    Express,
+    /// OEM-defined profile.
+    Oem(u8),
+}
+
+/// Charger parameters applied when a profile is selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargeProfile {
+    /// Profile name.
+    pub name: ChargeProfileName,
+    /// Target charge voltage.
+    pub charge_voltage: MilliVolts,
+    /// Target charge current.
+    pub charge_current: MilliAmps,
+    /// State of charge in percent at which charging stops.
+    pub stop_charge_percent: u8,
+    /// State of charge in percent below which charging resumes after being stopped.
+    pub resume_charge_percent: u8,
+}
+
+impl ChargeProfile {
+    /// Returns true if charging should be enabled at `state_of_charge` percent.
+    ///
+    /// Charging stops at [`Self::stop_charge_percent`] and doesn't resume until the state of charge falls below
+    /// [`Self::resume_charge_percent`], `charging` is whether charging is currently enabled.
+    pub fn charge_enabled(&self, state_of_charge: u8, charging: bool) -> bool {
+        if charging {
+            state_of_charge < self.stop_charge_percent
+        } else {
+            state_of_charge < self.resume_charge_percent
+        }
+    }
+}
+
+/// Charge profile selection error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChargeProfileError<E> {
+    /// No profile with the requested name is registered.
+    UnknownProfile,
+    /// The charger rejected the profile's parameters.
+    Charger(E),
+}
+
+/// Selects between OEM-registered charging profiles and programs the charger accordingly.
+pub struct ChargeProfileSelector<'a> {
+    profiles: &'a [ChargeProfile],
+    selected: Option<ChargeProfileName>,
+}
+
+impl<'a> ChargeProfileSelector<'a> {
+    /// Create a new selector over `profiles`, no profile is selected until [`Self::set_charge_profile`] is called.
+    pub const fn new(profiles: &'a [ChargeProfile]) -> Self {
+        Self {
+            profiles,
+            selected: None,
+        }
+    }
+
+    /// Returns the registered profiles.
+    pub fn profiles(&self) -> &'a [ChargeProfile] {
+        self.profiles
+    }
+
+    /// Look up a registered profile by name.
+    pub fn get(&self, name: ChargeProfileName) -> Option<&'a ChargeProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Returns the currently selected profile, if any.
+    pub fn selected(&self) -> Option<&'a ChargeProfile> {
+        self.selected.and_then(|name| self.get(name))
+    }
+
+    /// Select the profile `name` and program its charge voltage and current into `charger`.
+    ///
+    /// The charge current goes through `current_control` so a configured ramp is respected. The selection is only
+    /// updated once the charger has accepted both parameters.
+    pub async fn set_charge_profile<C: Charger>(
+        &mut self,
+        charger: &mut C,
+        current_control: &mut ChargeCurrentControl,
+        name: ChargeProfileName,
+    ) -> Result<&'a ChargeProfile, ChargeProfileError<C::Error>> {
+        let profile = self.get(name).ok_or(ChargeProfileError::UnknownProfile)?;
+
+        charger
+            .charging_voltage(profile.charge_voltage)
+            .await
+            .map_err(ChargeProfileError::Charger)?;
+        current_control
+            .set_charge_current(charger, profile.charge_current)
+            .await
+            .map_err(ChargeProfileError::Charger)?;
+
+        info!("Charge profile set to {:?}", name);
+        self.selected = Some(name);
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_batteries_async::charger::{self, Charger, ErrorType, MilliAmps, MilliVolts};
+
+    use super::{ChargeProfile, ChargeProfileError, ChargeProfileName, ChargeProfileSelector};
+    use crate::charger::ChargeCurrentControl;
+
+    const STANDARD: ChargeProfile = ChargeProfile {
+        name: ChargeProfileName::Standard,
+        charge_voltage: 8700,
+        charge_current: 2000,
+        stop_charge_percent: 100,
+        resume_charge_percent: 95,
+    };
+    const ADAPTIVE: ChargeProfile = ChargeProfile {
+        name: ChargeProfileName::Adaptive,
+        charge_voltage: 8400,
+        charge_current: 1000,
+        stop_charge_percent: 80,
+        resume_charge_percent: 75,
+    };
+    const OEM: ChargeProfile = ChargeProfile {
+        name: ChargeProfileName::Oem(0),
+        charge_voltage: 8800,
+        charge_current: 4000,
+        stop_charge_percent: 100,
+        resume_charge_percent: 90,
+    };
+    const PROFILES: [ChargeProfile; 3] = [STANDARD, ADAPTIVE, OEM];
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct ChargerFault;
+
+    impl charger::Error for ChargerFault {
+        fn kind(&self) -> charger::ErrorKind {
+            charger::ErrorKind::Other
+        }
+    }
+
+    /// Charger that records the last voltage and current it's given.
+    #[derive(Default)]
+    struct RecordingCharger {
+        voltage: Option<MilliVolts>,
+        current: Option<MilliAmps>,
+        fail: bool,
+    }
+
+    impl ErrorType for RecordingCharger {
+        type Error = ChargerFault;
+    }
+
+    impl Charger for RecordingCharger {
+        async fn charging_current(&mut self, current: MilliAmps) -> Result<MilliAmps, Self::Error> {
+            self.current = Some(current);
+            Ok(current)
+        }
+
+        async fn charging_voltage(&mut self, voltage: MilliVolts) -> Result<MilliVolts, Self::Error> {
+            if self.fail {
+                return Err(ChargerFault);
+            }
+            self.voltage = Some(voltage);
+            Ok(voltage)
+        }
+    }
+
+    #[tokio::test]
+    async fn switching_profiles_programs_charger() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None);
+        let mut selector = ChargeProfileSelector::new(&PROFILES);
+        assert_eq!(selector.selected(), None);
+
+        for profile in &PROFILES {
+            assert_eq!(
+                selector
+                    .set_charge_profile(&mut charger, &mut control, profile.name)
+                    .await,
+                Ok(profile)
+            );
+            assert_eq!(charger.voltage, Some(profile.charge_voltage));
+            assert_eq!(charger.current, Some(profile.charge_current));
+            assert_eq!(selector.selected(), Some(profile));
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_selection_keeps_previous_profile() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None);
+        let mut selector = ChargeProfileSelector::new(&PROFILES);
+
+        assert_eq!(
+            selector
+                .set_charge_profile(&mut charger, &mut control, ChargeProfileName::Standard)
+                .await,
+            Ok(&STANDARD)
+        );
+
+        // Express isn't registered
+        assert_eq!(
+            selector
+                .set_charge_profile(&mut charger, &mut control, ChargeProfileName::Express)
+                .await,
+            Err(ChargeProfileError::UnknownProfile)
+        );
+        assert_eq!(selector.selected().map(|p| p.name), Some(ChargeProfileName::Standard));
+
+        charger.fail = true;
+        assert_eq!(
+            selector
+                .set_charge_profile(&mut charger, &mut control, ChargeProfileName::Adaptive)
+                .await,
+            Err(ChargeProfileError::Charger(ChargerFault))
+        );
+        assert_eq!(selector.selected().map(|p| p.name), Some(ChargeProfileName::Standard));
+    }
+
+    #[test]
+    fn charge_thresholds_have_hysteresis() {
+        assert!(ADAPTIVE.charge_enabled(79, true));
+        assert!(!ADAPTIVE.charge_enabled(80, true));
+        // Stopped charging doesn't resume until below the resume threshold
+        assert!(!ADAPTIVE.charge_enabled(78, false));
+        assert!(ADAPTIVE.charge_enabled(74, false));
+    }
+}
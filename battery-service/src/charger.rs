@@ -0,0 +1,360 @@
+//! Charge current control.
+use embassy_time::{Duration, Timer};
+use embedded_batteries_async::charger::{Charger, MilliAmps};
+use embedded_batteries_async::smart_battery::DeciKelvin;
+use embedded_services::{info, warn};
+
+/// Charge current ramp configuration.
+///
+/// Increases in charge current are applied in `steps` equal increments spread evenly over `duration`
+/// rather than as a single jump. Decreases are always applied immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargeCurrentRamp {
+    /// Total time taken to reach the target current.
+    pub duration: Duration,
+    /// Number of increments used to reach the target current, zero is treated as one.
+    pub steps: u16,
+}
+
+/// Charger over-temperature throttling configuration.
+///
+/// Once the charger temperature exceeds `limit` the charge current is capped at `throttled_current`. The cap is
+/// lifted when the temperature falls below `limit - hysteresis`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargerThermalThrottle {
+    /// Charger temperature above which the charge current is throttled.
+    pub limit: DeciKelvin,
+    /// Temperature drop below `limit` required before the throttle is released.
+    pub hysteresis: DeciKelvin,
+    /// Maximum charge current while throttled.
+    pub throttled_current: MilliAmps,
+}
+
+/// Tracks the charge current programmed into a charger and optionally ramps increases to limit inrush.
+pub struct ChargeCurrentControl {
+    ramp: Option<ChargeCurrentRamp>,
+    present: MilliAmps,
+    thermal_throttle: Option<ChargerThermalThrottle>,
+    throttled: bool,
+    requested: MilliAmps,
+}
+
+impl ChargeCurrentControl {
+    /// Create a new instance, the charger is assumed to start with no charge current.
+    pub const fn new(ramp: Option<ChargeCurrentRamp>) -> Self {
+        Self {
+            ramp,
+            present: 0,
+            thermal_throttle: None,
+            throttled: false,
+            requested: 0,
+        }
+    }
+
+    /// Throttle the charge current based on the temperatures given to [`Self::on_charger_temperature`].
+    pub const fn with_thermal_throttle(mut self, thermal_throttle: ChargerThermalThrottle) -> Self {
+        self.thermal_throttle = Some(thermal_throttle);
+        self
+    }
+
+    /// Returns the charge current most recently accepted by the charger.
+    pub fn present(&self) -> MilliAmps {
+        self.present
+    }
+
+    /// Returns true if the charge current is currently reduced because the charger is too hot.
+    pub fn is_throttled(&self) -> bool {
+        self.throttled
+    }
+
+    /// Update the throttle state from a charger temperature reading.
+    ///
+    /// The charger interface doesn't report its own temperature, so callers read it from whatever sensor the
+    /// platform provides. When the throttle engages or releases, the most recently requested charge current is
+    /// reapplied with the new cap. Returns the current accepted by the charger.
+    pub async fn on_charger_temperature<C: Charger>(
+        &mut self,
+        charger: &mut C,
+        temperature: DeciKelvin,
+    ) -> Result<MilliAmps, C::Error> {
+        let Some(throttle) = self.thermal_throttle else {
+            return Ok(self.present);
+        };
+
+        let throttled = if self.throttled {
+            temperature >= throttle.limit.saturating_sub(throttle.hysteresis)
+        } else {
+            temperature > throttle.limit
+        };
+        if throttled == self.throttled {
+            return Ok(self.present);
+        }
+
+        if throttled {
+            warn!(
+                "Charger temperature {} dK over limit, throttling charge current",
+                temperature
+            );
+        } else {
+            info!(
+                "Charger temperature {} dK back within limit, restoring charge current",
+                temperature
+            );
+        }
+        // Only change state once the charger accepts the new current, so a failure is retried on the next reading
+        let present = self.apply_charge_current(charger, throttled).await?;
+        self.throttled = throttled;
+        Ok(present)
+    }
+
+    /// Set the charge current, stepping from the present current to `target` if a ramp is configured.
+    ///
+    /// While throttled the charger is given at most the throttled current, `target` is still remembered and
+    /// applied once the throttle is released. Returns the current accepted by the charger for the final step.
+    /// If a step fails the present current reflects the last step that succeeded.
+    pub async fn set_charge_current<C: Charger>(
+        &mut self,
+        charger: &mut C,
+        target: MilliAmps,
+    ) -> Result<MilliAmps, C::Error> {
+        self.requested = target;
+        self.apply_charge_current(charger, self.throttled).await
+    }
+
+    /// Apply the requested charge current, capped if `throttled`.
+    async fn apply_charge_current<C: Charger>(
+        &mut self,
+        charger: &mut C,
+        throttled: bool,
+    ) -> Result<MilliAmps, C::Error> {
+        let target = match self.thermal_throttle {
+            Some(throttle) if throttled => self.requested.min(throttle.throttled_current),
+            _ => self.requested,
+        };
+
+        let ramp = match self.ramp {
+            Some(ramp) if target > self.present => ramp,
+            _ => {
+                self.present = charger.charging_current(target).await?;
+                return Ok(self.present);
+            }
+        };
+
+        let steps = ramp.steps.max(1);
+        let interval = ramp.duration / u32::from(steps);
+        let start = self.present;
+        let delta = u32::from(target - start);
+        for step in 1..=steps {
+            let increment = delta * u32::from(step) / u32::from(steps);
+            let current = start.saturating_add(u16::try_from(increment).unwrap_or(u16::MAX));
+            self.present = charger.charging_current(current).await?;
+            if step < steps {
+                Timer::after(interval).await;
+            }
+        }
+
+        Ok(self.present)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_time::{Duration, Instant};
+    use embedded_batteries_async::charger::{self, Charger, ErrorType, MilliAmps, MilliVolts};
+
+    use super::{ChargeCurrentControl, ChargeCurrentRamp, ChargerThermalThrottle};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct ChargerFault;
+
+    impl charger::Error for ChargerFault {
+        fn kind(&self) -> charger::ErrorKind {
+            charger::ErrorKind::Other
+        }
+    }
+
+    /// Charger that records every charge current it accepts.
+    #[derive(Default)]
+    struct RecordingCharger {
+        currents: heapless::Vec<MilliAmps, 16>,
+        fail: bool,
+    }
+
+    impl ErrorType for RecordingCharger {
+        type Error = ChargerFault;
+    }
+
+    impl Charger for RecordingCharger {
+        async fn charging_current(&mut self, current: MilliAmps) -> Result<MilliAmps, Self::Error> {
+            if self.fail {
+                return Err(ChargerFault);
+            }
+            let _ = self.currents.push(current);
+            Ok(current)
+        }
+
+        async fn charging_voltage(&mut self, voltage: MilliVolts) -> Result<MilliVolts, Self::Error> {
+            Ok(voltage)
+        }
+    }
+
+    #[tokio::test]
+    async fn no_ramp_applies_target_directly() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None);
+
+        assert_eq!(control.set_charge_current(&mut charger, 3000).await, Ok(3000));
+        assert_eq!(charger.currents.as_slice(), &[3000]);
+        assert_eq!(control.present(), 3000);
+    }
+
+    #[tokio::test]
+    async fn ramp_steps_up_to_target() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(Some(ChargeCurrentRamp {
+            duration: Duration::from_millis(40),
+            steps: 4,
+        }));
+
+        let start = Instant::now();
+        assert_eq!(control.set_charge_current(&mut charger, 2000).await, Ok(2000));
+        assert_eq!(charger.currents.as_slice(), &[500, 1000, 1500, 2000]);
+        // Three intervals between the four steps
+        assert!(start.elapsed() >= Duration::from_millis(30));
+
+        // Ramps start from the present current
+        charger.currents.clear();
+        assert_eq!(control.set_charge_current(&mut charger, 3000).await, Ok(3000));
+        assert_eq!(charger.currents.as_slice(), &[2250, 2500, 2750, 3000]);
+    }
+
+    #[tokio::test]
+    async fn ramp_decrease_is_immediate() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(Some(ChargeCurrentRamp {
+            duration: Duration::from_millis(20),
+            steps: 2,
+        }));
+
+        assert_eq!(control.set_charge_current(&mut charger, 1000).await, Ok(1000));
+        charger.currents.clear();
+
+        assert_eq!(control.set_charge_current(&mut charger, 200).await, Ok(200));
+        assert_eq!(charger.currents.as_slice(), &[200]);
+    }
+
+    #[tokio::test]
+    async fn thermal_throttle_reduces_and_restores_current() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None).with_thermal_throttle(ChargerThermalThrottle {
+            limit: 3531,
+            hysteresis: 50,
+            throttled_current: 1000,
+        });
+
+        assert_eq!(control.set_charge_current(&mut charger, 3000).await, Ok(3000));
+        charger.currents.clear();
+
+        // At the limit nothing changes
+        assert_eq!(control.on_charger_temperature(&mut charger, 3531).await, Ok(3000));
+        assert!(!control.is_throttled());
+        assert!(charger.currents.is_empty());
+
+        // Over the limit the current is reduced
+        assert_eq!(control.on_charger_temperature(&mut charger, 3541).await, Ok(1000));
+        assert!(control.is_throttled());
+        assert_eq!(charger.currents.as_slice(), &[1000]);
+
+        // New requests are capped while throttled
+        assert_eq!(control.set_charge_current(&mut charger, 2500).await, Ok(1000));
+
+        // Within the hysteresis band the throttle holds
+        charger.currents.clear();
+        assert_eq!(control.on_charger_temperature(&mut charger, 3500).await, Ok(1000));
+        assert!(control.is_throttled());
+        assert!(charger.currents.is_empty());
+
+        // Below the band the latest request is restored
+        assert_eq!(control.on_charger_temperature(&mut charger, 3480).await, Ok(2500));
+        assert!(!control.is_throttled());
+        assert_eq!(charger.currents.as_slice(), &[2500]);
+    }
+
+    #[tokio::test]
+    async fn thermal_throttle_retried_after_charger_failure() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None).with_thermal_throttle(ChargerThermalThrottle {
+            limit: 3531,
+            hysteresis: 50,
+            throttled_current: 1000,
+        });
+
+        assert_eq!(control.set_charge_current(&mut charger, 3000).await, Ok(3000));
+        charger.currents.clear();
+
+        // The throttle doesn't engage if the charger rejects the reduced current
+        charger.fail = true;
+        assert_eq!(
+            control.on_charger_temperature(&mut charger, 3600).await,
+            Err(ChargerFault)
+        );
+        assert!(!control.is_throttled());
+
+        // So the next reading tries again
+        charger.fail = false;
+        assert_eq!(control.on_charger_temperature(&mut charger, 3600).await, Ok(1000));
+        assert!(control.is_throttled());
+        assert_eq!(charger.currents.as_slice(), &[1000]);
+
+        // Likewise the throttle holds if restoring the current fails
+        charger.fail = true;
+        assert_eq!(
+            control.on_charger_temperature(&mut charger, 3400).await,
+            Err(ChargerFault)
+        );
+        assert!(control.is_throttled());
+
+        charger.fail = false;
+        assert_eq!(control.on_charger_temperature(&mut charger, 3400).await, Ok(3000));
+        assert!(!control.is_throttled());
+    }
+
+    #[tokio::test]
+    async fn thermal_throttle_keeps_lower_request() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None).with_thermal_throttle(ChargerThermalThrottle {
+            limit: 3531,
+            hysteresis: 50,
+            throttled_current: 1000,
+        });
+
+        assert_eq!(control.set_charge_current(&mut charger, 500).await, Ok(500));
+        assert_eq!(control.on_charger_temperature(&mut charger, 3600).await, Ok(500));
+        assert!(control.is_throttled());
+    }
+
+    #[tokio::test]
+    async fn no_thermal_throttle_ignores_temperature() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(None);
+
+        assert_eq!(control.set_charge_current(&mut charger, 3000).await, Ok(3000));
+        assert_eq!(control.on_charger_temperature(&mut charger, 4000).await, Ok(3000));
+        assert!(!control.is_throttled());
+        assert_eq!(charger.currents.as_slice(), &[3000]);
+    }
+
+    #[tokio::test]
+    async fn ramp_with_zero_steps_is_single_step() {
+        let mut charger = RecordingCharger::default();
+        let mut control = ChargeCurrentControl::new(Some(ChargeCurrentRamp {
+            duration: Duration::from_millis(20),
+            steps: 0,
+        }));
+
+        assert_eq!(control.set_charge_current(&mut charger, 1500).await, Ok(1500));
+        assert_eq!(charger.currents.as_slice(), &[1500]);
+    }
+}
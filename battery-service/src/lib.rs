@@ -5,10 +5,12 @@ use battery_service_interface::{
     BtmReturnResult, Btp, PifFixedStrings, PsrReturn, StaReturn,
 };
 use core::marker::PhantomData;
-use embedded_services::info;
 use embedded_services::sync::Lockable;
+use embedded_services::{error, info};
 
 mod acpi;
+pub mod charge_profile;
+pub mod charger;
 #[cfg(feature = "mock")]
 pub mod mock;
 pub mod registration;
@@ -53,6 +55,20 @@ impl<'hw, Reg: Registration<'hw>> Service<'hw, Reg> {
     pub fn get_fuel_gauge(&self, id: DeviceId) -> Option<&'hw Reg::FuelGauge> {
         self.registration.get_fuel_gauge(id)
     }
+
+    /// Immediately re-sample a battery's dynamic data and return the refreshed _BST.
+    ///
+    /// Lets a caller that needs up-to-date readings (e.g. right after a charger plug event) skip waiting for the
+    /// OEM's next periodic [`FuelGauge::update_dynamic_data`] poll. The fresh sample is cached like any other, so
+    /// subsequent ACPI queries see it too.
+    pub async fn refresh_now(&self, id: DeviceId) -> Result<BstReturn, BatteryError> {
+        let mut fuel_gauge = self.fuel_gauge(id)?.lock().await;
+        fuel_gauge.update_dynamic_data().await.map_err(|_| {
+            error!("Battery service: failed to refresh dynamic data");
+            BatteryError::UnspecifiedFailure
+        })?;
+        self.battery_status(&mut *fuel_gauge)
+    }
 }
 
 impl<'hw, Reg: Registration<'hw>> battery_service_interface::BatteryService for Service<'hw, Reg> {
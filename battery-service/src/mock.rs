@@ -64,6 +64,19 @@ fn padded<const N: usize>(bytes: &[u8]) -> [u8; N] {
     arr
 }
 
+/// Synthetic readings that can be injected into a [`MockFuelGauge`] in place of values read from hardware.
+#[derive(Clone, Copy, Debug)]
+pub struct SyntheticReadings {
+    /// Remaining capacity, in the units selected by the battery's capacity mode
+    pub remaining_capacity: smart_battery::CapacityModeValue,
+    /// Pack voltage
+    pub voltage: smart_battery::MilliVolts,
+    /// Pack current, negative while discharging
+    pub current: smart_battery::MilliAmpsSigned,
+    /// Whether the battery is charging rather than discharging
+    pub charging: bool,
+}
+
 /// A mock fuel gauge that manages its own state and produces static, arbitrary data.
 pub struct MockFuelGauge {
     state: State,
@@ -228,6 +241,23 @@ impl MockFuelGauge {
         MockFuelGauge { state }
     }
 
+    /// Inject synthetic readings as if they had just been read from hardware.
+    ///
+    /// The readings go through [`State::on_dynamic_data`] like a real poll, so ACPI queries (including measurement
+    /// averaging) report them exactly as they would hardware values. Since the mock reads its "hardware" back from
+    /// its cache, the readings also survive later calls to [`FuelGauge::update_dynamic_data`].
+    pub fn inject_readings(&mut self, readings: SyntheticReadings) {
+        self.state.on_dynamic_data(|d| {
+            d.remaining_capacity = readings.remaining_capacity;
+            d.voltage = readings.voltage;
+            d.current = readings.current;
+            d.average_current = readings.current;
+            d.battery_status = smart_battery::BatteryStatusFields::from(d.battery_status)
+                .with_discharging(!readings.charging)
+                .into();
+        });
+    }
+
     async fn set_capacity_bit(&mut self, mwh: bool) -> Result<(), MockBatteryError> {
         let battery_mode = self.battery_mode().await?;
         SmartBattery::set_battery_mode(self, battery_mode.with_capacity_mode(mwh)).await?;
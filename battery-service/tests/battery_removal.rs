@@ -0,0 +1,58 @@
+// Panicking is how tests communicate failure, so we need to allow it here.
+#![allow(clippy::unwrap_used)]
+
+use battery_service::mock::{MockFuelGauge, SyntheticReadings};
+use battery_service::{ArrayRegistration, BatteryService, DeviceId, FuelGauge, Service};
+use embassy_sync::mutex::Mutex;
+use embedded_batteries_async::acpi::StaReturn;
+use embedded_batteries_async::smart_battery::CapacityModeValue;
+use embedded_services::GlobalRawMutex;
+
+type FuelGaugeType = Mutex<GlobalRawMutex, MockFuelGauge>;
+
+#[tokio::test]
+async fn removed_battery_reports_absent() {
+    let fuel_gauge: FuelGaugeType = Mutex::new(MockFuelGauge::new());
+    let service = Service::new(ArrayRegistration {
+        fuel_gauges: [&fuel_gauge],
+    });
+    {
+        let mut fg = fuel_gauge.lock().await;
+        fg.initialize().await.unwrap();
+        fg.update_static_data().await.unwrap();
+        fg.inject_readings(SyntheticReadings {
+            remaining_capacity: CapacityModeValue::MilliAmpUnsigned(1_500),
+            voltage: 11_400,
+            current: -1_000,
+            charging: false,
+        });
+    }
+
+    let sta = BatteryService::device_status(&service, DeviceId(0)).await.unwrap();
+    assert!(sta.contains(StaReturn::BATTERY_PRESENT));
+    let bst = BatteryService::battery_status(&service, DeviceId(0)).await.unwrap();
+    assert_eq!(bst.battery_remaining_capacity, 1_500);
+    assert_eq!(bst.battery_present_rate, 1_000);
+    assert_eq!(bst.battery_present_voltage, 11_400);
+
+    // The driver reports the battery as removed, e.g. after a bus error
+    assert!(fuel_gauge.lock().await.state_mut().on_removed());
+
+    let sta = BatteryService::device_status(&service, DeviceId(0)).await.unwrap();
+    assert!(!sta.contains(StaReturn::BATTERY_PRESENT));
+    let bst = BatteryService::battery_status(&service, DeviceId(0)).await.unwrap();
+    assert_eq!(bst.battery_remaining_capacity, 0);
+    assert_eq!(bst.battery_present_rate, 0);
+    assert_eq!(bst.battery_present_voltage, 0);
+
+    // Reinsertion brings the battery back through initialization
+    {
+        let mut fg = fuel_gauge.lock().await;
+        fg.initialize().await.unwrap();
+        fg.update_dynamic_data().await.unwrap();
+    }
+    let sta = BatteryService::device_status(&service, DeviceId(0)).await.unwrap();
+    assert!(sta.contains(StaReturn::BATTERY_PRESENT));
+    let bst = BatteryService::battery_status(&service, DeviceId(0)).await.unwrap();
+    assert_eq!(bst.battery_remaining_capacity, 1_500);
+}
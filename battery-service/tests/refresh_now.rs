@@ -0,0 +1,65 @@
+// Panicking is how tests communicate failure, so we need to allow it here.
+#![allow(clippy::unwrap_used)]
+
+use battery_service::mock::{MockFuelGauge, SyntheticReadings};
+use battery_service::{ArrayRegistration, BatteryService, DeviceId, FuelGauge, Service};
+use battery_service_interface::BatteryError;
+use embassy_sync::mutex::Mutex;
+use embedded_batteries_async::acpi::BatteryState;
+use embedded_batteries_async::smart_battery::CapacityModeValue;
+use embedded_services::GlobalRawMutex;
+
+type FuelGaugeType = Mutex<GlobalRawMutex, MockFuelGauge>;
+
+/// Peak power the mock fuel gauge reports once its dynamic data has been sampled.
+const SAMPLED_PEAK_POWER_MW: u32 = 100;
+
+#[tokio::test]
+async fn refresh_now_samples_fresh_dynamic_data() {
+    let fuel_gauge: FuelGaugeType = Mutex::new(MockFuelGauge::new());
+    let service = Service::new(ArrayRegistration {
+        fuel_gauges: [&fuel_gauge],
+    });
+    {
+        let mut fg = fuel_gauge.lock().await;
+        fg.initialize().await.unwrap();
+        fg.update_static_data().await.unwrap();
+    }
+
+    // Nothing has polled the dynamic data yet
+    let bps = BatteryService::battery_power_state(&service, DeviceId(0))
+        .await
+        .unwrap();
+    assert_ne!(bps.instantaneous_peak_power_level, SAMPLED_PEAK_POWER_MW);
+
+    fuel_gauge.lock().await.inject_readings(SyntheticReadings {
+        remaining_capacity: CapacityModeValue::MilliAmpUnsigned(1_500),
+        voltage: 11_400,
+        current: -1_000,
+        charging: false,
+    });
+    let bst = service.refresh_now(DeviceId(0)).await.unwrap();
+    assert_eq!(bst.battery_state, BatteryState::DISCHARGING);
+    assert_eq!(bst.battery_remaining_capacity, 1_500);
+    assert_eq!(bst.battery_present_rate, 1_000);
+    assert_eq!(bst.battery_present_voltage, 11_400);
+
+    // The refresh went through the fuel gauge and is visible to later queries
+    let bps = BatteryService::battery_power_state(&service, DeviceId(0))
+        .await
+        .unwrap();
+    assert_eq!(bps.instantaneous_peak_power_level, SAMPLED_PEAK_POWER_MW);
+}
+
+#[tokio::test]
+async fn refresh_now_unknown_device() {
+    let fuel_gauge: FuelGaugeType = Mutex::new(MockFuelGauge::new());
+    let service = Service::new(ArrayRegistration {
+        fuel_gauges: [&fuel_gauge],
+    });
+
+    assert!(matches!(
+        service.refresh_now(DeviceId(1)).await,
+        Err(BatteryError::UnknownDeviceId)
+    ));
+}
@@ -0,0 +1,67 @@
+// Panicking is how tests communicate failure, so we need to allow it here.
+#![allow(clippy::unwrap_used)]
+
+use battery_service::mock::{MockFuelGauge, SyntheticReadings, init_state_machine};
+use battery_service::{ArrayRegistration, BatteryService, DeviceId, FuelGauge, Service};
+use embassy_sync::mutex::Mutex;
+use embedded_batteries_async::acpi::BatteryState;
+use embedded_batteries_async::smart_battery::CapacityModeValue;
+use embedded_services::GlobalRawMutex;
+
+type FuelGaugeType = Mutex<GlobalRawMutex, MockFuelGauge>;
+
+const CHARGING: SyntheticReadings = SyntheticReadings {
+    remaining_capacity: CapacityModeValue::MilliAmpUnsigned(1_234),
+    voltage: 12_100,
+    current: 2_000,
+    charging: true,
+};
+
+const DISCHARGING: SyntheticReadings = SyntheticReadings {
+    remaining_capacity: CapacityModeValue::MilliAmpUnsigned(567),
+    voltage: 10_800,
+    current: -3_000,
+    charging: false,
+};
+
+#[tokio::test]
+async fn synthetic_readings_reported_through_bst() {
+    let fuel_gauge: FuelGaugeType = Mutex::new(MockFuelGauge::new());
+    let service = Service::new(ArrayRegistration {
+        fuel_gauges: [&fuel_gauge],
+    });
+    init_state_machine(&fuel_gauge).await.unwrap();
+
+    for (readings, expected_capacity) in [(CHARGING, 1_234), (DISCHARGING, 567)] {
+        fuel_gauge.lock().await.inject_readings(readings);
+
+        let bst = BatteryService::battery_status(&service, DeviceId(0)).await.unwrap();
+        let expected_state = if readings.charging {
+            BatteryState::CHARGING
+        } else {
+            BatteryState::DISCHARGING
+        };
+        assert_eq!(bst.battery_state, expected_state);
+        assert_eq!(bst.battery_remaining_capacity, expected_capacity);
+        assert_eq!(bst.battery_present_rate, u32::from(readings.current.unsigned_abs()));
+        assert_eq!(bst.battery_present_voltage, u32::from(readings.voltage));
+    }
+}
+
+#[tokio::test]
+async fn synthetic_readings_survive_polling() {
+    let fuel_gauge: FuelGaugeType = Mutex::new(MockFuelGauge::new());
+    let service = Service::new(ArrayRegistration {
+        fuel_gauges: [&fuel_gauge],
+    });
+    init_state_machine(&fuel_gauge).await.unwrap();
+
+    fuel_gauge.lock().await.inject_readings(DISCHARGING);
+    fuel_gauge.lock().await.update_dynamic_data().await.unwrap();
+
+    let bst = BatteryService::battery_status(&service, DeviceId(0)).await.unwrap();
+    assert_eq!(bst.battery_state, BatteryState::DISCHARGING);
+    assert_eq!(bst.battery_remaining_capacity, 567);
+    assert_eq!(bst.battery_present_rate, 3_000);
+    assert_eq!(bst.battery_present_voltage, 10_800);
+}
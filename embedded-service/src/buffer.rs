@@ -32,7 +32,8 @@ pub enum Error {
     Poisoned,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 enum Status {
     None,
     Mutable,
@@ -112,6 +113,23 @@ impl<'a, T> Buffer<'a, T> {
     }
 }
 
+// Debug output reads the status cell directly so that formatting never acquires a borrow
+impl<T> core::fmt::Debug for Buffer<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Buffer")
+            .field("status", &self.status.get())
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T> defmt::Format for Buffer<'_, T> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "Buffer {{ status: {}, len: {} }}", self.status.get(), self.len)
+    }
+}
+
 /// A mutable, owned reference to a buffer
 pub struct OwnedRef<'a, T>(&'a Buffer<'a, T>);
 
@@ -146,6 +164,27 @@ impl<'a, T> OwnedRef<'a, T> {
     }
 }
 
+impl<T> core::fmt::Debug for OwnedRef<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OwnedRef")
+            .field("status", &self.0.status.get())
+            .field("len", &self.0.len)
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T> defmt::Format for OwnedRef<'_, T> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "OwnedRef {{ status: {}, len: {} }}",
+            self.0.status.get(),
+            self.0.len
+        )
+    }
+}
+
 /// Guard struct for mutable buffer access
 pub struct AccessMut<'a, T>(&'a Buffer<'a, T>);
 
@@ -324,6 +363,7 @@ macro_rules! define_static_buffer {
 mod test {
     extern crate std;
     use super::*;
+    use std::format;
 
     // Verify that only one mutable borrow is allowed
     #[test]
@@ -422,4 +462,29 @@ mod test {
 
         let _slice = buffer.reference().slice(0..9).unwrap();
     }
+
+    // Verify the debug output tracks the borrow status without taking a borrow itself
+    #[test]
+    fn test_debug_status() {
+        define_static_buffer!(buffer, u8, [0; 16]);
+        let buffer = buffer::get_mut().unwrap();
+        assert_eq!(format!("{buffer:?}"), "OwnedRef { status: None, len: 16 }");
+
+        let mut_a = buffer.borrow_mut().unwrap();
+        assert_eq!(format!("{buffer:?}"), "OwnedRef { status: Mutable, len: 16 }");
+        drop(mut_a);
+        assert_eq!(format!("{buffer:?}"), "OwnedRef { status: None, len: 16 }");
+
+        let a = buffer.borrow().unwrap();
+        assert_eq!(format!("{buffer:?}"), "OwnedRef { status: Immutable(1), len: 16 }");
+        let b = buffer.borrow().unwrap();
+        assert_eq!(format!("{buffer:?}"), "OwnedRef { status: Immutable(2), len: 16 }");
+        assert_eq!(format!("{:?}", buffer.0), "Buffer { status: Immutable(2), len: 16 }");
+        drop(a);
+        drop(b);
+        assert_eq!(format!("{buffer:?}"), "OwnedRef { status: None, len: 16 }");
+
+        // Formatting must not have left a borrow behind
+        let _mut_c = buffer.borrow_mut().unwrap();
+    }
 }
@@ -32,6 +32,9 @@ pub trait Receiver<E> {
     /// Return none if there are no pending events
     fn try_next(&mut self) -> Option<E>;
     /// Receive an event
+    ///
+    /// Receivers are commonly polled inside `select`, so implementations must be cancel-safe:
+    /// dropping the returned future before it completes must not lose an event.
     fn wait_next(&mut self) -> impl Future<Output = E>;
 }
 
@@ -116,11 +116,12 @@ impl From<ReportFreq> for u16 {
 }
 
 /// HID device protocol, see spec for more details
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(missing_docs)]
 pub enum Protocol {
     Boot,
+    #[default]
     Report,
 }
 
@@ -2,11 +2,11 @@
 //! See spec at <http://msdn.microsoft.com/en-us/library/windows/hardware/hh852380.aspx>
 use core::convert::Infallible;
 
-use embassy_sync::signal::Signal;
+use embassy_sync::channel::Channel;
 
 use crate::buffer::SharedRef;
 use crate::comms::{self, Endpoint, EndpointID, External, Internal, MailboxDelegate};
-use crate::{GlobalRawMutex, IntrusiveList, Node, NodeContainer, error, intrusive_list};
+use crate::{GlobalRawMutex, IntrusiveList, Node, NodeContainer, SyncCell, error, intrusive_list};
 
 mod command;
 pub use command::*;
@@ -14,6 +14,9 @@ pub use command::*;
 /// HID descriptor length
 pub const DESCRIPTOR_LEN: usize = 30;
 
+/// Number of requests that can be pending for a single device before new requests are rejected
+pub const REQUEST_QUEUE_LEN: usize = 4;
+
 /// Data for [`Error::InvalidSize`]
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -172,11 +175,53 @@ impl Default for RegisterFile {
     }
 }
 
+impl RegisterFile {
+    /// Build a HID descriptor that advertises these registers
+    pub fn build_descriptor(&self, version_info: VersionInfo) -> Descriptor {
+        Descriptor {
+            w_hid_desc_length: DESCRIPTOR_LEN as u16,
+            bcd_version: version_info.bcd_version,
+            w_report_desc_length: version_info.report_desc_length,
+            w_report_desc_register: self.report_desc_reg,
+            w_input_register: self.input_reg,
+            w_max_input_length: version_info.max_input_length,
+            w_output_register: self.output_reg,
+            w_max_output_length: version_info.max_output_length,
+            w_command_register: self.command_reg,
+            w_data_register: self.data_reg,
+            w_vendor_id: version_info.vendor_id,
+            w_product_id: version_info.product_id,
+            w_version_id: version_info.version_id,
+        }
+    }
+}
+
+/// HID descriptor fields that aren't derived from a device's registers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VersionInfo {
+    /// HID spec version, in BCD
+    pub bcd_version: u16,
+    /// Report descriptor length
+    pub report_desc_length: u16,
+    /// Maximum input report length
+    pub max_input_length: u16,
+    /// Maximum output report length
+    pub max_output_length: u16,
+    /// Vendor ID
+    pub vendor_id: u16,
+    /// Product ID
+    pub product_id: u16,
+    /// Device version
+    pub version_id: u16,
+}
+
 /// HID device that responds to HID requests
 pub struct Device {
     node: Node,
     tp: Endpoint,
-    request: Signal<GlobalRawMutex, Request<'static>>,
+    request: Channel<GlobalRawMutex, Request<'static>, REQUEST_QUEUE_LEN>,
+    protocol: SyncCell<Protocol>,
     /// Device ID
     pub id: DeviceId,
     /// Registers
@@ -201,15 +246,33 @@ impl Device {
         Self {
             node: Node::uninit(),
             tp: Endpoint::uninit(EndpointID::Internal(Internal::Hid)),
-            request: Signal::new(),
+            request: Channel::new(),
+            protocol: SyncCell::new(Protocol::Report),
             id,
             regs,
         }
     }
 
+    /// Build a HID descriptor for this device, with register fields taken from [`Self::regs`]
+    pub fn build_descriptor(&self, version_info: VersionInfo) -> Descriptor {
+        self.regs.build_descriptor(version_info)
+    }
+
     /// Wait for this device to receive a request
+    ///
+    /// Requests are returned in the order they were received.
     pub async fn wait_request(&self) -> Request<'static> {
-        self.request.wait().await
+        self.request.receive().await
+    }
+
+    /// Current protocol of this device, as last set by the host with `SetProtocol`
+    pub fn protocol(&self) -> Protocol {
+        self.protocol.get()
+    }
+
+    /// Record the protocol set by the host with `SetProtocol`
+    pub fn set_protocol(&self, protocol: Protocol) {
+        self.protocol.set(protocol);
     }
 
     /// Send a response to the host from this device
@@ -236,10 +299,10 @@ impl MailboxDelegate for Device {
             .ok_or(comms::MailboxDelegateError::MessageNotFound)?;
 
         match message.data {
-            MessageData::Request(ref request) => {
-                self.request.signal(request.clone());
-                Ok(())
-            }
+            MessageData::Request(ref request) => self
+                .request
+                .try_send(request.clone())
+                .map_err(|_| comms::MailboxDelegateError::BufferFull),
             _ if message.id != self.id => Err(comms::MailboxDelegateError::InvalidId),
             _ => Err(comms::MailboxDelegateError::InvalidData),
         }
@@ -388,4 +451,91 @@ mod test {
 
         assert_eq!(decoded, descriptor);
     }
+
+    #[test]
+    fn build_descriptor_uses_device_registers() {
+        let regs = RegisterFile {
+            hid_desc_reg: 0x0020,
+            report_desc_reg: 0x0021,
+            input_reg: 0x0024,
+            output_reg: 0x0025,
+            command_reg: 0x0022,
+            data_reg: 0x0023,
+        };
+        let device = Device::new(DeviceId(0), regs);
+        let version_info = VersionInfo {
+            bcd_version: 0x0100,
+            report_desc_length: 56,
+            max_input_length: 8,
+            max_output_length: 45,
+            vendor_id: 0x483,
+            product_id: 0x572B,
+            version_id: 0x0100,
+        };
+
+        let descriptor = device.build_descriptor(version_info);
+        assert_eq!(descriptor.w_hid_desc_length, DESCRIPTOR_LEN as u16);
+        assert_eq!(descriptor.w_report_desc_register, regs.report_desc_reg);
+        assert_eq!(descriptor.w_input_register, regs.input_reg);
+        assert_eq!(descriptor.w_output_register, regs.output_reg);
+        assert_eq!(descriptor.w_command_register, regs.command_reg);
+        assert_eq!(descriptor.w_data_register, regs.data_reg);
+
+        assert_eq!(descriptor.bcd_version, version_info.bcd_version);
+        assert_eq!(descriptor.w_report_desc_length, version_info.report_desc_length);
+        assert_eq!(descriptor.w_max_input_length, version_info.max_input_length);
+        assert_eq!(descriptor.w_max_output_length, version_info.max_output_length);
+        assert_eq!(descriptor.w_vendor_id, version_info.vendor_id);
+        assert_eq!(descriptor.w_product_id, version_info.product_id);
+        assert_eq!(descriptor.w_version_id, version_info.version_id);
+    }
+
+    /// Deliver a request to the device as if it was sent by the host
+    fn deliver(device: &Device, request: Request<'static>) -> Result<(), comms::MailboxDelegateError> {
+        let message = Message {
+            id: device.id,
+            data: MessageData::Request(request),
+        };
+        device.receive(&comms::Message {
+            from: EndpointID::External(External::Host),
+            to: EndpointID::Internal(Internal::Hid),
+            data: comms::Data::new(&message),
+        })
+    }
+
+    #[tokio::test]
+    async fn back_to_back_requests_delivered_in_order() {
+        let device = Device::new(DeviceId(0), RegisterFile::default());
+
+        assert!(deliver(&device, Request::Descriptor).is_ok());
+        assert!(deliver(&device, Request::ReportDescriptor).is_ok());
+
+        assert!(matches!(device.wait_request().await, Request::Descriptor));
+        assert!(matches!(device.wait_request().await, Request::ReportDescriptor));
+    }
+
+    #[test]
+    fn full_request_queue_rejects() {
+        let device = Device::new(DeviceId(0), RegisterFile::default());
+
+        for _ in 0..REQUEST_QUEUE_LEN {
+            assert!(deliver(&device, Request::InputReport).is_ok());
+        }
+
+        assert!(matches!(
+            deliver(&device, Request::Descriptor),
+            Err(comms::MailboxDelegateError::BufferFull)
+        ));
+    }
+
+    #[test]
+    fn protocol_state() {
+        let device = Device::new(DeviceId(0), RegisterFile::default());
+
+        // Devices start in report protocol mode
+        assert_eq!(device.protocol(), Protocol::Report);
+
+        device.set_protocol(Protocol::Boot);
+        assert_eq!(device.protocol(), Protocol::Boot);
+    }
 }
@@ -171,6 +171,26 @@ pub mod mctp {
         ) -> impl core::future::Future<Output = Self::ResultEnumType> + 'a;
     }
 
+    /// Construct the reply context for sending a result back to the host over eSPI
+    ///
+    /// `message_tag` is the tag of the request being answered; echoing it lets the host match the result to its
+    /// request. The tag-owner bit is cleared on the reply, since the host owns the tag.
+    pub fn espi_reply_context(
+        service_id: u8,
+        message_tag: mctp_rs::MctpMessageTag,
+    ) -> mctp_rs::MctpReplyContext<mctp_rs::smbus_espi::SmbusEspiMedium> {
+        mctp_rs::MctpReplyContext {
+            source_endpoint_id: mctp_rs::EndpointId::Id(0x80),
+            destination_endpoint_id: mctp_rs::EndpointId::Id(service_id), // TODO We're currently using this incorrectly - it should be the bus address of the host. Revisit once we have assigned a bus address to the host.
+            packet_sequence_number: mctp_rs::MctpSequenceNumber::new(0),
+            message_tag,
+            medium_context: mctp_rs::smbus_espi::SmbusEspiReplyContext {
+                destination_slave_address: 1,
+                source_slave_address: 0,
+            }, // Medium-specific context
+        }
+    }
+
     /// This macro generates a relay type over a collection of message types, which can be used by a relay service to
     /// receive messages over the wire and translate them into calls to a particular service on the EC.
     ///
@@ -500,4 +520,72 @@ pub mod mctp {
     } // end macro
 
     pub use impl_odp_mctp_relay_handler;
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)]
+    mod test {
+        use super::*;
+        use mctp_rs::smbus_espi::{SmbusEspiMedium, SmbusEspiReplyContext};
+        use mctp_rs::{MctpMedium, MctpPacketContext, VendorDefinedPci, VendorDefinedPciHeader};
+
+        /// Tag-owner bit in the flags byte of the MCTP transport header
+        const TAG_OWNER: u8 = 1 << 3;
+
+        /// Mask for the message tag in the flags byte of the MCTP transport header
+        const TAG_MASK: u8 = 0b111;
+
+        /// Return the flags byte of the MCTP transport header carried by `packet`
+        fn transport_flags(packet: &[u8]) -> u8 {
+            let (_, mut decoder) = SmbusEspiMedium.deserialize(packet).unwrap();
+            let [_version, _destination, _source, flags] = core::array::from_fn(|_| decoder.read().unwrap());
+            flags
+        }
+
+        /// Test that a result echoes the message tag of the request it answers
+        #[test]
+        fn test_espi_reply_context_echoes_tag() {
+            // Single-packet request with the tag-owner bit set and tag 5
+            let request_flags = 0b1100_0000 | TAG_OWNER | 5;
+            let mut request_buf = [0u8; 32];
+            let request = SmbusEspiMedium
+                .serialize(
+                    SmbusEspiReplyContext {
+                        destination_slave_address: 0,
+                        source_slave_address: 1,
+                    },
+                    &mut request_buf,
+                    |encoder| {
+                        encoder
+                            .write_all(&[0x01, 0x08, 0x80, request_flags, 0x7E, 0x12, 0x34, 0xAA])
+                            .map_err(|_| mctp_rs::MctpPacketError::SerializeError("encode error"))
+                    },
+                )
+                .unwrap();
+            assert_eq!(transport_flags(request), request_flags);
+
+            let mut assembly_buf = [0u8; 32];
+            let mut ctx = MctpPacketContext::new(SmbusEspiMedium, &mut assembly_buf);
+            let message_tag = ctx
+                .deserialize_packet(request)
+                .unwrap()
+                .unwrap()
+                .reply_context
+                .message_tag;
+
+            let mut assembly_buf = [0u8; 32];
+            let mut ctx = MctpPacketContext::new(SmbusEspiMedium, &mut assembly_buf);
+            let mut packets = ctx
+                .serialize_packet(
+                    espi_reply_context(0x08, message_tag),
+                    (VendorDefinedPciHeader(0x1234), VendorDefinedPci(&[0xBB])),
+                )
+                .unwrap();
+            let response_flags = transport_flags(packets.next().unwrap().unwrap());
+
+            assert_eq!(response_flags & TAG_MASK, request_flags & TAG_MASK);
+            // The requester owns the tag, so the response carries it with the tag-owner bit cleared
+            assert_eq!(response_flags & TAG_OWNER, 0);
+            assert!(packets.next().is_none());
+        }
+    }
 }
@@ -6,7 +6,6 @@ use embassy_sync::channel::Channel;
 use embassy_sync::mutex::Mutex;
 use embedded_services::{GlobalRawMutex, error, info, trace};
 use mctp_rs::smbus_espi::SmbusEspiMedium;
-use mctp_rs::smbus_espi::SmbusEspiReplyContext;
 
 const HOST_TX_QUEUE_SIZE: usize = 5;
 
@@ -22,6 +21,8 @@ const ASSEMBLY_BUF_SIZE: usize = 256;
 struct HostResultMessage<RelayHandler: embedded_services::relay::mctp::RelayHandler> {
     pub handler_service_id: RelayHandler::ServiceIdType,
     pub message: RelayHandler::ResultEnumType,
+    /// Message tag of the request this is a response to, echoed back so the host can match responses to requests
+    pub message_tag: mctp_rs::MctpMessageTag,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -177,7 +178,13 @@ impl<'hw, RelayHandler: embedded_services::relay::mctp::RelayHandler> ServiceInn
                             trace!("MCTP packet successfully deserialized");
                             match message.parse_as::<RelayHandler::RequestEnumType>() {
                                 Ok((header, body)) => {
-                                    self.process_request_to_ec((header, body), espi, &port_event).await?;
+                                    self.process_request_to_ec(
+                                        (header, body),
+                                        message.reply_context.message_tag,
+                                        espi,
+                                        &port_event,
+                                    )
+                                    .await?;
                                 }
                                 Err(e) => {
                                     error!("MCTP ODP type malformed: {:?}", e);
@@ -226,6 +233,7 @@ impl<'hw, RelayHandler: embedded_services::relay::mctp::RelayHandler> ServiceInn
             <RelayHandler::RequestEnumType as mctp_rs::MctpMessageTrait<'_>>::Header,
             RelayHandler::RequestEnumType,
         ),
+        message_tag: mctp_rs::MctpMessageTag,
         espi: &mut espi::Espi<'hw>,
         port_event: &espi::PortEvent,
     ) -> Result<(), Error> {
@@ -239,6 +247,7 @@ impl<'hw, RelayHandler: embedded_services::relay::mctp::RelayHandler> ServiceInn
             .try_send(HostResultMessage {
                 handler_service_id: header.get_service_id(),
                 message: response,
+                message_tag,
             })
             .map_err(|_| Error::Serialize)?;
 
@@ -268,19 +277,8 @@ impl<'hw, RelayHandler: embedded_services::relay::mctp::RelayHandler> ServiceInn
         let mut mctp_ctx =
             mctp_rs::MctpPacketContext::new(mctp_rs::smbus_espi::SmbusEspiMedium, assembly_buf.as_mut_slice());
 
-        let reply_context: mctp_rs::MctpReplyContext<SmbusEspiMedium> = mctp_rs::MctpReplyContext {
-            source_endpoint_id: mctp_rs::EndpointId::Id(0x80),
-            destination_endpoint_id: mctp_rs::EndpointId::Id(result.handler_service_id.into()), // TODO We're currently using this incorrectly - it should be the bus address of the host. Revisit once we have assigned a bus address to the host.
-            packet_sequence_number: mctp_rs::MctpSequenceNumber::new(0),
-            message_tag: mctp_rs::MctpMessageTag::try_from(3).map_err(|e| {
-                error!("serialize_packet_from_subsystem: {:?}", e);
-                Error::Serialize
-            })?,
-            medium_context: SmbusEspiReplyContext {
-                destination_slave_address: 1,
-                source_slave_address: 0,
-            }, // Medium-specific context
-        };
+        let reply_context =
+            embedded_services::relay::mctp::espi_reply_context(result.handler_service_id.into(), result.message_tag);
 
         let header = result.message.create_header(&result.handler_service_id);
         let mut packet_state = mctp_ctx
@@ -26,7 +26,17 @@ async fn main(spawner: embassy_executor::Spawner) {
     let rtc = RTC.init(embassy_imxrt::rtc::Rtc::new(p.RTC));
     let (dt_clock, rtc_nvram) = rtc.split();
 
-    let [tz, ac_expiration, ac_policy, dc_expiration, dc_policy, ..] = rtc_nvram.storage();
+    let [
+        tz,
+        ac_expiration,
+        ac_policy,
+        dc_expiration,
+        dc_policy,
+        drift,
+        boot_count,
+        boot_time,
+        ..,
+    ] = rtc_nvram.storage();
 
     embedded_services::init().await;
     info!("services initialized");
@@ -34,12 +44,18 @@ async fn main(spawner: embassy_executor::Spawner) {
     let time_service = odp_service_common::spawn_service!(spawner, TimeAlarmServiceType, |resources| {
         time_alarm_service::Service::new(
             resources,
+            Default::default(),
             dt_clock,
-            tz,
-            ac_expiration,
-            ac_policy,
-            dc_expiration,
-            dc_policy,
+            time_alarm_service::Storage {
+                time_zone: tz,
+                ac_expiration,
+                ac_policy,
+                dc_expiration,
+                dc_policy,
+                drift,
+                boot_count,
+                boot_time,
+            },
         )
     })
     .expect("Failed to spawn time alarm service");
@@ -37,6 +37,7 @@ use type_c_service::controller::macros::PortComponents;
 use type_c_service::controller::state::SharedState as PortSharedState;
 use type_c_service::define_controller_port_static_cell_channel;
 use type_c_service::service::Service;
+use type_c_service::service::event_receiver::LagDetectingReceiver;
 use type_c_service::service::registration::PortData;
 
 extern crate rt685s_evk_example;
@@ -84,7 +85,11 @@ type PowerPolicySenderType = MapSender<
     ) -> power_policy_interface::service::event::EventData,
 >;
 
-type PowerPolicyReceiverType = DynSubscriber<'static, power_policy_interface::service::event::EventData>;
+type PowerPolicyReceiverType = LagDetectingReceiver<
+    'static,
+    DynSubscriber<'static, power_policy_interface::service::event::EventData>,
+    PowerPolicyServiceType,
+>;
 
 type PowerPolicyServiceType = Mutex<
     GlobalRawMutex,
@@ -370,27 +375,30 @@ async fn main(spawner: Spawner) {
     };
 
     static POWER_SERVICE: StaticCell<PowerPolicyServiceType> = StaticCell::new();
-    let power_service = POWER_SERVICE.init(Mutex::new(power_policy_service::service::Service::new(
+    let power_service = &*POWER_SERVICE.init(Mutex::new(power_policy_service::service::Service::new(
         power_policy_registration,
         power_policy_service::service::config::Config::default(),
     )));
 
     static TYPE_C_SERVICE: StaticCell<Mutex<GlobalRawMutex, TypeCServiceType>> = StaticCell::new();
-    let type_c_service = TYPE_C_SERVICE.init(Mutex::new(Service::new(
-        Default::default(),
-        TypeCRegistrationType {
-            ports: [port0, port1],
-            port_data: [
-                PortData {
-                    local_port: Some(LocalPortId(0)),
-                },
-                PortData {
-                    local_port: Some(LocalPortId(1)),
-                },
-            ],
-            service_senders: [NoopSender],
-        },
-    )));
+    let type_c_service = TYPE_C_SERVICE.init(Mutex::new(
+        Service::new(
+            Default::default(),
+            TypeCRegistrationType {
+                ports: [port0, port1],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(1)),
+                    },
+                ],
+                service_senders: [NoopSender],
+            },
+        )
+        .expect("Invalid type-C service config"),
+    ));
 
     info!("Spawining type-c service task");
     spawner.spawn(
@@ -399,7 +407,7 @@ async fn main(spawner: Spawner) {
             TypeCServiceEventReceiverType::new(
                 [port0, port1],
                 [type_c_receiver0, type_c_receiver1],
-                power_policy_subscriber,
+                LagDetectingReceiver::new(power_policy_subscriber, power_service),
             ),
         )
         .expect("Failed to spawn type-c service task"),
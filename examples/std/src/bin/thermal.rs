@@ -43,6 +43,7 @@ async fn run(spawner: Spawner) {
                 driver: ts::mock::sensor::MockSensor::new(),
                 config: ts::mock::sensor::MockSensor::config(),
                 event_senders,
+                persistent_thresholds: None,
             },
         ))
         .expect("Failed to spawn sensor service");
@@ -56,6 +57,7 @@ async fn run(spawner: Spawner) {
             config: ts::mock::fan::MockFan::config(),
             sensor_service,
             event_senders: &mut [],
+            persistent_state_temps: None,
         },
     ))
     .expect("Failed to spawn fan service");
@@ -12,13 +12,15 @@ use log::{debug, info};
 
 use power_policy_interface::capability::PowerCapability;
 use type_c_interface::control::dp::{DpConfig, DpPinConfig, DpStatus};
+use type_c_interface::control::mode::{BillboardIndication, DataMode, LaneConfig, ModeEntryStatus};
 use type_c_interface::control::pd::{PdStateMachineConfig, PortStatus};
+use type_c_interface::control::pdo::{PdoQuery, Pdos};
 use type_c_interface::control::power::SystemPowerState;
 use type_c_interface::control::retimer::RetimerFwUpdateState;
 use type_c_interface::control::svid::DiscoveredSvids;
 use type_c_interface::control::tbt::TbtConfig;
 use type_c_interface::control::type_c::TypeCStateMachineState;
-use type_c_interface::control::usb::UsbControlConfig;
+use type_c_interface::control::usb::{UsbControlConfig, UsbDataStatus};
 use type_c_interface::control::vdm::{AttnVdm, OtherVdm, SendVdm};
 use type_c_interface::port::event::PortEventBitfield;
 use type_c_interface::util::power_capability_from_current;
@@ -28,6 +30,7 @@ pub struct ControllerState {
     events: Signal<GlobalRawMutex, PortEventBitfield>,
     status: Mutex<GlobalRawMutex, PortStatus>,
     pd_alert: Mutex<GlobalRawMutex, Option<Ado>>,
+    event_mask: Mutex<GlobalRawMutex, PortEventBitfield>,
 }
 
 impl ControllerState {
@@ -36,6 +39,7 @@ impl ControllerState {
             events: Signal::new(),
             status: Mutex::new(PortStatus::new()),
             pd_alert: Mutex::new(None),
+            event_mask: Mutex::new(PortEventBitfield::none()),
         }
     }
 
@@ -148,6 +152,11 @@ impl type_c_interface::controller::Controller for Controller<'_> {
         debug!("Reset controller");
         Ok(())
     }
+
+    async fn get_die_temperature(&mut self) -> Result<i16, PdError> {
+        debug!("Get die temperature");
+        Ok(35)
+    }
 }
 
 impl type_c_interface::controller::pd::Pd for Controller<'_> {
@@ -161,6 +170,15 @@ impl type_c_interface::controller::pd::Pd for Controller<'_> {
         Ok(())
     }
 
+    async fn set_source_capability(
+        &mut self,
+        _port: LocalPortId,
+        capability: power_policy_interface::capability::PowerCapability,
+    ) -> Result<(), PdError> {
+        debug!("Set source capability: {capability:?}");
+        Ok(())
+    }
+
     async fn get_pd_alert(&mut self, port: LocalPortId) -> Result<Option<Ado>, PdError> {
         let pd_alert = self.state.pd_alert.lock().await;
         if let Some(ado) = *pd_alert {
@@ -172,6 +190,18 @@ impl type_c_interface::controller::pd::Pd for Controller<'_> {
         }
     }
 
+    async fn get_event_mask(&mut self, port: LocalPortId) -> Result<PortEventBitfield, PdError> {
+        let mask = *self.state.event_mask.lock().await;
+        debug!("Port{}: Get event mask: {mask:?}", port.0);
+        Ok(mask)
+    }
+
+    async fn set_event_mask(&mut self, port: LocalPortId, mask: PortEventBitfield) -> Result<(), PdError> {
+        debug!("Port{}: Set event mask: {mask:?}", port.0);
+        *self.state.event_mask.lock().await = mask;
+        Ok(())
+    }
+
     async fn set_unconstrained_power(&mut self, _port: LocalPortId, unconstrained: bool) -> Result<(), PdError> {
         debug!("Set unconstrained power: {unconstrained}");
         Ok(())
@@ -205,6 +235,11 @@ impl type_c_interface::controller::pd::Pd for Controller<'_> {
         Ok(())
     }
 
+    async fn get_usb_data_status(&mut self, port: LocalPortId) -> Result<UsbDataStatus, PdError> {
+        debug!("Get USB data status for port {port:?}");
+        Ok(UsbDataStatus::default())
+    }
+
     async fn get_dp_status(&mut self, port: LocalPortId) -> Result<DpStatus, PdError> {
         debug!("Get DisplayPort status for port {port:?}");
         Ok(DpStatus {
@@ -231,6 +266,28 @@ impl type_c_interface::controller::pd::Pd for Controller<'_> {
         Ok(())
     }
 
+    async fn enter_data_mode(&mut self, port: LocalPortId, mode: DataMode) -> Result<ModeEntryStatus, PdError> {
+        debug!("Enter {mode:?} for port {port:?}");
+        Ok(ModeEntryStatus {
+            mode,
+            lanes: LaneConfig::X2,
+        })
+    }
+
+    async fn exit_data_mode(&mut self, port: LocalPortId, mode: DataMode) -> Result<(), PdError> {
+        debug!("Exit {mode:?} for port {port:?}");
+        Ok(())
+    }
+
+    async fn set_billboard_indication(
+        &mut self,
+        port: LocalPortId,
+        indication: BillboardIndication,
+    ) -> Result<(), PdError> {
+        debug!("Set billboard indication for port {port:?}: {indication:?}");
+        Ok(())
+    }
+
     async fn hard_reset(&mut self, port: LocalPortId) -> Result<(), PdError> {
         debug!("Hard reset for port {port:?}");
         Ok(())
@@ -241,6 +298,11 @@ impl type_c_interface::controller::pd::Pd for Controller<'_> {
         Ok(DiscoveredSvids::default())
     }
 
+    async fn get_pdos(&mut self, port: LocalPortId, query: PdoQuery) -> Result<Pdos, PdError> {
+        debug!("Get {query:?} PDOs for port {port:?}");
+        Ok(Pdos::default())
+    }
+
     async fn get_discover_identity_sop_response(&mut self, port: LocalPortId) -> Result<sop::ResponseVdos, PdError> {
         debug!("Get Discover Identity SOP response for port {port:?}");
         Err(PdError::Failed)
@@ -190,6 +190,13 @@ impl<A: AddressMode + Copy, B: I2c<A>> Device<A, B> {
     ) -> Result<Option<Response<'static>>, Error<B::Error>> {
         info!("Handling command");
 
+        // Protocol state is tracked locally so it can be reported without querying the device
+        if let hid::Command::GetProtocol = cmd {
+            return Ok(Some(Response::Command(hid::CommandResponse::GetProtocol(
+                self.device.protocol(),
+            ))));
+        }
+
         let desc = self.get_hid_descriptor().await?;
         let (command_reg, data_reg) = (desc.w_command_register, desc.w_data_register);
 
@@ -314,6 +321,10 @@ impl<A: AddressMode + Copy, B: I2c<A>> Device<A, B> {
                 Error::Bus(e)
             })?;
 
+            if let hid::Command::SetProtocol(protocol) = cmd {
+                self.device.set_protocol(*protocol);
+            }
+
             Ok(None)
         }
     }
@@ -375,21 +375,15 @@ impl<
     fn hid_descriptor(&self) -> hid::Descriptor {
         const VERSION: u16 = 0x0100;
 
-        hid::Descriptor {
-            w_hid_desc_length: hid::DESCRIPTOR_LEN as u16,
+        self.register_file().build_descriptor(hid::VersionInfo {
             bcd_version: VERSION,
-            w_report_desc_length: REPORT_DESCRIPTOR.len() as u16,
-            w_report_desc_register: self.register_file().report_desc_reg,
-            w_input_register: self.register_file().input_reg,
-            w_max_input_length: INPUT_MAX_LEN as u16,
-            w_output_register: self.register_file().output_reg,
-            w_max_output_length: OUTPUT_MAX_LEN as u16,
-            w_command_register: self.register_file().command_reg,
-            w_data_register: self.register_file().data_reg,
-            w_vendor_id: self.hid_cfg.vid,
-            w_product_id: self.hid_cfg.pid,
-            w_version_id: VERSION,
-        }
+            report_desc_length: REPORT_DESCRIPTOR.len() as u16,
+            max_input_length: INPUT_MAX_LEN as u16,
+            max_output_length: OUTPUT_MAX_LEN as u16,
+            vendor_id: self.hid_cfg.vid,
+            product_id: self.hid_cfg.pid,
+            version_id: VERSION,
+        })
     }
 
     fn report_descriptor(&self) -> &'static [u8] {
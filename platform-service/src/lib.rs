@@ -3,6 +3,9 @@
 /// CRC service abstraction
 pub mod embedded_crc;
 
+/// Persist the last panic to NVRAM
+pub mod panic_record;
+
 /// Initiate a delayed MCU Reset
 pub mod reset;
 
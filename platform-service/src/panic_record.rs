@@ -0,0 +1,316 @@
+//! Persist the location and message of the last panic to NVRAM for post-mortem debugging
+//!
+//! Register a set of NVRAM slots once on startup with [`register`], call [`record_panic`] from the platform's
+//! `#[panic_handler]` before resetting, then retrieve the record on the next boot with [`last_panic`].
+
+use core::cell::RefCell;
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embedded_mcu_hal::nvram::NvramStorage;
+
+use embedded_services::GlobalRawMutex;
+
+/// A single 32-bit NVRAM slot used to store part of a panic record
+pub type PanicRecordSlot = &'static mut (dyn NvramStorage<'static, u32> + Send);
+
+/// NVRAM slots holding a panic record, see [`PanicRecord::WORDS`] for the layout
+pub type PanicRecordStorage = [PanicRecordSlot; PanicRecord::WORDS];
+
+static STORAGE: Mutex<GlobalRawMutex, RefCell<Option<&'static mut PanicRecordStorage>>> =
+    Mutex::new(RefCell::new(None));
+
+/// CRC used to validate a stored record, also prevents erased or uninitialized NVRAM from being read as a record
+const RECORD_CRC: crc::Crc<u16> = crc::Crc::<u16>::new(&crc::CRC_16_IBM_3740);
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicRecordError {
+    /// Panic record storage has already been registered
+    AlreadyRegistered,
+}
+
+/// Bounded record of a panic
+///
+/// The file name keeps its last [`PanicRecord::FILE_LEN`] bytes since the end of the path is the most useful part,
+/// the message keeps its first [`PanicRecord::MESSAGE_LEN`] bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PanicRecord {
+    line: u32,
+    file_len: u8,
+    file: [u8; Self::FILE_LEN],
+    message_len: u8,
+    message: [u8; Self::MESSAGE_LEN],
+}
+
+impl PanicRecord {
+    /// Maximum number of bytes of the file name that are stored
+    pub const FILE_LEN: usize = 12;
+    /// Maximum number of bytes of the message that are stored
+    pub const MESSAGE_LEN: usize = 20;
+    /// Number of 32-bit NVRAM slots needed to store a record
+    ///
+    /// Layout: a header containing the CRC and lengths, the line number, then the file name and message bytes.
+    pub const WORDS: usize = 2 + Self::FILE_LEN / 4 + Self::MESSAGE_LEN / 4;
+
+    /// Create a new record, truncating `file` and `message` to fit
+    pub fn new(file: &str, line: u32, message: fmt::Arguments<'_>) -> Self {
+        let mut record = Self {
+            line,
+            file_len: 0,
+            file: [0; Self::FILE_LEN],
+            message_len: 0,
+            message: [0; Self::MESSAGE_LEN],
+        };
+
+        let mut start = file.len().saturating_sub(Self::FILE_LEN);
+        while !file.is_char_boundary(start) {
+            start += 1;
+        }
+        let file = file.as_bytes().get(start..).unwrap_or_default();
+        if let Some(dest) = record.file.get_mut(..file.len()) {
+            dest.copy_from_slice(file);
+            record.file_len = file.len() as u8;
+        }
+
+        let mut writer = TruncatingWriter {
+            buffer: &mut record.message,
+            len: 0,
+        };
+        // TruncatingWriter never fails, running out of space just drops the rest of the message
+        let _ = writer.write_fmt(message);
+        record.message_len = writer.len as u8;
+
+        record
+    }
+
+    /// Create a new record from the information passed to a panic handler
+    pub fn from_panic_info(info: &PanicInfo<'_>) -> Self {
+        let (file, line) = info
+            .location()
+            .map_or(("", 0), |location| (location.file(), location.line()));
+        Self::new(file, line, format_args!("{}", info.message()))
+    }
+
+    /// Returns the line the panic occurred on, zero if unknown
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the end of the name of the file the panic occurred in
+    pub fn file(&self) -> &str {
+        Self::as_str(&self.file, self.file_len)
+    }
+
+    /// Returns the start of the panic message
+    pub fn message(&self) -> &str {
+        Self::as_str(&self.message, self.message_len)
+    }
+
+    fn as_str(bytes: &[u8], len: u8) -> &str {
+        bytes
+            .get(..len as usize)
+            .and_then(|bytes| core::str::from_utf8(bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Bytes covered by the CRC, the header's CRC field is excluded
+    fn crc(&self) -> u16 {
+        let mut digest = RECORD_CRC.digest();
+        digest.update(&[self.file_len, self.message_len]);
+        digest.update(&self.line.to_le_bytes());
+        digest.update(&self.file);
+        digest.update(&self.message);
+        digest.finalize()
+    }
+
+    fn to_words(self) -> [u32; Self::WORDS] {
+        let mut words = [0; Self::WORDS];
+        let header = (u32::from(self.crc()) << 16) | (u32::from(self.file_len) << 8) | u32::from(self.message_len);
+        let bytes = self.file.chunks_exact(4).chain(self.message.chunks_exact(4));
+        for (word, value) in words.iter_mut().zip(
+            [header, self.line]
+                .into_iter()
+                .chain(bytes.map(|chunk| chunk.try_into().map_or(0, u32::from_le_bytes))),
+        ) {
+            *word = value;
+        }
+        words
+    }
+
+    fn from_words(words: [u32; Self::WORDS]) -> Option<Self> {
+        let [header, line, bytes @ ..] = words;
+        let mut record = Self {
+            line,
+            file_len: (header >> 8) as u8,
+            file: [0; Self::FILE_LEN],
+            message_len: header as u8,
+            message: [0; Self::MESSAGE_LEN],
+        };
+        if record.file_len as usize > Self::FILE_LEN || record.message_len as usize > Self::MESSAGE_LEN {
+            return None;
+        }
+
+        let dest = record
+            .file
+            .chunks_exact_mut(4)
+            .chain(record.message.chunks_exact_mut(4));
+        for (chunk, word) in dest.zip(bytes) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        if (header >> 16) as u16 != record.crc() {
+            return None;
+        }
+
+        // Reject records whose text doesn't decode rather than silently reporting an empty string
+        if core::str::from_utf8(record.file.get(..record.file_len as usize)?).is_err()
+            || core::str::from_utf8(record.message.get(..record.message_len as usize)?).is_err()
+        {
+            return None;
+        }
+
+        Some(record)
+    }
+}
+
+/// Writer that fills a fixed buffer and silently drops anything that doesn't fit, never splitting a character
+struct TruncatingWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for TruncatingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let remaining = self.buffer.len() - self.len;
+        let mut end = s.len().min(remaining);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        if let (Some(dest), Some(src)) = (self.buffer.get_mut(self.len..self.len + end), s.as_bytes().get(..end)) {
+            dest.copy_from_slice(src);
+            self.len += end;
+        }
+        Ok(())
+    }
+}
+
+/// Call once on startup to provide the NVRAM slots used to store panic records
+///
+/// Panics are not recorded until storage has been registered.
+pub fn register(storage: &'static mut PanicRecordStorage) -> Result<(), PanicRecordError> {
+    STORAGE.lock(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_some() {
+            return Err(PanicRecordError::AlreadyRegistered);
+        }
+        *slot = Some(storage);
+        Ok(())
+    })
+}
+
+/// Persist `record` to NVRAM, overwriting any previous record
+///
+/// Does nothing if no storage has been registered.
+pub fn store(record: &PanicRecord) {
+    with_storage(|storage| {
+        for (slot, word) in storage.iter_mut().zip(record.to_words()) {
+            slot.write(word);
+        }
+    });
+}
+
+/// Persist the panic described by `info` to NVRAM, intended to be called from the platform's `#[panic_handler]`
+pub fn record_panic(info: &PanicInfo<'_>) {
+    store(&PanicRecord::from_panic_info(info));
+}
+
+/// Returns the panic record persisted in NVRAM, if storage has been registered and contains a valid record
+pub fn last_panic() -> Option<PanicRecord> {
+    with_storage(|storage| {
+        let mut words = [0; PanicRecord::WORDS];
+        for (word, slot) in words.iter_mut().zip(storage.iter()) {
+            *word = slot.read();
+        }
+        PanicRecord::from_words(words)
+    })
+    .flatten()
+}
+
+/// Erase the panic record persisted in NVRAM, typically after it has been reported
+pub fn clear_last_panic() {
+    with_storage(|storage| {
+        for slot in storage.iter_mut() {
+            slot.write(0);
+        }
+    });
+}
+
+fn with_storage<R>(f: impl FnOnce(&mut PanicRecordStorage) -> R) -> Option<R> {
+    // `try_borrow_mut` so a panic while the storage is in use doesn't cause a second panic in the handler
+    STORAGE.lock(|cell| cell.try_borrow_mut().ok()?.as_deref_mut().map(f))
+}
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let record = PanicRecord::new("src/main.rs", 42, format_args!("oops {}", 7));
+        assert_eq!(record.file(), "src/main.rs");
+        assert_eq!(record.line(), 42);
+        assert_eq!(record.message(), "oops 7");
+
+        assert_eq!(PanicRecord::from_words(record.to_words()), Some(record));
+    }
+
+    #[test]
+    fn truncation() {
+        let record = PanicRecord::new(
+            "platform-service/src/panic_record.rs",
+            1,
+            format_args!("this message is longer than twenty bytes"),
+        );
+        assert_eq!(record.file(), "ic_record.rs");
+        assert_eq!(record.message(), "this message is long");
+        assert_eq!(PanicRecord::from_words(record.to_words()), Some(record));
+
+        // Characters are never split
+        let record = PanicRecord::new("é/lib/main.rs", 1, format_args!("aéééééééééé"));
+        assert_eq!(record.file(), "/lib/main.rs");
+        assert_eq!(record.message(), "aééééééééé");
+        assert_eq!(PanicRecord::from_words(record.to_words()), Some(record));
+    }
+
+    #[test]
+    fn corrupt_crc() {
+        let record = PanicRecord::new("src/main.rs", 42, format_args!("oops"));
+
+        // Corrupted message byte
+        let mut words = record.to_words();
+        words[PanicRecord::WORDS - 1] ^= 1;
+        assert_eq!(PanicRecord::from_words(words), None);
+
+        // Corrupted line number
+        let mut words = record.to_words();
+        words[1] ^= 1;
+        assert_eq!(PanicRecord::from_words(words), None);
+
+        // Corrupted CRC
+        let mut words = record.to_words();
+        words[0] ^= 1 << 16;
+        assert_eq!(PanicRecord::from_words(words), None);
+    }
+
+    #[test]
+    fn erased_storage() {
+        assert_eq!(PanicRecord::from_words([0xFFFF_FFFF; PanicRecord::WORDS]), None);
+        // Left behind by clear_last_panic
+        assert_eq!(PanicRecord::from_words([0; PanicRecord::WORDS]), None);
+    }
+}
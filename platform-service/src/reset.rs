@@ -3,8 +3,9 @@
 use core::future::Future;
 
 use embassy_sync::{lazy_lock::LazyLock, signal::Signal};
+use embassy_time::{Duration, with_timeout};
 
-use embedded_services::{GlobalRawMutex, IntrusiveList, Node, NodeContainer, intrusive_list};
+use embedded_services::{GlobalRawMutex, IntrusiveList, Node, NodeContainer, intrusive_list, warn};
 
 static BLOCKERS: LazyLock<IntrusiveList> = LazyLock::new(IntrusiveList::new);
 
@@ -36,6 +37,9 @@ impl Blocker {
     }
 
     /// waitable reset indicator, for handling resets
+    ///
+    /// `before_reset` is the pre-reset hook, used to flush NVRAM, quiesce DMA, etc. The reset proceeds once it completes,
+    /// or once the pre-reset timeout expires when resetting through [`system_reset_with_timeout`].
     pub async fn wait_for_reset<F, Fut>(&self, before_reset: F)
     where
         F: FnOnce() -> Fut,
@@ -47,22 +51,114 @@ impl Blocker {
     }
 }
 
-/// Signals and waits for all registered blockers to complete their async operations before performing a platform-specific reset, typically NVIC_RESET
-#[cfg(feature = "cortex-m")]
-pub async fn system_reset() -> ! {
-    // signal and wait for completion as two separate events to allow for alternative scheduling algorithms to take effect
-    let blockers = BLOCKERS.get();
-
-    // 1. signal all events
-    for blocker in blockers.iter_only::<Blocker>() {
+/// Signals all registered blockers that a reset is pending
+fn signal_blockers() {
+    for blocker in BLOCKERS.get().iter_only::<Blocker>() {
         blocker.reset_pending.signal(());
     }
+}
 
-    // 2. wait for all events
-    for blocker in blockers.iter_only::<Blocker>() {
+/// Waits for every registered blocker's pre-reset hook to complete
+async fn wait_for_blockers() {
+    for blocker in BLOCKERS.get().iter_only::<Blocker>() {
         blocker.unblocked.wait().await;
     }
+}
+
+/// Runs the pre-reset stage: signals all registered blockers and waits for their hooks to complete.
+///
+/// Waits at most `timeout` in total, so a hanging hook cannot prevent the reset. Returns `true` if every hook completed
+/// in time.
+pub async fn prepare_for_reset(timeout: Duration) -> bool {
+    // signal and wait for completion as two separate events to allow for alternative scheduling algorithms to take effect
+    signal_blockers();
+    let completed = with_timeout(timeout, wait_for_blockers()).await.is_ok();
+
+    if !completed {
+        warn!(
+            "Pre-reset hooks did not complete within {}ms, resetting anyway",
+            timeout.as_millis()
+        );
+    }
 
-    // 3. perform platform reset
+    completed
+}
+
+/// Signals and waits for all registered blockers to complete their async operations before performing a platform-specific reset, typically NVIC_RESET
+///
+/// There is no bound on the wait, so a hanging hook prevents the reset. Use [`system_reset_with_timeout`] to force the
+/// reset after a time limit.
+#[cfg(feature = "cortex-m")]
+pub async fn system_reset() -> ! {
+    // 1. run pre-reset hooks
+    signal_blockers();
+    wait_for_blockers().await;
+
+    // 2. perform platform reset
     cortex_m::peripheral::SCB::sys_reset();
 }
+
+/// Two-stage reset: runs the pre-reset hooks for at most `timeout` in total, then performs a platform-specific reset
+#[cfg(feature = "cortex-m")]
+pub async fn system_reset_with_timeout(timeout: Duration) -> ! {
+    // 1. run pre-reset hooks
+    prepare_for_reset(timeout).await;
+
+    // 2. perform platform reset
+    cortex_m::peripheral::SCB::sys_reset();
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use embassy_time::{Instant, Timer};
+
+    use super::*;
+
+    static FLUSH: Blocker = Blocker::uninit();
+    static HANG: Blocker = Blocker::uninit();
+
+    /// Blockers are registered globally, so every stage is checked in a single test
+    #[tokio::test]
+    async fn pre_reset_hooks() {
+        let flushes = AtomicU32::new(0);
+        let flush = async {
+            loop {
+                FLUSH
+                    .wait_for_reset(|| async {
+                        Timer::after_millis(10).await;
+                        flushes.fetch_add(1, Ordering::SeqCst);
+                    })
+                    .await;
+            }
+        };
+
+        let test = async {
+            FLUSH.register().unwrap();
+
+            // Hooks only run once a reset is being prepared
+            Timer::after_millis(20).await;
+            assert_eq!(flushes.load(Ordering::SeqCst), 0);
+
+            // And have all completed once it's ready
+            assert!(prepare_for_reset(Duration::from_secs(1)).await);
+            assert_eq!(flushes.load(Ordering::SeqCst), 1);
+
+            // A hanging hook is cut off at the timeout without holding up the others
+            HANG.register().unwrap();
+            let start = Instant::now();
+            assert!(!prepare_for_reset(Duration::from_millis(100)).await);
+            let elapsed = start.elapsed();
+            assert!(elapsed >= Duration::from_millis(100) && elapsed < Duration::from_millis(500));
+            assert_eq!(flushes.load(Ordering::SeqCst), 2);
+        };
+
+        tokio::select! {
+            _ = flush => {}
+            _ = HANG.wait_for_reset(core::future::pending::<()>) => {}
+            _ = test => {}
+        }
+    }
+}
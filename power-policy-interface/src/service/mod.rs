@@ -19,3 +19,11 @@ impl UnconstrainedState {
         }
     }
 }
+
+/// Read access to the power policy's current unconstrained state
+///
+/// Lets event consumers resync after missing [`event::EventData::Unconstrained`] events.
+pub trait UnconstrainedStateSource {
+    /// Returns the current unconstrained state, as last broadcast in [`event::EventData::Unconstrained`]
+    fn unconstrained_state(&self) -> UnconstrainedState;
+}
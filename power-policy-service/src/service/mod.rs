@@ -20,7 +20,7 @@ use power_policy_interface::{
         Error, Psu,
         event::{Event as PsuEvent, EventData as PsuEventData},
     },
-    service::{UnconstrainedState, event::Event as ServiceEvent},
+    service::{UnconstrainedState, UnconstrainedStateSource, event::Event as ServiceEvent},
 };
 
 use crate::service::registration::Registration;
@@ -94,6 +94,11 @@ impl<'device, Reg: Registration<'device>, Customization: customization::Customiz
         }
     }
 
+    /// Returns the current system unconstrained state, as last broadcast in [`ServiceEvent::Unconstrained`]
+    pub fn unconstrained_state(&self) -> UnconstrainedState {
+        self.state.unconstrained
+    }
+
     /// Returns the total amount of power that is being supplied to external devices
     pub async fn compute_total_provider_power_mw(&self) -> u32 {
         let mut total = 0;
@@ -225,3 +230,11 @@ impl<'device, Reg: Registration<'device>, Customization: customization::Customiz
         Ok(())
     }
 }
+
+impl<'device, Reg: Registration<'device>, Customization: customization::Customization> UnconstrainedStateSource
+    for Service<'device, Reg, Customization>
+{
+    fn unconstrained_state(&self) -> UnconstrainedState {
+        self.state.unconstrained
+    }
+}
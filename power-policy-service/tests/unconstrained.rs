@@ -26,7 +26,7 @@ impl Test for TestUnconstrained {
 
     async fn run<'a>(
         &mut self,
-        _service: &ServiceMutex<'a, 'a, Self::Customization>,
+        service: &ServiceMutex<'a, 'a, Self::Customization>,
         service_receiver: DynamicReceiver<'a, ServiceEvent<'a, DeviceType<'a>>>,
         device0: &DeviceType<'a>,
         device1: &DeviceType<'a>,
@@ -99,6 +99,13 @@ impl Test for TestUnconstrained {
                 },
             )
             .await;
+            assert_eq!(
+                service.lock().await.unconstrained_state(),
+                UnconstrainedState {
+                    unconstrained: true,
+                    available: 1,
+                }
+            );
 
             {
                 let mut device0 = device0.lock().await;
@@ -13,6 +13,10 @@ pub trait Driver: Fan + RpmSense {}
 pub enum Error {
     /// Fan encountered a hardware failure.
     Hardware,
+    /// The request needs configuration the fan doesn't have, such as an acoustic table.
+    Unsupported,
+    /// Automatic control stopped making progress, for example because a fan command hung.
+    Stalled,
 }
 
 /// Fan event.
@@ -46,6 +50,49 @@ pub enum State {
     On(OnState),
 }
 
+/// Cooling policy, as selected by the host through ACPI's _SCP method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CoolingPolicy {
+    /// Prefer the fan, which responds from its [`OnState::Min`] temperature.
+    #[default]
+    Active,
+    /// Prefer throttling the SoC, keeping the fan off until the temperature reaches the sensor's throttle threshold.
+    Passive,
+}
+
+/// A single point relating fan speed to its measured noise level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AcousticPoint {
+    /// Fan speed in RPM.
+    pub rpm: u16,
+    /// Noise level in dBA at this speed.
+    pub dba: f32,
+}
+
+/// Snapshot of all fan attributes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FanInfo {
+    /// Current fan [`State`].
+    pub state: State,
+    /// Most recently sampled RPM measurement.
+    pub rpm: u16,
+    /// Average RPM over a sampling period.
+    pub rpm_average: u16,
+    /// Minimum RPM supported by the fan.
+    pub min_rpm: u16,
+    /// Maximum RPM supported by the fan.
+    pub max_rpm: u16,
+    /// Manual RPM override, if one is set.
+    pub rpm_override: Option<u16>,
+    /// RPM ceiling applied in automatic control mode to respect an acoustic limit, if any.
+    pub acoustic_rpm_limit: Option<u16>,
+    /// Whether the fan responds to temperature changes.
+    pub enabled: bool,
+}
+
 /// Fan service interface trait.
 pub trait FanService {
     /// Enable automatic fan control.
@@ -76,6 +123,38 @@ pub trait FanService {
     fn state_temp(&self, state: OnState) -> impl Future<Output = DegreesCelsius>;
     /// Sets the temperature at which the fan will change to the specified [`OnState`] when in automatic control mode.
     fn set_state_temp(&self, state: OnState, temp: DegreesCelsius) -> impl Future<Output = ()>;
+    /// Returns the manual RPM override, if one is set.
+    fn rpm_override(&self) -> impl Future<Output = Option<u16>>;
+    /// Sets (or clears) a manual RPM override.
+    ///
+    /// While set, the fan holds the given RPM regardless of temperature, though it still escalates to its maximum RPM
+    /// if temperature reaches the max [`OnState`] temperature. Clearing the override resumes automatic control.
+    fn set_rpm_override(&self, rpm: Option<u16>) -> impl Future<Output = Result<(), Error>>;
+    /// Returns the RPM ceiling applied in automatic control mode to respect an acoustic limit, if any.
+    fn acoustic_rpm_limit(&self) -> impl Future<Output = Option<u16>>;
+    /// Sets (or clears) the RPM ceiling applied in automatic control mode to respect an acoustic limit.
+    fn set_acoustic_rpm_limit(&self, limit: Option<u16>) -> impl Future<Output = Result<(), Error>>;
+    /// Returns the estimated noise level in dBA at the average RPM, or `None` if the fan has no acoustic table.
+    fn dba_estimate(&self) -> impl Future<Output = Option<f32>>;
+    /// Sets (or clears) the acoustic limit in dBA.
+    ///
+    /// The limit is converted to an RPM ceiling using the fan's acoustic table, returning [`Error::Unsupported`] if
+    /// there is none. Reading [`FanService::acoustic_rpm_limit`] afterwards returns the converted ceiling.
+    fn set_acoustic_dba_limit(&self, limit: Option<f32>) -> impl Future<Output = Result<(), Error>>;
+    /// Returns whether the fan responds to temperature changes.
+    fn is_enabled(&self) -> impl Future<Output = bool>;
+    /// Enable or disable the fan's response to temperature changes.
+    ///
+    /// While disabled, the fan holds its current [`State`] and automatic control takes no action.
+    fn set_enabled(&self, enabled: bool) -> impl Future<Output = ()>;
+    /// Returns the [`CoolingPolicy`] automatic control follows.
+    fn cooling_policy(&self) -> impl Future<Output = CoolingPolicy>;
+    /// Sets the [`CoolingPolicy`] automatic control follows.
+    fn set_cooling_policy(&self, policy: CoolingPolicy) -> impl Future<Output = ()>;
+    /// Returns all fan attributes at once.
+    ///
+    /// Prefer this over the individual getters when several attributes are needed, as it avoids repeated driver access.
+    fn info(&self) -> impl Future<Output = FanInfo>;
 }
 
 impl<T: FanService> FanService for &T {
@@ -130,4 +209,48 @@ impl<T: FanService> FanService for &T {
     fn set_state_temp(&self, state: OnState, temp: DegreesCelsius) -> impl Future<Output = ()> {
         T::set_state_temp(self, state, temp)
     }
+
+    fn rpm_override(&self) -> impl Future<Output = Option<u16>> {
+        T::rpm_override(self)
+    }
+
+    fn set_rpm_override(&self, rpm: Option<u16>) -> impl Future<Output = Result<(), Error>> {
+        T::set_rpm_override(self, rpm)
+    }
+
+    fn acoustic_rpm_limit(&self) -> impl Future<Output = Option<u16>> {
+        T::acoustic_rpm_limit(self)
+    }
+
+    fn set_acoustic_rpm_limit(&self, limit: Option<u16>) -> impl Future<Output = Result<(), Error>> {
+        T::set_acoustic_rpm_limit(self, limit)
+    }
+
+    fn dba_estimate(&self) -> impl Future<Output = Option<f32>> {
+        T::dba_estimate(self)
+    }
+
+    fn set_acoustic_dba_limit(&self, limit: Option<f32>) -> impl Future<Output = Result<(), Error>> {
+        T::set_acoustic_dba_limit(self, limit)
+    }
+
+    fn is_enabled(&self) -> impl Future<Output = bool> {
+        T::is_enabled(self)
+    }
+
+    fn set_enabled(&self, enabled: bool) -> impl Future<Output = ()> {
+        T::set_enabled(self, enabled)
+    }
+
+    fn cooling_policy(&self) -> impl Future<Output = CoolingPolicy> {
+        T::cooling_policy(self)
+    }
+
+    fn set_cooling_policy(&self, policy: CoolingPolicy) -> impl Future<Output = ()> {
+        T::set_cooling_policy(self, policy)
+    }
+
+    fn info(&self) -> impl Future<Output = FanInfo> {
+        T::info(self)
+    }
 }
@@ -3,6 +3,16 @@
 pub mod fan;
 pub mod sensor;
 
+/// Snapshot of the full state of a thermal zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ZoneState {
+    /// Attributes of the zone's sensor, if one exists.
+    pub sensor: Option<sensor::SensorInfo>,
+    /// Attributes of the zone's fan, if one exists.
+    pub fan: Option<fan::FanInfo>,
+}
+
 /// Thermal service interface trait.
 pub trait ThermalService {
     /// Associated type for registered sensor services.
@@ -14,4 +24,91 @@ pub trait ThermalService {
     fn sensor(&self, id: u8) -> Option<Self::Sensor>;
     /// Retrieve a handle to the fan service with the specified instance ID, if it exists.
     fn fan(&self, id: u8) -> Option<Self::Fan>;
+
+    /// Enable or disable the thermal zone with the specified instance ID.
+    ///
+    /// A zone is made up of the sensor and fan sharing that instance ID. While disabled, the sensor still reports its
+    /// most recent temperature but generates no threshold events, and the fan takes no action.
+    ///
+    /// Returns `false` if neither a sensor nor a fan exists with the specified instance ID.
+    fn set_zone_enabled(&self, id: u8, enabled: bool) -> impl core::future::Future<Output = bool> {
+        use fan::FanService as _;
+        use sensor::SensorService as _;
+
+        async move {
+            let sensor = self.sensor(id);
+            let fan = self.fan(id);
+            if sensor.is_none() && fan.is_none() {
+                return false;
+            }
+
+            if let Some(sensor) = sensor {
+                sensor.set_enabled(enabled).await;
+            }
+            if let Some(fan) = fan {
+                fan.set_enabled(enabled).await;
+            }
+            true
+        }
+    }
+
+    /// Set the sampling cadence of the thermal zone with the specified instance ID.
+    ///
+    /// The sensor samples temperature at this period, and the fan re-evaluates its speed at the same period. Both pick
+    /// up the new period on their next iteration, so zones can be tuned independently (e.g. skin temperature slow, CPU
+    /// fast). The sensor's fast sample period and the fan's RPM sampling period are unaffected.
+    ///
+    /// Returns `false` if neither a sensor nor a fan exists with the specified instance ID.
+    fn set_zone_sample_period(
+        &self,
+        id: u8,
+        period: embassy_time::Duration,
+    ) -> impl core::future::Future<Output = bool> {
+        use fan::FanService as _;
+        use sensor::SensorService as _;
+
+        async move {
+            let sensor = self.sensor(id);
+            let fan = self.fan(id);
+            if sensor.is_none() && fan.is_none() {
+                return false;
+            }
+
+            if let Some(sensor) = sensor {
+                sensor.set_sample_period(period).await;
+            }
+            if let Some(fan) = fan {
+                fan.set_rpm_update_period(period).await;
+            }
+            true
+        }
+    }
+
+    /// Returns a snapshot of the full state of the thermal zone with the specified instance ID.
+    ///
+    /// Intended for debugging and bring-up. Returns `None` if neither a sensor nor a fan exists with the specified
+    /// instance ID.
+    fn dump_state(&self, id: u8) -> impl core::future::Future<Output = Option<ZoneState>> {
+        use fan::FanService as _;
+        use sensor::SensorService as _;
+
+        async move {
+            let sensor = self.sensor(id);
+            let fan = self.fan(id);
+            if sensor.is_none() && fan.is_none() {
+                return None;
+            }
+
+            Some(ZoneState {
+                sensor: match sensor {
+                    Some(sensor) => Some(sensor.info().await),
+                    None => None,
+                },
+                fan: match fan {
+                    Some(fan) => Some(fan.info().await),
+                    None => None,
+                },
+            })
+        }
+    }
 }
@@ -14,6 +14,8 @@ pub enum Error {
     Hardware,
     /// Retry attempts to communicate with sensor exhausted.
     RetryExhausted,
+    /// Sensor repeatedly reported temperatures outside its plausible range or changing implausibly fast.
+    Implausible,
 }
 
 /// Sensor event.
@@ -22,9 +24,17 @@ pub enum Error {
 #[non_exhaustive]
 pub enum Event {
     /// A sensor threshold was exceeded.
-    ThresholdExceeded(Threshold),
+    ThresholdExceeded(ThresholdCrossing),
     /// A sensor threshold which was previously exceeded is now cleared.
-    ThresholdCleared(Threshold),
+    ThresholdCleared(ThresholdCrossing),
+    /// Every previously exceeded threshold has cleared and the temperature is back in its normal range.
+    ///
+    /// Sent once, after the last [`Event::ThresholdCleared`], containing the temperature that cleared it.
+    Normal(DegreesCelsius),
+    /// The [`Threshold::Throttle`] threshold was exceeded, the SoC should reduce its performance state.
+    ThrottleRequest(ThresholdCrossing),
+    /// A previous [`Event::ThrottleRequest`] cleared, the SoC may restore its performance state.
+    ThrottleRelease(ThresholdCrossing),
     /// Sensor encountered a failure.
     Failure(Error),
 }
@@ -39,10 +49,83 @@ pub enum Threshold {
     WarnHigh,
     /// The temperature threshold above which a prochot event is generated.
     Prochot,
+    /// The temperature threshold above which the SoC is asked to throttle, normally between prochot and critical.
+    Throttle,
     /// The temperature threshold above which a critical event is generated.
     Critical,
 }
 
+impl Threshold {
+    /// Returns the direction in which the temperature must move to exceed this threshold.
+    pub fn direction(self) -> ThresholdDirection {
+        match self {
+            Threshold::WarnLow => ThresholdDirection::Below,
+            Threshold::WarnHigh | Threshold::Prochot | Threshold::Throttle | Threshold::Critical => {
+                ThresholdDirection::Above
+            }
+        }
+    }
+}
+
+/// Direction in which a threshold is exceeded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThresholdDirection {
+    /// The threshold is exceeded when the temperature falls below it.
+    Below,
+    /// The threshold is exceeded when the temperature rises above it.
+    Above,
+}
+
+/// Details of a threshold crossing carried by threshold events.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ThresholdCrossing {
+    /// The threshold which was crossed.
+    pub threshold: Threshold,
+    /// The direction in which the threshold is exceeded.
+    pub direction: ThresholdDirection,
+    /// The temperature in degrees Celsius which caused the crossing.
+    pub temperature: DegreesCelsius,
+}
+
+impl ThresholdCrossing {
+    /// Create a new threshold crossing for the given threshold and temperature.
+    pub fn new(threshold: Threshold, temperature: DegreesCelsius) -> Self {
+        Self {
+            threshold,
+            direction: threshold.direction(),
+            temperature,
+        }
+    }
+}
+
+/// Snapshot of all sensor attributes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SensorInfo {
+    /// Most recently sampled temperature in degrees Celsius.
+    pub temperature: DegreesCelsius,
+    /// Average temperature over a sampling period in degrees Celsius.
+    pub temperature_average: DegreesCelsius,
+    /// The [`Threshold::WarnLow`] temperature in degrees Celsius.
+    pub warn_low_threshold: DegreesCelsius,
+    /// The [`Threshold::WarnHigh`] temperature in degrees Celsius.
+    pub warn_high_threshold: DegreesCelsius,
+    /// The [`Threshold::Prochot`] temperature in degrees Celsius.
+    pub prochot_threshold: DegreesCelsius,
+    /// The [`Threshold::Throttle`] temperature in degrees Celsius.
+    pub throttle_threshold: DegreesCelsius,
+    /// The [`Threshold::Critical`] temperature in degrees Celsius.
+    pub critical_threshold: DegreesCelsius,
+    /// Rate at which temperature measurements are sampled.
+    pub sample_period: Duration,
+    /// Whether periodic temperature sampling is enabled.
+    pub sampling_enabled: bool,
+    /// Whether threshold checking is enabled.
+    pub enabled: bool,
+}
+
 /// Sensor service interface trait
 pub trait SensorService {
     /// Returns the most recently sampled temperature measurement in degrees Celsius.
@@ -61,6 +144,17 @@ pub trait SensorService {
     fn enable_sampling(&self) -> impl Future<Output = ()>;
     /// Disable periodic temperature sampling.
     fn disable_sampling(&self) -> impl Future<Output = ()>;
+    /// Returns whether threshold checking is enabled.
+    fn is_enabled(&self) -> impl Future<Output = bool>;
+    /// Enable or disable threshold checking.
+    ///
+    /// While disabled, the sensor keeps sampling so the most recent temperature can still be read, but no threshold
+    /// events are generated.
+    fn set_enabled(&self, enabled: bool) -> impl Future<Output = ()>;
+    /// Returns all sensor attributes at once.
+    ///
+    /// Prefer this over the individual getters when several attributes are needed, as it avoids repeated locking.
+    fn info(&self) -> impl Future<Output = SensorInfo>;
 }
 
 impl<T: SensorService> SensorService for &T {
@@ -95,4 +189,16 @@ impl<T: SensorService> SensorService for &T {
     async fn disable_sampling(&self) {
         T::disable_sampling(self).await
     }
+
+    async fn is_enabled(&self) -> bool {
+        T::is_enabled(self).await
+    }
+
+    async fn set_enabled(&self, enabled: bool) {
+        T::set_enabled(self, enabled).await
+    }
+
+    async fn info(&self) -> SensorInfo {
+        T::info(self).await
+    }
 }
@@ -2,6 +2,8 @@
 
 mod serialization;
 
+use core::future::Future;
+
 pub use serialization::{ThermalError, ThermalRequest, ThermalResponse, ThermalResult};
 use thermal_service_interface::ThermalService;
 use thermal_service_interface::fan::{self, FanService};
@@ -27,6 +29,44 @@ impl DeciKelvin {
     }
 }
 
+/// DeciFahrenheit temperature representation.
+///
+/// Reported instead of [`DeciKelvin`] by sensors configured with [`TemperatureUnit::DeciFahrenheit`].
+/// Signed since temperatures below 0 degF are within the range of some sensors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeciFahrenheit(pub i32);
+
+impl DeciFahrenheit {
+    /// Convert from degrees Celsius to DeciFahrenheit, rounding to the nearest tenth of a degree.
+    pub const fn from_celsius(c: f32) -> Self {
+        let deci_f = (c * 9.0 / 5.0 + 32.0) * 10.0;
+        Self(if deci_f < 0.0 { deci_f - 0.5 } else { deci_f + 0.5 } as i32)
+    }
+
+    /// Convert from DeciFahrenheit to degrees Celsius.
+    pub const fn to_celsius(self) -> f32 {
+        ((self.0 as f32 / 10.0) - 32.0) * 5.0 / 9.0
+    }
+}
+
+impl From<DeciKelvin> for DeciFahrenheit {
+    fn from(temp: DeciKelvin) -> Self {
+        Self::from_celsius(temp.to_celsius())
+    }
+}
+
+/// Unit a sensor's temperature is reported in by `GetTmp`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TemperatureUnit {
+    /// Report in [`DeciKelvin`], as expected by the standard MPTF interface.
+    #[default]
+    DeciKelvin,
+    /// Report in [`DeciFahrenheit`].
+    DeciFahrenheit,
+}
+
 /// MPTF Standard UUIDs which the thermal service understands.
 pub mod uuid_standard {
     /// The critical temperature threshold of a sensor.
@@ -47,22 +87,90 @@ pub mod uuid_standard {
     pub const FAN_CURRENT_RPM: uuid::Bytes = uuid::uuid!("adf95492-0776-4ffc-84f3-b6c8b5269683").to_bytes_le();
 }
 
+/// Handler for `GetVar`/`SetVar` requests with a UUID outside of [`uuid_standard`], implemented by the OEM.
+pub trait OemVarHandler {
+    /// Read the OEM variable identified by `var_uuid` for the given instance.
+    fn get_var(&self, instance_id: u8, var_uuid: uuid::Bytes) -> impl Future<Output = Result<u32, ThermalError>>;
+    /// Write the OEM variable identified by `var_uuid` for the given instance.
+    fn set_var(
+        &self,
+        instance_id: u8,
+        var_uuid: uuid::Bytes,
+        value: u32,
+    ) -> impl Future<Output = Result<(), ThermalError>>;
+}
+
+/// [`OemVarHandler`] for platforms without OEM variables, rejects every variable as an invalid parameter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOemVars;
+
+impl OemVarHandler for NoOemVars {
+    async fn get_var(&self, _instance_id: u8, _var_uuid: uuid::Bytes) -> Result<u32, ThermalError> {
+        Err(ThermalError::InvalidParameter)
+    }
+
+    async fn set_var(&self, _instance_id: u8, _var_uuid: uuid::Bytes, _value: u32) -> Result<(), ThermalError> {
+        Err(ThermalError::InvalidParameter)
+    }
+}
+
 /// Thermal service relay handler which wraps a thermal service instance.
-pub struct ThermalServiceRelayHandler<T: ThermalService> {
+pub struct ThermalServiceRelayHandler<T: ThermalService, O: OemVarHandler = NoOemVars> {
     service: T,
+    output_units: &'static [TemperatureUnit],
+    oem_vars: O,
 }
 
 impl<T: ThermalService> ThermalServiceRelayHandler<T> {
     /// Create a new thermal service relay handler.
     pub fn new(service: T) -> Self {
-        Self { service }
+        Self {
+            service,
+            output_units: &[],
+            oem_vars: NoOemVars,
+        }
+    }
+}
+
+impl<T: ThermalService, O: OemVarHandler> ThermalServiceRelayHandler<T, O> {
+    /// Forward `GetVar`/`SetVar` requests for UUIDs outside of [`uuid_standard`] to the given OEM handler.
+    ///
+    /// Without one, such requests fail with [`ThermalError::InvalidParameter`].
+    pub fn with_oem_var_handler<H: OemVarHandler>(self, oem_vars: H) -> ThermalServiceRelayHandler<T, H> {
+        ThermalServiceRelayHandler {
+            service: self.service,
+            output_units: self.output_units,
+            oem_vars,
+        }
+    }
+
+    /// Configure the unit `GetTmp` reports each sensor's temperature in, indexed by sensor instance ID.
+    ///
+    /// Sensors without an entry report in [`TemperatureUnit::DeciKelvin`]. Only `GetTmp` is affected, thresholds
+    /// and variables are always exchanged in DeciKelvin.
+    pub fn with_output_units(mut self, output_units: &'static [TemperatureUnit]) -> Self {
+        self.output_units = output_units;
+        self
+    }
+
+    /// Returns the unit `GetTmp` reports the temperature of sensor `instance_id` in.
+    pub fn output_unit(&self, instance_id: u8) -> TemperatureUnit {
+        self.output_units
+            .get(usize::from(instance_id))
+            .copied()
+            .unwrap_or_default()
     }
 
     async fn sensor_get_tmp(&self, instance_id: u8) -> ThermalResult {
         let sensor = self.service.sensor(instance_id).ok_or(ThermalError::InvalidParameter)?;
         let temp = sensor.temperature().await;
-        Ok(ThermalResponse::ThermalGetTmpResponse {
-            temperature: DeciKelvin::from_celsius(temp),
+        Ok(match self.output_unit(instance_id) {
+            TemperatureUnit::DeciKelvin => ThermalResponse::ThermalGetTmpResponse {
+                temperature: DeciKelvin::from_celsius(temp),
+            },
+            TemperatureUnit::DeciFahrenheit => ThermalResponse::ThermalGetTmpFahrenheitResponse {
+                temperature: DeciFahrenheit::from_celsius(temp),
+            },
         })
     }
 
@@ -91,7 +199,10 @@ impl<T: ThermalService> ThermalServiceRelayHandler<T> {
             uuid_standard::FAN_MIN_RPM => self.fan_get_min_rpm(instance_id).await,
             uuid_standard::FAN_MAX_RPM => self.fan_get_max_rpm(instance_id).await,
             uuid_standard::FAN_CURRENT_RPM => self.fan_get_rpm(instance_id).await,
-            _ => Err(ThermalError::InvalidParameter),
+            _ => {
+                let val = self.oem_vars.get_var(instance_id, var_uuid).await?;
+                Ok(ThermalResponse::ThermalGetVarResponse { val })
+            }
         }
     }
 
@@ -121,7 +232,10 @@ impl<T: ThermalService> ThermalServiceRelayHandler<T> {
                 let rpm = u16::try_from(set_var).map_err(|_| ThermalError::InvalidParameter)?;
                 self.fan_set_rpm(instance_id, rpm).await
             }
-            _ => Err(ThermalError::InvalidParameter),
+            _ => {
+                self.oem_vars.set_var(instance_id, var_uuid, set_var).await?;
+                Ok(ThermalResponse::ThermalSetVarResponse)
+            }
         }
     }
 
@@ -184,6 +298,32 @@ impl<T: ThermalService> ThermalServiceRelayHandler<T> {
         Ok(ThermalResponse::ThermalSetVarResponse)
     }
 
+    async fn fan_set_scp(&self, instance_id: u8, policy_id: u32, acoustic_lim: u32) -> ThermalResult {
+        let fan = self.service.fan(instance_id).ok_or(ThermalError::InvalidParameter)?;
+
+        // Cooling policy modes as defined for ACPI's _SCP method
+        let policy = match policy_id {
+            0 => fan::CoolingPolicy::Active,
+            1 => fan::CoolingPolicy::Passive,
+            _ => return Err(ThermalError::InvalidParameter),
+        };
+
+        // The cooling policy doesn't depend on the acoustic limit, so apply it even if the limit is rejected
+        fan.set_cooling_policy(policy).await;
+
+        // The acoustic limit is expressed in dBA, with 0 meaning no limit. The fan converts a limit to an RPM ceiling
+        // using its acoustic table, so it can't honor one without a table, but clearing the ceiling needs no table.
+        let result = match acoustic_lim {
+            0 => fan.set_acoustic_rpm_limit(None).await,
+            dba => fan.set_acoustic_dba_limit(Some(dba as f32)).await,
+        };
+        result.map_err(|e| match e {
+            fan::Error::Unsupported => ThermalError::InvalidParameter,
+            _ => ThermalError::HardwareError,
+        })?;
+        Ok(ThermalResponse::ThermalSetScpResponse)
+    }
+
     async fn fan_set_rpm(&self, instance_id: u8, rpm: u16) -> ThermalResult {
         let fan = self.service.fan(instance_id).ok_or(ThermalError::InvalidParameter)?;
         fan.set_rpm(rpm).await.map_err(|_| ThermalError::HardwareError)?;
@@ -191,12 +331,16 @@ impl<T: ThermalService> ThermalServiceRelayHandler<T> {
     }
 }
 
-impl<T: ThermalService> embedded_services::relay::mctp::RelayServiceHandlerTypes for ThermalServiceRelayHandler<T> {
+impl<T: ThermalService, O: OemVarHandler> embedded_services::relay::mctp::RelayServiceHandlerTypes
+    for ThermalServiceRelayHandler<T, O>
+{
     type RequestType = ThermalRequest;
     type ResultType = ThermalResult;
 }
 
-impl<T: ThermalService> embedded_services::relay::mctp::RelayServiceHandler for ThermalServiceRelayHandler<T> {
+impl<T: ThermalService, O: OemVarHandler> embedded_services::relay::mctp::RelayServiceHandler
+    for ThermalServiceRelayHandler<T, O>
+{
     async fn process_request(&self, request: Self::RequestType) -> Self::ResultType {
         match request {
             ThermalRequest::ThermalGetTmpRequest { instance_id } => self.sensor_get_tmp(instance_id).await,
@@ -207,8 +351,13 @@ impl<T: ThermalService> embedded_services::relay::mctp::RelayServiceHandler for
                 high,
             } => self.sensor_set_warn_thrs(instance_id, timeout, low, high).await,
             ThermalRequest::ThermalGetThrsRequest { instance_id } => self.sensor_get_warn_thrs(instance_id).await,
-            // Revisit: Don't currently have a good strategy for handling this request
-            ThermalRequest::ThermalSetScpRequest { .. } => Err(ThermalError::InvalidParameter),
+            // Revisit: Power limit is not yet honored
+            ThermalRequest::ThermalSetScpRequest {
+                instance_id,
+                policy_id,
+                acoustic_lim,
+                ..
+            } => self.fan_set_scp(instance_id, policy_id, acoustic_lim).await,
             ThermalRequest::ThermalGetVarRequest {
                 instance_id, var_uuid, ..
             } => self.get_var_handler(instance_id, var_uuid).await,
@@ -221,3 +370,324 @@ impl<T: ThermalService> embedded_services::relay::mctp::RelayServiceHandler for
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use core::cell::Cell;
+
+    use embassy_futures::select::select;
+    use embassy_time::{Duration, Timer};
+    use embedded_services::event::NoopSender;
+    use embedded_services::relay::mctp::RelayServiceHandler;
+    use odp_service_common::runnable_service::ServiceRunner;
+    use thermal_service::mock::{fan::MockFan, sensor::MockSensor};
+
+    use super::{
+        DeciFahrenheit, DeciKelvin, FanService as _, OemVarHandler, ThermalError, ThermalRequest, ThermalResponse,
+        ThermalResult, ThermalServiceRelayHandler, fan, sensor, uuid_standard,
+    };
+
+    type SensorService = thermal_service::sensor::Service<'static, MockSensor, NoopSender, 1>;
+    type FanService = thermal_service::fan::Service<'static, MockFan, SensorService, NoopSender, 1>;
+
+    const OEM_VAR: uuid::Bytes = uuid::uuid!("0f6c2c5e-4a36-4d5e-9b51-6a1e7f0c9d21").to_bytes_le();
+
+    /// OEM handler which only knows [`OEM_VAR`] and records every request it receives.
+    #[derive(Default)]
+    struct RecordingOemVars {
+        last_get: Cell<Option<(u8, uuid::Bytes)>>,
+        last_set: Cell<Option<(u8, uuid::Bytes, u32)>>,
+    }
+
+    impl OemVarHandler for RecordingOemVars {
+        async fn get_var(&self, instance_id: u8, var_uuid: uuid::Bytes) -> Result<u32, ThermalError> {
+            self.last_get.set(Some((instance_id, var_uuid)));
+            if var_uuid == OEM_VAR {
+                Ok(1234)
+            } else {
+                Err(ThermalError::InvalidParameter)
+            }
+        }
+
+        async fn set_var(&self, instance_id: u8, var_uuid: uuid::Bytes, value: u32) -> Result<(), ThermalError> {
+            self.last_set.set(Some((instance_id, var_uuid, value)));
+            if var_uuid == OEM_VAR {
+                Ok(())
+            } else {
+                Err(ThermalError::HardwareError)
+            }
+        }
+    }
+
+    fn get_var(var_uuid: uuid::Bytes) -> ThermalRequest {
+        ThermalRequest::ThermalGetVarRequest {
+            instance_id: 2,
+            len: 4,
+            var_uuid,
+        }
+    }
+
+    fn set_var(var_uuid: uuid::Bytes, set_var: u32) -> ThermalRequest {
+        ThermalRequest::ThermalSetVarRequest {
+            instance_id: 2,
+            len: 4,
+            var_uuid,
+            set_var,
+        }
+    }
+
+    #[test]
+    fn unknown_vars_forwarded_to_oem() {
+        let mut resources = thermal_service::Resources::<SensorService, FanService>::default();
+        let service = thermal_service::Service::init(
+            &mut resources,
+            thermal_service::InitParams {
+                sensors: &[],
+                fans: &[],
+            },
+        );
+
+        // Without an OEM handler unknown variables are rejected
+        let handler = ThermalServiceRelayHandler::new(service);
+        assert!(matches!(
+            embassy_futures::block_on(handler.process_request(get_var(OEM_VAR))),
+            Err(ThermalError::InvalidParameter)
+        ));
+
+        let handler = handler.with_oem_var_handler(RecordingOemVars::default());
+
+        assert!(matches!(
+            embassy_futures::block_on(handler.process_request(get_var(OEM_VAR))),
+            Ok(ThermalResponse::ThermalGetVarResponse { val: 1234 })
+        ));
+        assert_eq!(handler.oem_vars.last_get.take(), Some((2, OEM_VAR)));
+
+        assert!(matches!(
+            embassy_futures::block_on(handler.process_request(set_var(OEM_VAR, 42))),
+            Ok(ThermalResponse::ThermalSetVarResponse)
+        ));
+        assert_eq!(handler.oem_vars.last_set.take(), Some((2, OEM_VAR, 42)));
+
+        // OEM errors are passed through
+        let other = uuid::uuid!("9d0a4f0e-3a8b-4c47-8f1e-2b6f1c7a5e10").to_bytes_le();
+        assert!(matches!(
+            embassy_futures::block_on(handler.process_request(set_var(other, 1))),
+            Err(ThermalError::HardwareError)
+        ));
+        assert_eq!(handler.oem_vars.last_set.take(), Some((2, other, 1)));
+
+        // Standard variables are still handled by the service, not forwarded
+        assert!(matches!(
+            embassy_futures::block_on(handler.process_request(get_var(uuid_standard::CRT_TEMP))),
+            Err(ThermalError::InvalidParameter)
+        ));
+        assert!(handler.oem_vars.last_get.take().is_none());
+    }
+
+    /// Sensor reporting a fixed temperature, so fan automatic control responds deterministically.
+    struct FixedSensor {
+        temp: f32,
+        throttle_threshold: f32,
+    }
+
+    impl super::SensorService for FixedSensor {
+        async fn temperature(&self) -> f32 {
+            self.temp
+        }
+
+        async fn temperature_average(&self) -> f32 {
+            self.temp
+        }
+
+        async fn temperature_immediate(&self) -> Result<f32, sensor::Error> {
+            Ok(self.temp)
+        }
+
+        async fn set_threshold(&self, _threshold: sensor::Threshold, _value: f32) {}
+
+        async fn threshold(&self, threshold: sensor::Threshold) -> f32 {
+            match threshold {
+                sensor::Threshold::Throttle => self.throttle_threshold,
+                _ => f32::MAX,
+            }
+        }
+
+        async fn set_sample_period(&self, _period: Duration) {}
+
+        async fn enable_sampling(&self) {}
+
+        async fn disable_sampling(&self) {}
+
+        async fn is_enabled(&self) -> bool {
+            true
+        }
+
+        async fn set_enabled(&self, _enabled: bool) {}
+
+        async fn info(&self) -> sensor::SensorInfo {
+            sensor::SensorInfo {
+                temperature: self.temp,
+                temperature_average: self.temp,
+                warn_low_threshold: f32::MIN,
+                warn_high_threshold: f32::MAX,
+                prochot_threshold: f32::MAX,
+                throttle_threshold: self.throttle_threshold,
+                critical_threshold: f32::MAX,
+                sample_period: Duration::from_secs(1),
+                sampling_enabled: true,
+                enabled: true,
+            }
+        }
+    }
+
+    /// Fan noise rising linearly from 20 dBA at 1000 RPM to 40 dBA at 5000 RPM.
+    const ACOUSTIC_TABLE: &[fan::AcousticPoint] = &[
+        fan::AcousticPoint { rpm: 1000, dba: 20.0 },
+        fan::AcousticPoint { rpm: 5000, dba: 40.0 },
+    ];
+
+    /// Apply `SetScp`, then let fan automatic control respond to `temp` for 50 ms.
+    ///
+    /// The fan turns on at 30 C, runs at max from 50 C, and the SoC is asked to throttle at 45 C. With the default
+    /// update period automatic control responds once. Returns the `SetScp` result, then the fan state and commanded RPM
+    /// afterwards.
+    fn set_scp_then_update(
+        policy_id: u32,
+        acoustic_lim: u32,
+        temp: f32,
+        update_period: Duration,
+    ) -> (ThermalResult, fan::State, u16) {
+        set_scp_then_update_with_table(policy_id, acoustic_lim, temp, update_period, ACOUSTIC_TABLE)
+    }
+
+    /// [`set_scp_then_update`] on a fan with the given acoustic table.
+    fn set_scp_then_update_with_table(
+        policy_id: u32,
+        acoustic_lim: u32,
+        temp: f32,
+        update_period: Duration,
+        acoustic_table: &'static [fan::AcousticPoint],
+    ) -> (ThermalResult, fan::State, u16) {
+        let sensor = FixedSensor {
+            temp,
+            throttle_threshold: 45.0,
+        };
+        let mut fan_resources = thermal_service::fan::Resources::<MockFan, 1>::default();
+        let mut fan_senders: [NoopSender; 0] = [];
+        let (fan, fan_runner) = embassy_futures::block_on(thermal_service::fan::Service::new(
+            &mut fan_resources,
+            thermal_service::fan::InitParams {
+                driver: MockFan::new(),
+                config: thermal_service::fan::Config {
+                    min_temp: 30.0,
+                    ramp_temp: 40.0,
+                    max_temp: 50.0,
+                    update_period,
+                    acoustic_table,
+                    ..Default::default()
+                },
+                sensor_service: &sensor,
+                event_senders: &mut fan_senders,
+                persistent_state_temps: None,
+            },
+        ))
+        .unwrap();
+
+        let sensors: [&FixedSensor; 0] = [];
+        let fans = [fan];
+        let mut resources = thermal_service::Resources::default();
+        let service = thermal_service::Service::init(
+            &mut resources,
+            thermal_service::InitParams {
+                sensors: &sensors,
+                fans: &fans,
+            },
+        );
+
+        let handler = ThermalServiceRelayHandler::new(service);
+        let result = embassy_futures::block_on(handler.process_request(ThermalRequest::ThermalSetScpRequest {
+            instance_id: 0,
+            policy_id,
+            acoustic_lim,
+            power_lim: 0,
+        }));
+
+        // Automatic control responds immediately, then waits an update period between responses
+        embassy_futures::block_on(select(fan_runner.run(), Timer::after_millis(50)));
+        let rpm = embassy_futures::block_on(fan.rpm_immediate()).unwrap();
+        (result, embassy_futures::block_on(fan.info()).state, rpm)
+    }
+
+    #[test]
+    fn active_policy_prefers_fan() {
+        let (result, state, _) = set_scp_then_update(0, 0, 35.0, Duration::from_secs(1));
+        assert!(matches!(result, Ok(ThermalResponse::ThermalSetScpResponse)));
+        assert_eq!(state, fan::State::On(fan::OnState::Min));
+    }
+
+    #[test]
+    fn passive_policy_prefers_throttling() {
+        // Below the throttle threshold the fan is held off
+        let (result, state, _) = set_scp_then_update(1, 0, 35.0, Duration::from_secs(1));
+        assert!(matches!(result, Ok(ThermalResponse::ThermalSetScpResponse)));
+        assert_eq!(state, fan::State::Off);
+
+        // Once throttling is requested the fan joins in
+        let (_, state, _) = set_scp_then_update(1, 0, 45.0, Duration::from_secs(1));
+        assert_eq!(state, fan::State::On(fan::OnState::Min));
+    }
+
+    #[test]
+    fn unknown_cooling_policy_rejected() {
+        // The fan keeps its default active policy
+        let (result, state, _) = set_scp_then_update(2, 0, 35.0, Duration::from_secs(1));
+        assert!(matches!(result, Err(ThermalError::InvalidParameter)));
+        assert_eq!(state, fan::State::On(fan::OnState::Min));
+    }
+
+    #[test]
+    fn acoustic_limit_caps_rpm() {
+        // Hot enough to step the fan through to its max state within a few fast updates, where the mock runs at 6000 RPM
+        let (_, state, rpm) = set_scp_then_update(0, 0, 60.0, Duration::from_millis(5));
+        assert_eq!(state, fan::State::On(fan::OnState::Max));
+        assert_eq!(rpm, 6000);
+
+        // A 30 dBA limit holds the fan to the 3000 RPM the acoustic table allows
+        let (result, state, rpm) = set_scp_then_update(0, 30, 60.0, Duration::from_millis(5));
+        assert!(matches!(result, Ok(ThermalResponse::ThermalSetScpResponse)));
+        assert_eq!(state, fan::State::On(fan::OnState::Max));
+        assert_eq!(rpm, 3000);
+    }
+
+    #[test]
+    fn policy_applied_without_acoustic_table() {
+        // No acoustic limit is requested, so a fan without a table still takes the passive policy
+        let (result, state, _) = set_scp_then_update_with_table(1, 0, 35.0, Duration::from_secs(1), &[]);
+        assert!(matches!(result, Ok(ThermalResponse::ThermalSetScpResponse)));
+        assert_eq!(state, fan::State::Off);
+
+        // A limit the fan can't honor is rejected, but the policy is still applied
+        let (result, state, _) = set_scp_then_update_with_table(1, 30, 35.0, Duration::from_secs(1), &[]);
+        assert!(matches!(result, Err(ThermalError::InvalidParameter)));
+        assert_eq!(state, fan::State::Off);
+    }
+
+    #[test]
+    fn celsius_to_deci_fahrenheit() {
+        assert_eq!(DeciFahrenheit::from_celsius(0.0), DeciFahrenheit(320));
+        assert_eq!(DeciFahrenheit::from_celsius(100.0), DeciFahrenheit(2120));
+        assert_eq!(DeciFahrenheit::from_celsius(37.0), DeciFahrenheit(986));
+        assert_eq!(DeciFahrenheit::from_celsius(-40.0), DeciFahrenheit(-400));
+        assert_eq!(DeciFahrenheit::from_celsius(-20.0), DeciFahrenheit(-40));
+        assert_eq!(DeciFahrenheit::from(DeciKelvin(2982)), DeciFahrenheit(771));
+    }
+
+    #[test]
+    fn deci_fahrenheit_round_trip() {
+        for deci_f in -400..=2570 {
+            let temp = DeciFahrenheit(deci_f);
+            assert_eq!(DeciFahrenheit::from_celsius(temp.to_celsius()), temp);
+        }
+    }
+}
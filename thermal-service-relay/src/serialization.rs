@@ -1,4 +1,4 @@
-use crate::DeciKelvin;
+use crate::{DeciFahrenheit, DeciKelvin};
 use embedded_services::relay::{MessageSerializationError, SerializableMessage};
 
 // Standard MPTF requests expected by the thermal subsystem
@@ -36,7 +36,9 @@ impl From<&ThermalRequest> for ThermalCmd {
 impl From<&ThermalResponse> for ThermalCmd {
     fn from(response: &ThermalResponse) -> Self {
         match response {
-            ThermalResponse::ThermalGetTmpResponse { .. } => ThermalCmd::GetTmp,
+            ThermalResponse::ThermalGetTmpResponse { .. } | ThermalResponse::ThermalGetTmpFahrenheitResponse { .. } => {
+                ThermalCmd::GetTmp
+            }
             ThermalResponse::ThermalSetThrsResponse => ThermalCmd::SetThrs,
             ThermalResponse::ThermalGetThrsResponse { .. } => ThermalCmd::GetThrs,
             ThermalResponse::ThermalSetScpResponse => ThermalCmd::SetScp,
@@ -172,6 +174,14 @@ pub enum ThermalResponse {
     ThermalGetTmpResponse {
         temperature: DeciKelvin,
     },
+    /// `GetTmp` response for sensors configured to report in Fahrenheit.
+    ///
+    /// Shares the `GetTmp` wire format, with the temperature encoded as a two's complement dword. The wire format
+    /// doesn't carry the unit, so deserializing always produces [`Self::ThermalGetTmpResponse`], hosts that configure
+    /// Fahrenheit output reinterpret the value.
+    ThermalGetTmpFahrenheitResponse {
+        temperature: DeciFahrenheit,
+    },
     ThermalSetThrsResponse,
     ThermalGetThrsResponse {
         timeout: u32,
@@ -189,6 +199,7 @@ impl SerializableMessage for ThermalResponse {
     fn serialize(self, buffer: &mut [u8]) -> Result<usize, MessageSerializationError> {
         match self {
             Self::ThermalGetTmpResponse { temperature } => safe_put_dword(buffer, 0, temperature.0),
+            Self::ThermalGetTmpFahrenheitResponse { temperature } => safe_put_dword(buffer, 0, temperature.0 as u32),
             Self::ThermalGetThrsResponse { timeout, low, high } => Ok(safe_put_dword(buffer, 0, timeout)?
                 + safe_put_dword(buffer, 4, low.0)?
                 + safe_put_dword(buffer, 8, high.0)?),
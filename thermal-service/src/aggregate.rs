@@ -0,0 +1,97 @@
+//! Sensor driver combining several physical sensors into a single reading.
+//!
+//! A thermal zone often spans multiple physical sensors. Wrapping them in an [`AggregateSensor`] lets a single sensor
+//! service monitor the whole zone, with thresholds applied to the aggregated temperature.
+use embedded_sensors_hal_async::sensor as sensor_traits;
+use embedded_sensors_hal_async::temperature::{DegreesCelsius, TemperatureSensor};
+use embedded_services::error;
+use thermal_service_interface::sensor;
+
+/// How readings from multiple sensors are combined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Aggregation {
+    /// Report the hottest sensor.
+    #[default]
+    Max,
+    /// Report the coolest sensor.
+    Min,
+    /// Report the mean of all sensors.
+    Average,
+}
+
+/// `AggregateSensor` error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AggregateSensorError {
+    /// Every underlying sensor failed to report a temperature.
+    AllSensorsFailed,
+}
+
+impl sensor_traits::Error for AggregateSensorError {
+    fn kind(&self) -> sensor_traits::ErrorKind {
+        sensor_traits::ErrorKind::Other
+    }
+}
+
+/// Sensor driver reporting the aggregated temperature of several physical sensors.
+///
+/// Sensors that fail to report are left out of the aggregation, a reading only fails if every sensor fails.
+pub struct AggregateSensor<'hw, T: TemperatureSensor> {
+    sensors: &'hw mut [T],
+    aggregation: Aggregation,
+}
+
+impl<'hw, T: TemperatureSensor> AggregateSensor<'hw, T> {
+    /// Create a new `AggregateSensor` over `sensors`.
+    pub fn new(sensors: &'hw mut [T], aggregation: Aggregation) -> Self {
+        Self { sensors, aggregation }
+    }
+
+    /// Returns the aggregation in use.
+    pub fn aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+
+    /// Change how readings are combined.
+    pub fn set_aggregation(&mut self, aggregation: Aggregation) {
+        self.aggregation = aggregation;
+    }
+}
+
+impl<T: TemperatureSensor> sensor_traits::ErrorType for AggregateSensor<'_, T> {
+    type Error = AggregateSensorError;
+}
+
+impl<T: TemperatureSensor> TemperatureSensor for AggregateSensor<'_, T> {
+    async fn temperature(&mut self) -> Result<DegreesCelsius, Self::Error> {
+        let mut min = DegreesCelsius::MAX;
+        let mut max = DegreesCelsius::MIN;
+        let mut sum = 0.0;
+        let mut count: u16 = 0;
+
+        for (i, sensor) in self.sensors.iter_mut().enumerate() {
+            match sensor.temperature().await {
+                Ok(temp) => {
+                    min = min.min(temp);
+                    max = max.max(temp);
+                    sum += temp;
+                    count = count.saturating_add(1);
+                }
+                Err(_) => error!("Aggregated sensor {} failed to report a temperature", i),
+            }
+        }
+
+        if count == 0 {
+            return Err(AggregateSensorError::AllSensorsFailed);
+        }
+
+        Ok(match self.aggregation {
+            Aggregation::Max => max,
+            Aggregation::Min => min,
+            Aggregation::Average => sum / f32::from(count),
+        })
+    }
+}
+
+impl<T: TemperatureSensor> sensor::Driver for AggregateSensor<'_, T> {}
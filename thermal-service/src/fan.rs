@@ -1,12 +1,14 @@
-use crate::utils::SampleBuf;
+use crate::utils::{SampleBuf, persist_temp, restore_temp};
 use core::marker::PhantomData;
+use embassy_futures::select::{Either, select};
 use embassy_sync::mutex::Mutex;
 use embassy_sync::signal::Signal;
-use embassy_time::{Duration, Timer};
+use embassy_time::{Duration, Timer, with_timeout};
 use embedded_fans_async::Error as _;
+use embedded_mcu_hal::nvram::NvramStorage;
 use embedded_sensors_hal_async::temperature::DegreesCelsius;
 use embedded_services::event::NonBlockingSender;
-use embedded_services::{GlobalRawMutex, error, trace};
+use embedded_services::{GlobalRawMutex, error, trace, warn};
 use thermal_service_interface::{fan, sensor};
 
 /// Fan service configuration parameters.
@@ -19,6 +21,8 @@ pub struct Config {
     pub update_period: Duration,
     /// Whether automatic fan control based on temperature is enabled.
     pub auto_control: bool,
+    /// Whether the fan responds to temperature changes. Automatic control takes no action while disabled.
+    pub enabled: bool,
     /// Hysteresis value to prevent rapid toggling between fan states when temperature is around a state transition point.
     pub hysteresis: DegreesCelsius,
     /// Temperature at which the fan will turn on and begin running at its minimum RPM.
@@ -27,6 +31,84 @@ pub struct Config {
     pub ramp_temp: DegreesCelsius,
     /// Temperature at which the fan will run at its maximum RPM.
     pub max_temp: DegreesCelsius,
+    /// Optional RPM ceiling applied while under automatic control to keep the fan within an acoustic limit.
+    ///
+    /// When set, this takes priority over the speed curve, trading cooling performance for quieter operation.
+    pub acoustic_rpm_limit: Option<u16>,
+    /// Measured RPM to dBA relationship for this fan, used to convert between the two by piecewise linear
+    /// interpolation.
+    ///
+    /// Points must be sorted by ascending RPM with non-decreasing dBA. Values outside the table are clamped to its
+    /// first or last point. Leave empty if the fan hasn't been characterized.
+    pub acoustic_table: &'static [fan::AcousticPoint],
+    /// Optional high RPM kick applied when the fan turns on, for fans that can't reliably start at their minimum RPM.
+    pub spin_up_kick: Option<SpinUpKick>,
+    /// Optional watchdog timeout for automatic control.
+    ///
+    /// If automatic control doesn't complete an update within this long, for example because a fan command hung, it's
+    /// restarted and the fan is driven to its fail-safe speed. Must be comfortably longer than `update_period`.
+    pub watchdog_timeout: Option<Duration>,
+    /// Whether automatic control prefers the fan or SoC throttling, see [`fan::CoolingPolicy`].
+    pub cooling_policy: fan::CoolingPolicy,
+}
+
+/// Brief high RPM run used to overcome static friction when a fan starts from off.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpinUpKick {
+    /// RPM commanded during the kick. The acoustic limit isn't applied since the kick is brief.
+    pub rpm: u16,
+    /// How long to hold the kick RPM before settling to the minimum RPM.
+    pub duration: Duration,
+}
+
+impl Config {
+    /// Caps the given RPM to the acoustic limit, if one is set.
+    fn apply_acoustic_limit(&self, rpm: u16) -> u16 {
+        self.acoustic_rpm_limit.map_or(rpm, |limit| rpm.min(limit))
+    }
+
+    /// Returns the noise level at the given RPM, or `None` if there is no acoustic table.
+    fn dba_at_rpm(&self, rpm: u16) -> Option<f32> {
+        let (first, last) = (self.acoustic_table.first()?, self.acoustic_table.last()?);
+        if rpm <= first.rpm {
+            return Some(first.dba);
+        }
+        if rpm >= last.rpm {
+            return Some(last.dba);
+        }
+
+        self.acoustic_table.windows(2).find_map(|points| match points {
+            [lo, hi] if rpm <= hi.rpm => Some(if hi.rpm == lo.rpm {
+                hi.dba
+            } else {
+                lo.dba + (hi.dba - lo.dba) * f32::from(rpm - lo.rpm) / f32::from(hi.rpm - lo.rpm)
+            }),
+            _ => None,
+        })
+    }
+
+    /// Returns the highest RPM that stays within the given noise level, or `None` if there is no acoustic table.
+    fn rpm_at_dba(&self, dba: f32) -> Option<u16> {
+        let (first, last) = (self.acoustic_table.first()?, self.acoustic_table.last()?);
+        if dba <= first.dba {
+            return Some(first.rpm);
+        }
+        if dba >= last.dba {
+            return Some(last.rpm);
+        }
+
+        // Search from the loudest segment down so that flat segments resolve to their highest RPM
+        self.acoustic_table.windows(2).rev().find_map(|points| match points {
+            [lo, hi] if dba >= lo.dba => Some(if hi.dba == lo.dba {
+                hi.rpm
+            } else {
+                let rpm = f32::from(lo.rpm) + f32::from(hi.rpm - lo.rpm) * (dba - lo.dba) / (hi.dba - lo.dba);
+                rpm as u16
+            }),
+            _ => None,
+        })
+    }
 }
 
 impl Default for Config {
@@ -35,30 +117,74 @@ impl Default for Config {
             sample_period: Duration::from_secs(1),
             update_period: Duration::from_secs(1),
             auto_control: true,
+            enabled: true,
             hysteresis: 2.0,
             min_temp: 25.0,
             ramp_temp: 35.0,
             max_temp: 45.0,
+            acoustic_rpm_limit: None,
+            acoustic_table: &[],
+            spin_up_kick: None,
+            watchdog_timeout: None,
+            cooling_policy: fan::CoolingPolicy::Active,
+        }
+    }
+}
+
+/// NVRAM cells backing the state temperatures that must survive a reset.
+///
+/// On init, a temperature restored from its cell replaces the one in [`Config`]. A cell that has never been written
+/// (e.g. on first boot) leaves the configured default in place. Temperatures set afterwards are written back.
+pub struct PersistentStateTemps<'hw> {
+    /// Backs [`Config::min_temp`].
+    pub min: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// Backs [`Config::ramp_temp`].
+    pub ramp: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// Backs [`Config::max_temp`].
+    pub max: &'hw mut dyn NvramStorage<'hw, u32>,
+}
+
+impl PersistentStateTemps<'_> {
+    /// Apply any previously persisted state temperatures to `config`.
+    fn restore(&self, config: &mut Config) {
+        if let Some(temp) = restore_temp(&*self.min) {
+            config.min_temp = temp;
+        }
+        if let Some(temp) = restore_temp(&*self.ramp) {
+            config.ramp_temp = temp;
+        }
+        if let Some(temp) = restore_temp(&*self.max) {
+            config.max_temp = temp;
         }
     }
 }
 
-struct ServiceInner<T: fan::Driver, const SAMPLE_BUF_LEN: usize> {
+struct ServiceInner<'hw, T: fan::Driver, const SAMPLE_BUF_LEN: usize> {
     driver: Mutex<GlobalRawMutex, T>,
     state: Mutex<GlobalRawMutex, fan::State>,
     en_signal: Signal<GlobalRawMutex, ()>,
     config: Mutex<GlobalRawMutex, Config>,
     samples: Mutex<GlobalRawMutex, SampleBuf<u16, SAMPLE_BUF_LEN>>,
+    rpm_override: Mutex<GlobalRawMutex, Option<u16>>,
+    heartbeat: Signal<GlobalRawMutex, ()>,
+    persistent_state_temps: Mutex<GlobalRawMutex, Option<PersistentStateTemps<'hw>>>,
 }
 
-impl<T: fan::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<T, SAMPLE_BUF_LEN> {
-    fn new(driver: T, config: Config) -> Self {
+impl<'hw, T: fan::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<'hw, T, SAMPLE_BUF_LEN> {
+    fn new(driver: T, mut config: Config, persistent_state_temps: Option<PersistentStateTemps<'hw>>) -> Self {
+        if let Some(persistent_state_temps) = &persistent_state_temps {
+            persistent_state_temps.restore(&mut config);
+        }
+
         Self {
             driver: Mutex::new(driver),
             state: Mutex::new(fan::State::Off),
             en_signal: Signal::new(),
             config: Mutex::new(config),
             samples: Mutex::new(SampleBuf::create()),
+            rpm_override: Mutex::new(None),
+            heartbeat: Signal::new(),
+            persistent_state_temps: Mutex::new(persistent_state_temps),
         }
     }
 
@@ -75,6 +201,25 @@ impl<T: fan::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<T, SAMPLE_BUF_LEN
     }
 
     async fn change_state(&self, to: fan::State) -> Result<(), fan::Error> {
+        let config = *self.config.lock().await;
+        let from = *self.state.lock().await;
+
+        if let Some(kick) = config.spin_up_kick
+            && to == fan::State::On(fan::OnState::Min)
+            && from == fan::State::Off
+        {
+            // The driver is released for the kick so sampling and service calls aren't blocked while it runs
+            trace!("Kicking fan to {} RPM for {} ms", kick.rpm, kick.duration.as_millis());
+            let _ = self
+                .driver
+                .lock()
+                .await
+                .set_speed_rpm(kick.rpm)
+                .await
+                .map_err(|_| fan::Error::Hardware)?;
+            Timer::after(kick.duration).await;
+        }
+
         let mut driver = self.driver.lock().await;
         match to {
             fan::State::Off => {
@@ -87,7 +232,7 @@ impl<T: fan::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<T, SAMPLE_BUF_LEN
                 // Ramp state will continuously update RPM according to its ramp response function
             }
             fan::State::On(fan::OnState::Max) => {
-                let max_rpm = driver.max_rpm();
+                let max_rpm = config.apply_acoustic_limit(driver.max_rpm());
                 let _ = driver.set_speed_rpm(max_rpm).await.map_err(|_| fan::Error::Hardware)?;
             }
         }
@@ -99,6 +244,35 @@ impl<T: fan::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<T, SAMPLE_BUF_LEN
 
         Ok(())
     }
+
+    /// Returns once automatic control is active but hasn't reported an update within `timeout`.
+    async fn wait_for_stall(&self, timeout: Duration) {
+        loop {
+            if with_timeout(timeout, self.heartbeat.wait()).await.is_err() {
+                let config = *self.config.lock().await;
+                // No heartbeat is expected while automatic control is parked
+                if config.auto_control && config.enabled {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Drive the fan to its maximum RPM after a failure so a faulty fan errs on the side of cooling.
+    ///
+    /// The acoustic limit is deliberately ignored here.
+    async fn fail_safe(&self) {
+        let mut driver = self.driver.lock().await;
+        let max_rpm = driver.max_rpm();
+        match driver.set_speed_rpm(max_rpm).await {
+            Ok(_) => {
+                drop(driver);
+                warn!("Fan driven to fail-safe speed of {} RPM", max_rpm);
+                *self.state.lock().await = fan::State::On(fan::OnState::Max);
+            }
+            Err(e) => error!("Failed to drive fan to fail-safe speed: {:?}", e.kind()),
+        }
+    }
 }
 
 /// Fan service control handle.
@@ -109,7 +283,7 @@ pub struct Service<
     E: NonBlockingSender<fan::Event>,
     const SAMPLE_BUF_LEN: usize,
 > {
-    inner: &'hw ServiceInner<T, SAMPLE_BUF_LEN>,
+    inner: &'hw ServiceInner<'hw, T, SAMPLE_BUF_LEN>,
     _phantom: PhantomData<(S, E)>,
 }
 
@@ -132,6 +306,7 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
     fan::FanService for Service<'hw, T, S, E, SAMPLE_BUF_LEN>
 {
     async fn enable_auto_control(&self) -> Result<(), fan::Error> {
+        *self.inner.rpm_override.lock().await = None;
         self.inner.change_state(fan::State::Off).await?;
         self.inner.config.lock().await.auto_control = true;
         self.inner.en_signal.signal(());
@@ -173,6 +348,7 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
             .await
             .map_err(|_| fan::Error::Hardware)?;
         self.inner.config.lock().await.auto_control = false;
+        *self.inner.rpm_override.lock().await = None;
         Ok(())
     }
 
@@ -185,6 +361,7 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
             .await
             .map_err(|_| fan::Error::Hardware)?;
         self.inner.config.lock().await.auto_control = false;
+        *self.inner.rpm_override.lock().await = None;
         Ok(())
     }
 
@@ -197,6 +374,7 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
             .await
             .map_err(|_| fan::Error::Hardware)?;
         self.inner.config.lock().await.auto_control = false;
+        *self.inner.rpm_override.lock().await = None;
         Ok(())
     }
 
@@ -224,6 +402,119 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
             fan::OnState::Ramping => config.ramp_temp = temp,
             fan::OnState::Max => config.max_temp = temp,
         }
+
+        if let Some(persistent_state_temps) = self.inner.persistent_state_temps.lock().await.as_mut() {
+            let storage = match on_state {
+                fan::OnState::Min => &mut *persistent_state_temps.min,
+                fan::OnState::Ramping => &mut *persistent_state_temps.ramp,
+                fan::OnState::Max => &mut *persistent_state_temps.max,
+            };
+            persist_temp(storage, temp);
+        }
+    }
+
+    async fn rpm_override(&self) -> Option<u16> {
+        *self.inner.rpm_override.lock().await
+    }
+
+    async fn set_rpm_override(&self, rpm: Option<u16>) -> Result<(), fan::Error> {
+        match rpm {
+            Some(rpm) => {
+                self.inner
+                    .driver
+                    .lock()
+                    .await
+                    .set_speed_rpm(rpm)
+                    .await
+                    .map_err(|_| fan::Error::Hardware)?;
+                *self.inner.rpm_override.lock().await = Some(rpm);
+
+                // Auto control keeps running so that it can still escalate to max speed if temperature gets too high
+                self.inner.config.lock().await.auto_control = true;
+                self.inner.en_signal.signal(());
+                Ok(())
+            }
+            None => self.enable_auto_control().await,
+        }
+    }
+
+    async fn acoustic_rpm_limit(&self) -> Option<u16> {
+        self.inner.config.lock().await.acoustic_rpm_limit
+    }
+
+    async fn set_acoustic_rpm_limit(&self, limit: Option<u16>) -> Result<(), fan::Error> {
+        let mut config = self.inner.config.lock().await;
+        config.acoustic_rpm_limit = limit;
+        let auto_control = config.auto_control;
+        drop(config);
+
+        // The max state only commands its RPM on entry, so re-apply it here so the new limit takes effect immediately
+        let state = *self.inner.state.lock().await;
+        let rpm_override = *self.inner.rpm_override.lock().await;
+        if auto_control && rpm_override.is_none() && state == fan::State::On(fan::OnState::Max) {
+            self.inner.change_state(state).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn dba_estimate(&self) -> Option<f32> {
+        let rpm = self.rpm_average().await;
+        self.inner.config.lock().await.dba_at_rpm(rpm)
+    }
+
+    async fn set_acoustic_dba_limit(&self, limit: Option<f32>) -> Result<(), fan::Error> {
+        let rpm_limit = match limit {
+            Some(dba) => {
+                let rpm = self.inner.config.lock().await.rpm_at_dba(dba);
+                Some(rpm.ok_or(fan::Error::Unsupported)?)
+            }
+            None => None,
+        };
+
+        self.set_acoustic_rpm_limit(rpm_limit).await
+    }
+
+    async fn is_enabled(&self) -> bool {
+        self.inner.config.lock().await.enabled
+    }
+
+    async fn set_enabled(&self, enabled: bool) {
+        self.inner.config.lock().await.enabled = enabled;
+        if enabled {
+            self.inner.en_signal.signal(());
+        }
+    }
+
+    async fn cooling_policy(&self) -> fan::CoolingPolicy {
+        self.inner.config.lock().await.cooling_policy
+    }
+
+    async fn set_cooling_policy(&self, policy: fan::CoolingPolicy) {
+        self.inner.config.lock().await.cooling_policy = policy;
+    }
+
+    async fn info(&self) -> fan::FanInfo {
+        let (min_rpm, max_rpm) = {
+            let driver = self.inner.driver.lock().await;
+            (driver.min_rpm(), driver.max_rpm())
+        };
+        let (rpm, rpm_average) = {
+            let samples = self.inner.samples.lock().await;
+            (samples.recent(), samples.average())
+        };
+        let config = *self.inner.config.lock().await;
+
+        fan::FanInfo {
+            state: *self.inner.state.lock().await,
+            rpm,
+            rpm_average,
+            min_rpm,
+            max_rpm,
+            rpm_override: *self.inner.rpm_override.lock().await,
+            acoustic_rpm_limit: config.acoustic_rpm_limit,
+            enabled: config.enabled,
+        }
     }
 }
 
@@ -237,16 +528,18 @@ pub struct InitParams<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlock
     pub sensor_service: S,
     /// Event senders for fan events.
     pub event_senders: &'hw mut [E],
+    /// NVRAM cells to persist state temperatures across resets, or `None` to keep them in RAM only.
+    pub persistent_state_temps: Option<PersistentStateTemps<'hw>>,
 }
 
 /// The memory resources required by the fan.
-pub struct Resources<T: fan::Driver, const SAMPLE_BUF_LEN: usize> {
-    inner: Option<ServiceInner<T, SAMPLE_BUF_LEN>>,
+pub struct Resources<'hw, T: fan::Driver, const SAMPLE_BUF_LEN: usize> {
+    inner: Option<ServiceInner<'hw, T, SAMPLE_BUF_LEN>>,
 }
 
 // Note: We can't derive Default unless we trait bound T by Default,
 // but we don't want that restriction since the default is just the None case
-impl<T: fan::Driver, const SAMPLE_BUF_LEN: usize> Default for Resources<T, SAMPLE_BUF_LEN> {
+impl<T: fan::Driver, const SAMPLE_BUF_LEN: usize> Default for Resources<'_, T, SAMPLE_BUF_LEN> {
     fn default() -> Self {
         Self { inner: None }
     }
@@ -260,7 +553,7 @@ pub struct Runner<
     E: NonBlockingSender<fan::Event>,
     const SAMPLE_BUF_LEN: usize,
 > {
-    service: &'hw ServiceInner<T, SAMPLE_BUF_LEN>,
+    service: &'hw ServiceInner<'hw, T, SAMPLE_BUF_LEN>,
     sensor: S,
     event_senders: &'hw mut [E],
 }
@@ -293,6 +586,7 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
             let range = (max_rpm - min_rpm) as f32;
             min_rpm + (ratio * range) as u16
         };
+        let rpm = config.apply_acoustic_limit(rpm);
 
         driver
             .set_speed_rpm(rpm)
@@ -304,7 +598,18 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
     async fn handle_fan_off_state(&self, temp: DegreesCelsius) -> Result<(), fan::Error> {
         let config = *self.service.config.lock().await;
 
-        if temp >= config.min_temp {
+        let start_temp = match config.cooling_policy {
+            fan::CoolingPolicy::Active => config.min_temp,
+            // Leave the first response to SoC throttling, but never hold the fan off past its max temperature
+            fan::CoolingPolicy::Passive => self
+                .sensor
+                .threshold(sensor::Threshold::Throttle)
+                .await
+                .max(config.min_temp)
+                .min(config.max_temp),
+        };
+
+        if temp >= start_temp {
             self.service.change_state(fan::State::On(fan::OnState::Min)).await?;
         }
 
@@ -347,6 +652,31 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
         Ok(())
     }
 
+    async fn handle_fan_override(&self, rpm: u16, temp: DegreesCelsius) -> Result<(), fan::Error> {
+        let config = *self.service.config.lock().await;
+        let state = *self.service.state.lock().await;
+
+        // Safety takes priority over the override, so escalate to max speed if temperature gets too high and only
+        // return to the override once temperature drops back below the hysteresis band
+        if state == fan::State::On(fan::OnState::Max) {
+            if temp >= (config.max_temp - config.hysteresis) {
+                return Ok(());
+            }
+            self.service.change_state(fan::State::On(fan::OnState::Ramping)).await?;
+        } else if temp >= config.max_temp {
+            return self.service.change_state(fan::State::On(fan::OnState::Max)).await;
+        }
+
+        self.service
+            .driver
+            .lock()
+            .await
+            .set_speed_rpm(rpm)
+            .await
+            .map(|_| ())
+            .map_err(|_| fan::Error::Hardware)
+    }
+
     async fn handle_fan_state(&self, temp: DegreesCelsius) -> Result<(), fan::Error> {
         let state = *self.service.state.lock().await;
         match state {
@@ -359,23 +689,52 @@ impl<'hw, T: fan::Driver, S: sensor::SensorService, E: NonBlockingSender<fan::Ev
 
     async fn handle_auto_control(&mut self) {
         loop {
-            if self.service.config.lock().await.auto_control {
+            let config = *self.service.config.lock().await;
+            if config.auto_control && config.enabled {
                 let temp = self.sensor.temperature().await;
-                if let Err(e) = self.handle_fan_state(temp).await {
+                let rpm_override = *self.service.rpm_override.lock().await;
+                let result = match rpm_override {
+                    Some(rpm) => self.handle_fan_override(rpm, temp).await,
+                    None => self.handle_fan_state(temp).await,
+                };
+
+                if let Err(e) = result {
                     error!("Error handling fan state transition, disabling auto control: {:?}", e);
                     self.service.config.lock().await.auto_control = false;
+                    self.service.fail_safe().await;
                     self.broadcast_event(fan::Event::Failure(e));
                 }
 
+                self.service.heartbeat.signal(());
                 let sleep_duration = self.service.config.lock().await.update_period;
                 Timer::after(sleep_duration).await;
 
-            // Sleep until auto control is re-enabled
+            // Sleep until auto control or the fan is re-enabled
             } else {
                 self.service.en_signal.wait().await;
             }
         }
     }
+
+    /// Run automatic control, restarting it and escalating cooling if the watchdog detects a stall.
+    async fn supervise_auto_control(&mut self) {
+        let service = self.service;
+        loop {
+            let watchdog_timeout = service.config.lock().await.watchdog_timeout;
+            let Some(timeout) = watchdog_timeout else {
+                self.handle_auto_control().await;
+                continue;
+            };
+
+            service.heartbeat.reset();
+            if let Either::Second(()) = select(self.handle_auto_control(), service.wait_for_stall(timeout)).await {
+                // Dropping the stalled control loop cancels the hung command and releases the driver
+                error!("Fan auto control stalled, restarting and driving fan to fail-safe speed");
+                service.fail_safe().await;
+                self.broadcast_event(fan::Event::Failure(fan::Error::Stalled));
+            }
+        }
+    }
 }
 
 impl<
@@ -389,7 +748,7 @@ impl<
     async fn run(mut self) -> embedded_services::Never {
         let service = self.service;
         loop {
-            let _ = embassy_futures::join::join(service.handle_sampling(), self.handle_auto_control()).await;
+            let _ = embassy_futures::join::join(service.handle_sampling(), self.supervise_auto_control()).await;
         }
     }
 }
@@ -403,7 +762,7 @@ impl<
 > odp_service_common::runnable_service::Service<'hw> for Service<'hw, T, S, E, SAMPLE_BUF_LEN>
 {
     type Runner = Runner<'hw, T, S, E, SAMPLE_BUF_LEN>;
-    type Resources = Resources<T, SAMPLE_BUF_LEN>;
+    type Resources = Resources<'hw, T, SAMPLE_BUF_LEN>;
 }
 
 impl<
@@ -416,12 +775,14 @@ impl<
 {
     /// Initializes an instance of the fan service.
     pub async fn new(
-        service_storage: &'hw mut Resources<T, SAMPLE_BUF_LEN>,
+        service_storage: &'hw mut Resources<'hw, T, SAMPLE_BUF_LEN>,
         init_params: InitParams<'hw, T, S, E>,
     ) -> Result<(Self, Runner<'hw, T, S, E, SAMPLE_BUF_LEN>), fan::Error> {
-        let service = service_storage
-            .inner
-            .insert(ServiceInner::new(init_params.driver, init_params.config));
+        let service = service_storage.inner.insert(ServiceInner::new(
+            init_params.driver,
+            init_params.config,
+            init_params.persistent_state_temps,
+        ));
         Ok((
             Self {
                 inner: service,
@@ -435,3 +796,82 @@ impl<
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Noise rising from 20 to 30 dBA, flat until 4000 RPM, then rising to 40 dBA
+    const TABLE: &[fan::AcousticPoint] = &[
+        fan::AcousticPoint { rpm: 1000, dba: 20.0 },
+        fan::AcousticPoint { rpm: 3000, dba: 30.0 },
+        fan::AcousticPoint { rpm: 4000, dba: 30.0 },
+        fan::AcousticPoint { rpm: 6000, dba: 40.0 },
+    ];
+
+    fn config(acoustic_table: &'static [fan::AcousticPoint]) -> Config {
+        Config {
+            acoustic_table,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dba_at_rpm_interpolates() {
+        let config = config(TABLE);
+        assert_eq!(config.dba_at_rpm(1000), Some(20.0));
+        assert_eq!(config.dba_at_rpm(2000), Some(25.0));
+        assert_eq!(config.dba_at_rpm(3500), Some(30.0));
+        assert_eq!(config.dba_at_rpm(5000), Some(35.0));
+        assert_eq!(config.dba_at_rpm(6000), Some(40.0));
+    }
+
+    #[test]
+    fn dba_at_rpm_clamps() {
+        let config = config(TABLE);
+        assert_eq!(config.dba_at_rpm(0), Some(20.0));
+        assert_eq!(config.dba_at_rpm(u16::MAX), Some(40.0));
+    }
+
+    #[test]
+    fn rpm_at_dba_interpolates() {
+        let config = config(TABLE);
+        assert_eq!(config.rpm_at_dba(25.0), Some(2000));
+        assert_eq!(config.rpm_at_dba(35.0), Some(5000));
+        // Flat segments resolve to their highest RPM
+        assert_eq!(config.rpm_at_dba(30.0), Some(4000));
+    }
+
+    #[test]
+    fn rpm_at_dba_clamps() {
+        let config = config(TABLE);
+        assert_eq!(config.rpm_at_dba(10.0), Some(1000));
+        assert_eq!(config.rpm_at_dba(50.0), Some(6000));
+    }
+
+    #[test]
+    fn single_point_table() {
+        let config = config(&[fan::AcousticPoint { rpm: 2500, dba: 28.0 }]);
+        assert_eq!(config.dba_at_rpm(1000), Some(28.0));
+        assert_eq!(config.dba_at_rpm(4000), Some(28.0));
+        assert_eq!(config.rpm_at_dba(20.0), Some(2500));
+        assert_eq!(config.rpm_at_dba(35.0), Some(2500));
+    }
+
+    #[test]
+    fn empty_table() {
+        let config = config(&[]);
+        assert_eq!(config.dba_at_rpm(2000), None);
+        assert_eq!(config.rpm_at_dba(30.0), None);
+    }
+
+    #[test]
+    fn acoustic_limit() {
+        let mut config = config(&[]);
+        assert_eq!(config.apply_acoustic_limit(5000), 5000);
+
+        config.acoustic_rpm_limit = Some(3000);
+        assert_eq!(config.apply_acoustic_limit(5000), 3000);
+        assert_eq!(config.apply_acoustic_limit(2000), 2000);
+    }
+}
@@ -3,6 +3,7 @@
 
 use thermal_service_interface::{fan::FanService, sensor::SensorService};
 
+pub mod aggregate;
 pub mod fan;
 #[cfg(feature = "mock")]
 pub mod mock;
@@ -37,6 +37,7 @@ impl MockSensor {
         Config {
             warn_high_threshold: super::MIN_TEMP + super::TEMP_RANGE / 4.0,
             prochot_threshold: super::MIN_TEMP + super::TEMP_RANGE / 2.0,
+            throttle_threshold: super::MIN_TEMP + super::TEMP_RANGE * 5.0 / 8.0,
             critical_threshold: super::MAX_TEMP - super::TEMP_RANGE / 4.0,
             ..Default::default()
         }
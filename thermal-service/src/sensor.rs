@@ -1,7 +1,8 @@
-use crate::utils::SampleBuf;
+use crate::utils::{SampleBuf, persist_temp, restore_temp};
 use core::marker::PhantomData;
 use embassy_sync::{mutex::Mutex, signal::Signal};
 use embassy_time::{Duration, Timer, with_timeout};
+use embedded_mcu_hal::nvram::NvramStorage;
 use embedded_sensors_hal_async::temperature::DegreesCelsius;
 use embedded_services::event::NonBlockingSender;
 use embedded_services::{GlobalRawMutex, error};
@@ -47,6 +48,8 @@ pub struct Config {
     pub fast_sample_period: Duration,
     /// Whether periodic sampling is enabled.
     pub sampling_enabled: bool,
+    /// Whether threshold checking is enabled. Sampling continues while disabled.
+    pub enabled: bool,
     /// Hysteresis value to prevent rapid generation of threshold events when temperature is near a threshold.
     pub hysteresis: DegreesCelsius,
     /// Temperature threshold below which a warning event will be generated.
@@ -55,6 +58,8 @@ pub struct Config {
     pub warn_high_threshold: DegreesCelsius,
     /// Temperature threshold above which a prochot event will be generated.
     pub prochot_threshold: DegreesCelsius,
+    /// Temperature threshold above which a throttle request will be sent, normally between prochot and critical.
+    pub throttle_threshold: DegreesCelsius,
     /// Temperature threshold above which a critical event will be generated.
     pub critical_threshold: DegreesCelsius,
     /// Temperature threshold above which fast sampling is enabled.
@@ -63,6 +68,29 @@ pub struct Config {
     pub offset: DegreesCelsius,
     /// Number of retry attempts for bus operations.
     pub retry_attempts: u8,
+    /// Number of consecutive sampling failures after which the sensor is assumed to be at critical.
+    ///
+    /// When 0, sampling is instead disabled on the first failure. Otherwise sampling continues after a failure,
+    /// and once this many consecutive failures occur a critical threshold exceeded event is broadcast.
+    /// The critical state clears once a successful reading falls back below the critical threshold.
+    pub failures_before_critical: u8,
+    /// Lowest temperature the sensor can plausibly report, after applying the offset.
+    pub plausible_min: DegreesCelsius,
+    /// Highest temperature the sensor can plausibly report, after applying the offset.
+    pub plausible_max: DegreesCelsius,
+    /// Largest change from the last good reading the sensor can plausibly report between samples.
+    ///
+    /// Readings further than this from the last good one are treated like readings outside the plausible range,
+    /// unless they're also within this of the previous discarded reading. A genuine step larger than this is therefore
+    /// accepted from its second reading on, while an isolated glitch is discarded.
+    pub max_slew: DegreesCelsius,
+    /// Number of consecutive implausible readings after which a sensor fault is reported.
+    ///
+    /// Readings outside the plausible range or slew are discarded, leaving the last good reading in place, and never
+    /// trigger threshold events. Once this many occur in a row a [`sensor::Error::Implausible`] failure event is
+    /// broadcast.
+    /// When 0, the fault is reported on the first implausible reading.
+    pub implausible_before_fault: u8,
 }
 
 impl Default for Config {
@@ -71,39 +99,75 @@ impl Default for Config {
             sample_period: Duration::from_secs(1),
             fast_sample_period: Duration::from_millis(200),
             sampling_enabled: true,
+            enabled: true,
             hysteresis: 2.0,
             warn_low_threshold: DegreesCelsius::MIN,
             warn_high_threshold: DegreesCelsius::MAX,
             prochot_threshold: DegreesCelsius::MAX,
+            throttle_threshold: DegreesCelsius::MAX,
             critical_threshold: DegreesCelsius::MAX,
             fast_sampling_threshold: DegreesCelsius::MAX,
             offset: 0.0,
             retry_attempts: 5,
+            failures_before_critical: 0,
+            plausible_min: DegreesCelsius::MIN,
+            plausible_max: DegreesCelsius::MAX,
+            max_slew: DegreesCelsius::MAX,
+            implausible_before_fault: 3,
         }
     }
 }
 
-struct ServiceInner<T: sensor::Driver, const SAMPLE_BUF_LEN: usize> {
+/// NVRAM cells backing the thresholds that must survive a reset.
+///
+/// On init, a threshold restored from its cell replaces the one in [`Config`]. A cell that has never been written
+/// (e.g. on first boot) leaves the configured default in place. Thresholds set afterwards are written back.
+pub struct PersistentThresholds<'hw> {
+    /// Backs [`Config::prochot_threshold`].
+    pub prochot: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// Backs [`Config::critical_threshold`].
+    pub critical: &'hw mut dyn NvramStorage<'hw, u32>,
+}
+
+impl PersistentThresholds<'_> {
+    /// Apply any previously persisted thresholds to `config`.
+    fn restore(&self, config: &mut Config) {
+        if let Some(temp) = restore_temp(&*self.prochot) {
+            config.prochot_threshold = temp;
+        }
+        if let Some(temp) = restore_temp(&*self.critical) {
+            config.critical_threshold = temp;
+        }
+    }
+}
+
+struct ServiceInner<'hw, T: sensor::Driver, const SAMPLE_BUF_LEN: usize> {
     driver: Mutex<GlobalRawMutex, T>,
     en_signal: Signal<GlobalRawMutex, ()>,
     config: Mutex<GlobalRawMutex, Config>,
     samples: Mutex<GlobalRawMutex, SampleBuf<DegreesCelsius, SAMPLE_BUF_LEN>>,
+    persistent_thresholds: Mutex<GlobalRawMutex, Option<PersistentThresholds<'hw>>>,
 }
 
-impl<T: sensor::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<T, SAMPLE_BUF_LEN> {
-    fn new(driver: T, config: Config) -> Self {
+impl<'hw, T: sensor::Driver, const SAMPLE_BUF_LEN: usize> ServiceInner<'hw, T, SAMPLE_BUF_LEN> {
+    fn new(driver: T, mut config: Config, persistent_thresholds: Option<PersistentThresholds<'hw>>) -> Self {
+        if let Some(persistent_thresholds) = &persistent_thresholds {
+            persistent_thresholds.restore(&mut config);
+        }
+
         Self {
             driver: Mutex::new(driver),
             en_signal: Signal::new(),
             config: Mutex::new(config),
             samples: Mutex::new(SampleBuf::create()),
+            persistent_thresholds: Mutex::new(persistent_thresholds),
         }
     }
 }
 
 /// Sensor service control handle.
 pub struct Service<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_BUF_LEN: usize> {
-    inner: &'hw ServiceInner<T, SAMPLE_BUF_LEN>,
+    inner: &'hw ServiceInner<'hw, T, SAMPLE_BUF_LEN>,
     _phantom: PhantomData<E>,
 }
 
@@ -143,8 +207,17 @@ impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_B
             sensor::Threshold::WarnLow => config.warn_low_threshold = value,
             sensor::Threshold::WarnHigh => config.warn_high_threshold = value,
             sensor::Threshold::Prochot => config.prochot_threshold = value,
+            sensor::Threshold::Throttle => config.throttle_threshold = value,
             sensor::Threshold::Critical => config.critical_threshold = value,
         }
+
+        if let Some(persistent_thresholds) = self.inner.persistent_thresholds.lock().await.as_mut() {
+            match threshold {
+                sensor::Threshold::Prochot => persist_temp(persistent_thresholds.prochot, value),
+                sensor::Threshold::Critical => persist_temp(persistent_thresholds.critical, value),
+                _ => {}
+            }
+        }
     }
 
     async fn threshold(&self, threshold: sensor::Threshold) -> DegreesCelsius {
@@ -153,6 +226,7 @@ impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_B
             sensor::Threshold::WarnLow => config.warn_low_threshold,
             sensor::Threshold::WarnHigh => config.warn_high_threshold,
             sensor::Threshold::Prochot => config.prochot_threshold,
+            sensor::Threshold::Throttle => config.throttle_threshold,
             sensor::Threshold::Critical => config.critical_threshold,
         }
     }
@@ -169,6 +243,35 @@ impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_B
     async fn disable_sampling(&self) {
         self.inner.config.lock().await.sampling_enabled = false;
     }
+
+    async fn is_enabled(&self) -> bool {
+        self.inner.config.lock().await.enabled
+    }
+
+    async fn set_enabled(&self, enabled: bool) {
+        self.inner.config.lock().await.enabled = enabled;
+    }
+
+    async fn info(&self) -> sensor::SensorInfo {
+        let (temperature, temperature_average) = {
+            let samples = self.inner.samples.lock().await;
+            (samples.recent(), samples.average())
+        };
+        let config = *self.inner.config.lock().await;
+
+        sensor::SensorInfo {
+            temperature,
+            temperature_average,
+            warn_low_threshold: config.warn_low_threshold,
+            warn_high_threshold: config.warn_high_threshold,
+            prochot_threshold: config.prochot_threshold,
+            throttle_threshold: config.throttle_threshold,
+            critical_threshold: config.critical_threshold,
+            sample_period: config.sample_period,
+            sampling_enabled: config.sampling_enabled,
+            enabled: config.enabled,
+        }
+    }
 }
 
 /// Parameters required to initialize a sensor service.
@@ -179,16 +282,18 @@ pub struct InitParams<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event
     pub config: Config,
     /// Event senders for sensor events.
     pub event_senders: &'hw mut [E],
+    /// NVRAM cells to persist thresholds across resets, or `None` to keep them in RAM only.
+    pub persistent_thresholds: Option<PersistentThresholds<'hw>>,
 }
 
 /// The memory resources required by the sensor.
-pub struct Resources<T: sensor::Driver, const SAMPLE_BUF_LEN: usize> {
-    inner: Option<ServiceInner<T, SAMPLE_BUF_LEN>>,
+pub struct Resources<'hw, T: sensor::Driver, const SAMPLE_BUF_LEN: usize> {
+    inner: Option<ServiceInner<'hw, T, SAMPLE_BUF_LEN>>,
 }
 
 // Note: We can't derive Default unless we trait bound T by Default,
 // but we don't want that restriction since the default is just the None case
-impl<T: sensor::Driver, const SAMPLE_BUF_LEN: usize> Default for Resources<T, SAMPLE_BUF_LEN> {
+impl<T: sensor::Driver, const SAMPLE_BUF_LEN: usize> Default for Resources<'_, T, SAMPLE_BUF_LEN> {
     fn default() -> Self {
         Self { inner: None }
     }
@@ -201,12 +306,25 @@ struct State {
     is_warn_low: bool,
     is_warn_high: bool,
     is_prochot: bool,
+    is_throttled: bool,
     is_critical: bool,
+    consecutive_failures: u8,
+    consecutive_implausible: u8,
+    is_implausible_fault: bool,
+    last_plausible: Option<DegreesCelsius>,
+    last_implausible: Option<DegreesCelsius>,
+}
+
+impl State {
+    /// Returns true if any threshold is currently exceeded
+    fn is_elevated(&self) -> bool {
+        self.is_warn_low || self.is_warn_high || self.is_prochot || self.is_throttled || self.is_critical
+    }
 }
 
 /// A task runner for a sensor. Users must run this in an embassy task or similar async execution context.
 pub struct Runner<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_BUF_LEN: usize> {
-    service: &'hw ServiceInner<T, SAMPLE_BUF_LEN>,
+    service: &'hw ServiceInner<'hw, T, SAMPLE_BUF_LEN>,
     event_senders: &'hw mut [E],
     state: State,
 }
@@ -222,39 +340,110 @@ impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_B
         }
     }
 
+    /// Handle a failed sample, escalating to critical after too many consecutive failures.
+    async fn handle_sample_failure(&mut self, e: sensor::Error, config: &Config) {
+        self.broadcast_event(sensor::Event::Failure(e));
+
+        if config.failures_before_critical == 0 {
+            self.service.config.lock().await.sampling_enabled = false;
+            error!("Error sampling sensor, disabling sampling");
+            return;
+        }
+
+        self.state.consecutive_failures = self.state.consecutive_failures.saturating_add(1);
+        error!(
+            "Error sampling sensor, {} consecutive failures",
+            self.state.consecutive_failures
+        );
+
+        // Without a valid reading we can't rule out an over-temp, so assume the worst
+        if config.enabled
+            && self.state.consecutive_failures >= config.failures_before_critical
+            && !self.state.is_critical
+        {
+            error!("Sensor failure threshold reached, assuming critical temperature");
+            self.state.is_critical = true;
+            self.broadcast_event(sensor::Event::ThresholdExceeded(sensor::ThresholdCrossing::new(
+                sensor::Threshold::Critical,
+                config.critical_threshold,
+            )));
+        }
+    }
+
+    /// Returns true if the reading is within the plausible range and close enough to the last good reading.
+    ///
+    /// A reading close enough to the previous discarded one is also accepted, so a sustained step re-baselines the
+    /// slew check instead of being discarded forever.
+    fn is_plausible(&self, temp: DegreesCelsius, config: &Config) -> bool {
+        let in_range = (config.plausible_min..=config.plausible_max).contains(&temp);
+        let within_slew = |last: DegreesCelsius| (temp - last).abs() <= config.max_slew;
+        let in_slew =
+            self.state.last_plausible.is_none_or(within_slew) || self.state.last_implausible.is_some_and(within_slew);
+        in_range && in_slew
+    }
+
+    /// Handle an implausible reading, reporting a fault after too many in a row.
+    fn handle_implausible_sample(&mut self, temp: DegreesCelsius, config: &Config) {
+        self.state.consecutive_implausible = self.state.consecutive_implausible.saturating_add(1);
+        error!(
+            "Implausible sensor reading {}, {} consecutive",
+            temp, self.state.consecutive_implausible
+        );
+
+        if self.state.consecutive_implausible >= config.implausible_before_fault && !self.state.is_implausible_fault {
+            error!("Implausible reading threshold reached, reporting sensor fault");
+            self.state.is_implausible_fault = true;
+            self.broadcast_event(sensor::Event::Failure(sensor::Error::Implausible));
+        }
+    }
+
     async fn check_thresholds(&mut self, temp: DegreesCelsius) {
         let config = *self.service.config.lock().await;
+        let crossing = |threshold| sensor::ThresholdCrossing::new(threshold, temp);
+        let was_elevated = self.state.is_elevated();
 
         if temp >= config.warn_high_threshold && !self.state.is_warn_high {
             self.state.is_warn_high = true;
-            self.broadcast_event(sensor::Event::ThresholdExceeded(sensor::Threshold::WarnHigh));
+            self.broadcast_event(sensor::Event::ThresholdExceeded(crossing(sensor::Threshold::WarnHigh)));
         } else if temp < (config.warn_high_threshold - config.hysteresis) && self.state.is_warn_high {
             self.state.is_warn_high = false;
-            self.broadcast_event(sensor::Event::ThresholdCleared(sensor::Threshold::WarnHigh));
+            self.broadcast_event(sensor::Event::ThresholdCleared(crossing(sensor::Threshold::WarnHigh)));
         }
 
         if temp <= config.warn_low_threshold && !self.state.is_warn_low {
             self.state.is_warn_low = true;
-            self.broadcast_event(sensor::Event::ThresholdExceeded(sensor::Threshold::WarnLow));
+            self.broadcast_event(sensor::Event::ThresholdExceeded(crossing(sensor::Threshold::WarnLow)));
         } else if temp > (config.warn_low_threshold + config.hysteresis) && self.state.is_warn_low {
             self.state.is_warn_low = false;
-            self.broadcast_event(sensor::Event::ThresholdCleared(sensor::Threshold::WarnLow));
+            self.broadcast_event(sensor::Event::ThresholdCleared(crossing(sensor::Threshold::WarnLow)));
         }
 
         if temp >= config.prochot_threshold && !self.state.is_prochot {
             self.state.is_prochot = true;
-            self.broadcast_event(sensor::Event::ThresholdExceeded(sensor::Threshold::Prochot));
+            self.broadcast_event(sensor::Event::ThresholdExceeded(crossing(sensor::Threshold::Prochot)));
         } else if temp < (config.prochot_threshold - config.hysteresis) && self.state.is_prochot {
             self.state.is_prochot = false;
-            self.broadcast_event(sensor::Event::ThresholdCleared(sensor::Threshold::Prochot));
+            self.broadcast_event(sensor::Event::ThresholdCleared(crossing(sensor::Threshold::Prochot)));
+        }
+
+        if temp >= config.throttle_threshold && !self.state.is_throttled {
+            self.state.is_throttled = true;
+            self.broadcast_event(sensor::Event::ThrottleRequest(crossing(sensor::Threshold::Throttle)));
+        } else if temp < (config.throttle_threshold - config.hysteresis) && self.state.is_throttled {
+            self.state.is_throttled = false;
+            self.broadcast_event(sensor::Event::ThrottleRelease(crossing(sensor::Threshold::Throttle)));
         }
 
         if temp >= config.critical_threshold && !self.state.is_critical {
             self.state.is_critical = true;
-            self.broadcast_event(sensor::Event::ThresholdExceeded(sensor::Threshold::Critical));
+            self.broadcast_event(sensor::Event::ThresholdExceeded(crossing(sensor::Threshold::Critical)));
         } else if temp < (config.critical_threshold - config.hysteresis) && self.state.is_critical {
             self.state.is_critical = false;
-            self.broadcast_event(sensor::Event::ThresholdCleared(sensor::Threshold::Critical));
+            self.broadcast_event(sensor::Event::ThresholdCleared(crossing(sensor::Threshold::Critical)));
+        }
+
+        if was_elevated && !self.state.is_elevated() {
+            self.broadcast_event(sensor::Event::Normal(temp));
         }
     }
 }
@@ -271,21 +460,37 @@ impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event>, const SAMPLE_B
                 let temp = match with_retry!(self.service, self.service.driver.lock().await.temperature()) {
                     Ok(temp) => temp,
                     Err(e) => {
-                        self.service.config.lock().await.sampling_enabled = false;
-                        self.broadcast_event(sensor::Event::Failure(e));
-                        error!("Error sampling sensor, disabling sampling");
+                        self.handle_sample_failure(e, &config).await;
+                        if config.failures_before_critical != 0 {
+                            Timer::after(config.sample_period).await;
+                        }
                         continue;
                     }
                 };
+                self.state.consecutive_failures = 0;
 
                 // Add offset to measured temperature
                 let temp = temp + config.offset;
 
+                // Discard implausible readings, keeping the last good one
+                if !self.is_plausible(temp, &config) {
+                    self.state.last_implausible = Some(temp);
+                    self.handle_implausible_sample(temp, &config);
+                    Timer::after(config.sample_period).await;
+                    continue;
+                }
+                self.state.consecutive_implausible = 0;
+                self.state.is_implausible_fault = false;
+                self.state.last_plausible = Some(temp);
+                self.state.last_implausible = None;
+
                 // Cache in buffer for quick retrieval from other services
                 self.service.samples.lock().await.push(temp);
 
-                // Check thresholds
-                self.check_thresholds(temp).await;
+                // Check thresholds, unless disabled
+                if config.enabled {
+                    self.check_thresholds(temp).await;
+                }
 
                 // Adjust sampling rate based on how hot we are getting
                 let sleep_duration = if temp >= config.fast_sampling_threshold {
@@ -309,19 +514,21 @@ impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event> + 'hw, const SA
     odp_service_common::runnable_service::Service<'hw> for Service<'hw, T, E, SAMPLE_BUF_LEN>
 {
     type Runner = Runner<'hw, T, E, SAMPLE_BUF_LEN>;
-    type Resources = Resources<T, SAMPLE_BUF_LEN>;
+    type Resources = Resources<'hw, T, SAMPLE_BUF_LEN>;
 }
 
 impl<'hw, T: sensor::Driver, E: NonBlockingSender<sensor::Event> + 'hw, const SAMPLE_BUF_LEN: usize>
     Service<'hw, T, E, SAMPLE_BUF_LEN>
 {
     pub async fn new(
-        service_storage: &'hw mut Resources<T, SAMPLE_BUF_LEN>,
+        service_storage: &'hw mut Resources<'hw, T, SAMPLE_BUF_LEN>,
         init_params: InitParams<'hw, T, E>,
     ) -> Result<(Self, Runner<'hw, T, E, SAMPLE_BUF_LEN>), sensor::Error> {
-        let service = service_storage
-            .inner
-            .insert(ServiceInner::new(init_params.driver, init_params.config));
+        let service = service_storage.inner.insert(ServiceInner::new(
+            init_params.driver,
+            init_params.config,
+            init_params.persistent_thresholds,
+        ));
         Ok((
             Self {
                 inner: service,
@@ -1,6 +1,26 @@
 //! Helpful utilities for the thermal service.
+use embedded_mcu_hal::nvram::NvramStorage;
+use embedded_sensors_hal_async::temperature::DegreesCelsius;
 use heapless::Deque;
 
+/// Marker stored in the upper half of an NVRAM cell holding a persisted temperature
+const PERSISTED_TEMP_MARKER: u32 = 0x7E3C;
+
+/// Read a temperature written by [`persist_temp`], or `None` if the cell has never been written
+pub fn restore_temp(storage: &dyn NvramStorage<'_, u32>) -> Option<DegreesCelsius> {
+    let raw = storage.read();
+    (raw >> 16 == PERSISTED_TEMP_MARKER).then(|| f32::from(raw as u16 as i16) / 10.0)
+}
+
+/// Persist a temperature to an NVRAM cell
+///
+/// The temperature is stored in tenths of a degree, saturating at about +/-3276 degrees, which is far enough out
+/// that a saturated "disabled" threshold stays disabled once restored.
+pub fn persist_temp(storage: &mut dyn NvramStorage<'_, u32>, temp: DegreesCelsius) {
+    let deci_celsius = (temp * 10.0) as i16;
+    storage.write((PERSISTED_TEMP_MARKER << 16) | u32::from(deci_celsius as u16));
+}
+
 /// Buffer for storing samples
 pub struct SampleBuf<T: Default + Copy + core::fmt::Debug, const N: usize> {
     deque: Deque<T, N>,
@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_sensors_hal_async::temperature::TemperatureSensor;
+use thermal_service::aggregate::{AggregateSensor, AggregateSensorError, Aggregation};
+use thermal_service::mock::sensor::MockSensor;
+
+use crate::common::{Readings, ScriptedSensor};
+
+mod common;
+
+/// Mock sensors reporting 20, 25 and 30 C on their next reading, each rising by 1 C per reading
+async fn staggered_mock_sensors() -> [MockSensor; 3] {
+    let mut sensors = [MockSensor::new(); 3];
+    for (i, sensor) in sensors.iter_mut().enumerate() {
+        for _ in 0..i * 5 {
+            sensor.temperature().await.unwrap();
+        }
+    }
+    sensors
+}
+
+#[tokio::test]
+async fn aggregation() {
+    let mut sensors = staggered_mock_sensors().await;
+    let mut aggregate = AggregateSensor::new(&mut sensors, Aggregation::default());
+    assert_eq!(aggregate.aggregation(), Aggregation::Max);
+    assert_eq!(aggregate.temperature().await, Ok(30.0));
+
+    aggregate.set_aggregation(Aggregation::Min);
+    assert_eq!(aggregate.temperature().await, Ok(21.0));
+
+    aggregate.set_aggregation(Aggregation::Average);
+    assert_eq!(aggregate.temperature().await, Ok(27.0));
+}
+
+#[tokio::test]
+async fn failed_sensors_left_out() {
+    let readings = [Readings::default(), Readings::default(), Readings::default()];
+    let mut sensors = readings.each_ref().map(|readings| ScriptedSensor::new(readings, 0.0));
+    readings[0].push(20.0);
+    readings[1].push_failure();
+    readings[2].push(30.0);
+    let mut aggregate = AggregateSensor::new(&mut sensors, Aggregation::Average);
+    assert_eq!(aggregate.temperature().await, Ok(25.0));
+
+    // Counted again once it recovers
+    readings[0].push(20.0);
+    readings[1].push(40.0);
+    readings[2].push(30.0);
+    assert_eq!(aggregate.temperature().await, Ok(30.0));
+}
+
+#[tokio::test]
+async fn all_sensors_failed() {
+    let readings = [Readings::default(), Readings::default()];
+    let mut sensors = readings.each_ref().map(|readings| ScriptedSensor::new(readings, 0.0));
+    for readings in &readings {
+        readings.push_failure();
+    }
+    let mut aggregate = AggregateSensor::new(&mut sensors, Aggregation::Max);
+    assert_eq!(
+        aggregate.temperature().await,
+        Err(AggregateSensorError::AllSensorsFailed)
+    );
+
+    // Recovers once any sensor reports again
+    readings[1].push(40.0);
+    assert_eq!(aggregate.temperature().await, Ok(40.0));
+}
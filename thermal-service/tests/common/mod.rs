@@ -0,0 +1,345 @@
+//! Helpers shared by the thermal service tests.
+use core::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use embassy_sync::channel::{Channel, DynamicSender};
+use embassy_time::{Duration, Timer};
+use embedded_fans_async::{ErrorType, Fan, RpmSense};
+use embedded_mcu_hal::nvram::NvramStorage;
+use embedded_sensors_hal_async::sensor as sensor_traits;
+use embedded_sensors_hal_async::temperature::{DegreesCelsius, TemperatureSensor};
+use embedded_services::GlobalRawMutex;
+use odp_service_common::runnable_service::ServiceRunner;
+use thermal_service::mock::fan::{MockFan, MockFanError};
+use thermal_service::mock::sensor::MockSensorError;
+use thermal_service_interface::{fan, sensor};
+
+/// Period at which tests sample sensors and update fans
+pub const PERIOD: Duration = Duration::from_millis(10);
+
+/// Temperature a [`ScriptedSensor`] reports before any reading is queued
+pub const AMBIENT: DegreesCelsius = 25.0;
+
+/// Number of samples services under test keep
+pub const SAMPLE_BUF_LEN: usize = 4;
+
+/// Capacity of the channels tests receive events on
+pub const EVENT_CHANNEL_SIZE: usize = 16;
+
+/// Channel tests receive sensor events on
+pub type SensorEventChannel = Channel<GlobalRawMutex, sensor::Event, EVENT_CHANNEL_SIZE>;
+
+/// Channel tests receive fan events on
+pub type FanEventChannel = Channel<GlobalRawMutex, fan::Event, EVENT_CHANNEL_SIZE>;
+
+/// Fan service under test, controlled by a [`StubSensor`]
+pub type TestFan<'a, T> =
+    thermal_service::fan::Service<'a, T, &'a StubSensor, DynamicSender<'a, fan::Event>, SAMPLE_BUF_LEN>;
+
+/// Sensor service under test
+pub type TestSensor<'a> =
+    thermal_service::sensor::Service<'a, ScriptedSensor<'a>, DynamicSender<'a, sensor::Event>, SAMPLE_BUF_LEN>;
+
+/// Readings replayed by a [`ScriptedSensor`], in order
+#[derive(Default)]
+pub struct Readings {
+    queue: RefCell<VecDeque<Result<DegreesCelsius, MockSensorError>>>,
+    samples: Cell<u32>,
+}
+
+impl Readings {
+    /// Queue a successful reading
+    pub fn push(&self, temp: DegreesCelsius) {
+        self.queue.borrow_mut().push_back(Ok(temp));
+    }
+
+    /// Queue a failed reading
+    pub fn push_failure(&self) {
+        self.queue.borrow_mut().push_back(Err(MockSensorError));
+    }
+
+    /// Number of times the sensor has been sampled, including once the readings ran out
+    pub fn samples(&self) -> u32 {
+        self.samples.get()
+    }
+
+    /// Wait until every queued reading has been sampled and processed
+    pub async fn consumed(&self) {
+        // The sensor runner finishes processing a reading before it yields again
+        while !self.queue.borrow().is_empty() {
+            Timer::after_millis(1).await;
+        }
+    }
+}
+
+/// Sensor driver replaying [`Readings`], which repeats its last good reading once they run out
+pub struct ScriptedSensor<'a> {
+    readings: &'a Readings,
+    last: DegreesCelsius,
+}
+
+impl<'a> ScriptedSensor<'a> {
+    /// Create a new `ScriptedSensor` reporting `initial` until a reading is queued
+    pub fn new(readings: &'a Readings, initial: DegreesCelsius) -> Self {
+        Self {
+            readings,
+            last: initial,
+        }
+    }
+}
+
+impl sensor_traits::ErrorType for ScriptedSensor<'_> {
+    type Error = MockSensorError;
+}
+
+impl TemperatureSensor for ScriptedSensor<'_> {
+    async fn temperature(&mut self) -> Result<DegreesCelsius, Self::Error> {
+        self.readings.samples.set(self.readings.samples.get() + 1);
+        match self.readings.queue.borrow_mut().pop_front() {
+            Some(Ok(temp)) => {
+                self.last = temp;
+                Ok(temp)
+            }
+            Some(Err(e)) => Err(e),
+            None => Ok(self.last),
+        }
+    }
+}
+
+impl sensor::Driver for ScriptedSensor<'_> {}
+
+/// Sensor service reporting whatever temperature the test sets, to drive fan automatic control directly
+pub struct StubSensor {
+    temp: Cell<DegreesCelsius>,
+    reads: Cell<u32>,
+}
+
+impl StubSensor {
+    /// Create a new `StubSensor` reporting `temp`
+    pub fn new(temp: DegreesCelsius) -> Self {
+        Self {
+            temp: Cell::new(temp),
+            reads: Cell::new(0),
+        }
+    }
+
+    /// Change the reported temperature
+    pub fn set_temperature(&self, temp: DegreesCelsius) {
+        self.temp.set(temp);
+    }
+
+    /// Number of times the temperature has been read through [`sensor::SensorService::temperature`]
+    pub fn reads(&self) -> u32 {
+        self.reads.get()
+    }
+}
+
+impl sensor::SensorService for StubSensor {
+    async fn temperature(&self) -> DegreesCelsius {
+        self.reads.set(self.reads.get() + 1);
+        self.temp.get()
+    }
+
+    async fn temperature_average(&self) -> DegreesCelsius {
+        self.temp.get()
+    }
+
+    async fn temperature_immediate(&self) -> Result<DegreesCelsius, sensor::Error> {
+        Ok(self.temp.get())
+    }
+
+    async fn set_threshold(&self, _threshold: sensor::Threshold, _value: DegreesCelsius) {}
+
+    async fn threshold(&self, _threshold: sensor::Threshold) -> DegreesCelsius {
+        DegreesCelsius::MAX
+    }
+
+    async fn set_sample_period(&self, _period: Duration) {}
+
+    async fn enable_sampling(&self) {}
+
+    async fn disable_sampling(&self) {}
+
+    async fn is_enabled(&self) -> bool {
+        true
+    }
+
+    async fn set_enabled(&self, _enabled: bool) {}
+
+    async fn info(&self) -> sensor::SensorInfo {
+        sensor::SensorInfo {
+            temperature: self.temp.get(),
+            temperature_average: self.temp.get(),
+            warn_low_threshold: DegreesCelsius::MIN,
+            warn_high_threshold: DegreesCelsius::MAX,
+            prochot_threshold: DegreesCelsius::MAX,
+            throttle_threshold: DegreesCelsius::MAX,
+            critical_threshold: DegreesCelsius::MAX,
+            sample_period: PERIOD,
+            sampling_enabled: true,
+            enabled: true,
+        }
+    }
+}
+
+/// Faults a [`FaultyFan`] injects into its upcoming speed commands
+#[derive(Default)]
+pub struct FanFaults {
+    failures: Cell<u8>,
+    hang: Cell<bool>,
+}
+
+impl FanFaults {
+    /// Fail the next `count` speed commands
+    pub fn fail_next(&self, count: u8) {
+        self.failures.set(count);
+    }
+
+    /// Never complete the next speed command
+    pub fn hang_next(&self) {
+        self.hang.set(true);
+    }
+}
+
+/// [`MockFan`] whose speed commands fail or hang as set up through [`FanFaults`]
+pub struct FaultyFan<'a> {
+    fan: MockFan,
+    faults: &'a FanFaults,
+}
+
+impl<'a> FaultyFan<'a> {
+    /// Create a new `FaultyFan`, with no faults until some are set up through `faults`
+    pub fn new(faults: &'a FanFaults) -> Self {
+        Self {
+            fan: MockFan::new(),
+            faults,
+        }
+    }
+}
+
+impl ErrorType for FaultyFan<'_> {
+    type Error = MockFanError;
+}
+
+impl Fan for FaultyFan<'_> {
+    fn min_rpm(&self) -> u16 {
+        self.fan.min_rpm()
+    }
+
+    fn max_rpm(&self) -> u16 {
+        self.fan.max_rpm()
+    }
+
+    fn min_start_rpm(&self) -> u16 {
+        self.fan.min_start_rpm()
+    }
+
+    async fn set_speed_rpm(&mut self, rpm: u16) -> Result<u16, Self::Error> {
+        if self.faults.hang.take() {
+            core::future::pending::<()>().await;
+        }
+
+        let failures = self.faults.failures.get();
+        if failures > 0 {
+            self.faults.failures.set(failures - 1);
+            return Err(MockFanError);
+        }
+
+        self.fan.set_speed_rpm(rpm).await
+    }
+}
+
+impl RpmSense for FaultyFan<'_> {
+    async fn rpm(&mut self) -> Result<u16, Self::Error> {
+        self.fan.rpm().await
+    }
+}
+
+impl fan::Driver for FaultyFan<'_> {}
+
+/// Fan config sampling and updating at [`PERIOD`], turning on at 30 C, ramping from 40 C and at max from 50 C
+pub fn fan_config() -> thermal_service::fan::Config {
+    thermal_service::fan::Config {
+        sample_period: PERIOD,
+        update_period: PERIOD,
+        min_temp: 30.0,
+        ramp_temp: 40.0,
+        max_temp: 50.0,
+        ..Default::default()
+    }
+}
+
+/// Sensor config sampling at [`PERIOD`] without retries, so each queued reading is one sample
+pub fn sensor_config() -> thermal_service::sensor::Config {
+    thermal_service::sensor::Config {
+        sample_period: PERIOD,
+        fast_sample_period: PERIOD,
+        retry_attempts: 1,
+        ..Default::default()
+    }
+}
+
+/// Take every event currently queued on `channel`
+pub fn drain<E, const N: usize>(channel: &Channel<GlobalRawMutex, E, N>) -> Vec<E> {
+    core::iter::from_fn(|| channel.try_receive().ok()).collect()
+}
+
+/// Run a sensor service replaying `readings` with `config` alongside `test`
+///
+/// `test` receives the service and the channel its events are sent on. The service stops once `test` completes.
+pub async fn run_sensor(
+    readings: &Readings,
+    config: thermal_service::sensor::Config,
+    test: impl AsyncFnOnce(TestSensor<'_>, &SensorEventChannel),
+) {
+    let events = SensorEventChannel::new();
+    let mut senders = [events.dyn_sender()];
+    let mut resources = thermal_service::sensor::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (sensor, runner) = thermal_service::sensor::Service::new(
+        &mut resources,
+        thermal_service::sensor::InitParams {
+            driver: ScriptedSensor::new(readings, AMBIENT),
+            config,
+            event_senders: &mut senders,
+            persistent_thresholds: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    tokio::select! {
+        never = runner.run() => match never {},
+        _ = test(sensor, &events) => {}
+    }
+}
+
+/// Run a fan service over `driver` with `config` alongside `test`, controlled by `sensor`
+///
+/// `test` receives the service and the channel its events are sent on. The service stops once `test` completes.
+pub async fn run_fan<T: fan::Driver>(
+    driver: T,
+    sensor: &StubSensor,
+    config: thermal_service::fan::Config,
+    test: impl AsyncFnOnce(TestFan<'_, T>, &FanEventChannel),
+) {
+    let events = FanEventChannel::new();
+    let mut senders = [events.dyn_sender()];
+    let mut resources = thermal_service::fan::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (fan, runner) = thermal_service::fan::Service::new(
+        &mut resources,
+        thermal_service::fan::InitParams {
+            driver,
+            config,
+            sensor_service: sensor,
+            event_senders: &mut senders,
+            persistent_state_temps: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    tokio::select! {
+        never = runner.run() => match never {},
+        _ = test(fan, &events) => {}
+    }
+}
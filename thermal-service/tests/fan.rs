@@ -0,0 +1,196 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_time::{Duration, Timer, with_timeout};
+use thermal_service::fan::SpinUpKick;
+use thermal_service::mock::fan::MockFan;
+use thermal_service_interface::fan::{Error, Event, FanService, OnState, State};
+
+use crate::common::{FanFaults, FaultyFan, PERIOD, SAMPLE_BUF_LEN, StubSensor};
+
+mod common;
+
+/// Test that info reports every fan attribute
+#[tokio::test]
+async fn info_reports_all_attributes() {
+    // Cool enough that automatic control leaves the fan off
+    let sensor = StubSensor::new(20.0);
+
+    common::run_fan(MockFan::new(), &sensor, common::fan_config(), async |fan, _events| {
+        fan.set_rpm(3000).await.unwrap();
+        fan.set_acoustic_rpm_limit(Some(4000)).await.unwrap();
+        // Wait for the sample buffer to fill with the new RPM
+        Timer::after(PERIOD * (SAMPLE_BUF_LEN as u32 + 2)).await;
+
+        let info = fan.info().await;
+        assert_eq!(info.state, State::Off);
+        assert_eq!(info.rpm, 3000);
+        assert_eq!(info.rpm_average, 3000);
+        assert_eq!(info.min_rpm, 0);
+        assert_eq!(info.max_rpm, 6000);
+        assert_eq!(info.rpm_override, None);
+        assert_eq!(info.acoustic_rpm_limit, Some(4000));
+        assert!(info.enabled);
+
+        fan.set_rpm_override(Some(2000)).await.unwrap();
+        fan.set_enabled(false).await;
+        Timer::after(PERIOD * (SAMPLE_BUF_LEN as u32 + 2)).await;
+
+        let info = fan.info().await;
+        assert_eq!(info.rpm, 2000);
+        assert_eq!(info.rpm_average, 2000);
+        assert_eq!(info.rpm_override, Some(2000));
+        assert!(!info.enabled);
+    })
+    .await;
+}
+
+/// Test that a failed fan command drives the fan to max RPM and stops automatic control
+#[tokio::test]
+async fn fail_safe_after_auto_control_failure() {
+    let faults = FanFaults::default();
+    // Hot enough to turn the fan on, which fails
+    let sensor = StubSensor::new(35.0);
+    faults.fail_next(1);
+
+    let config = thermal_service::fan::Config {
+        // The fail-safe speed ignores the acoustic limit
+        acoustic_rpm_limit: Some(3000),
+        ..common::fan_config()
+    };
+    common::run_fan(FaultyFan::new(&faults), &sensor, config, async |fan, events| {
+        Timer::after(PERIOD * 3).await;
+
+        assert_eq!(common::drain(events), [Event::Failure(Error::Hardware)]);
+        assert_eq!(fan.info().await.state, State::On(OnState::Max));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 6000);
+
+        // Automatic control no longer responds, even once cool again
+        sensor.set_temperature(20.0);
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.info().await.state, State::On(OnState::Max));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 6000);
+
+        // Until it's re-enabled
+        fan.enable_auto_control().await.unwrap();
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.info().await.state, State::Off);
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 0);
+        assert!(common::drain(events).is_empty());
+    })
+    .await;
+}
+
+/// Test that the spin-up kick doesn't block other access to the fan while it runs
+#[tokio::test]
+async fn spin_up_kick_releases_driver() {
+    const KICK_DURATION: Duration = Duration::from_millis(200);
+
+    // Hot enough to turn the fan on
+    let sensor = StubSensor::new(35.0);
+
+    let config = thermal_service::fan::Config {
+        spin_up_kick: Some(SpinUpKick {
+            rpm: 5000,
+            duration: KICK_DURATION,
+        }),
+        ..common::fan_config()
+    };
+    common::run_fan(MockFan::new(), &sensor, config, async |fan, _events| {
+        Timer::after(PERIOD * 3).await;
+
+        // Mid-kick, the fan reports the kick RPM without waiting for the kick to finish
+        let rpm = with_timeout(PERIOD, fan.rpm_immediate()).await.unwrap();
+        assert_eq!(rpm.unwrap(), 5000);
+        let info = with_timeout(PERIOD, fan.info()).await.unwrap();
+        assert_eq!(info.state, State::Off);
+
+        // Then settles to its minimum start RPM
+        Timer::after(KICK_DURATION).await;
+        assert_eq!(fan.info().await.state, State::On(OnState::Min));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 1000);
+    })
+    .await;
+}
+
+/// Test that a stalled automatic control loop is restarted after driving the fan to its fail-safe speed
+#[tokio::test]
+async fn watchdog_restarts_stalled_auto_control() {
+    const WATCHDOG_TIMEOUT: Duration = Duration::from_millis(50);
+
+    let faults = FanFaults::default();
+    // Hot enough to turn the fan on, which hangs
+    let sensor = StubSensor::new(35.0);
+    faults.hang_next();
+
+    let config = thermal_service::fan::Config {
+        watchdog_timeout: Some(WATCHDOG_TIMEOUT),
+        ..common::fan_config()
+    };
+    common::run_fan(FaultyFan::new(&faults), &sensor, config, async |fan, events| {
+        Timer::after(PERIOD * 2).await;
+        assert!(common::drain(events).is_empty());
+        assert_eq!(fan.info().await.state, State::Off);
+
+        // Hot enough that the restarted loop keeps the fan at max
+        sensor.set_temperature(55.0);
+        Timer::after(WATCHDOG_TIMEOUT + PERIOD * 2).await;
+        assert_eq!(common::drain(events), [Event::Failure(Error::Stalled)]);
+        assert_eq!(fan.info().await.state, State::On(OnState::Max));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 6000);
+
+        // Automatic control is running again
+        sensor.set_temperature(20.0);
+        Timer::after(PERIOD * 6).await;
+        assert_eq!(fan.info().await.state, State::Off);
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 0);
+        assert!(common::drain(events).is_empty());
+    })
+    .await;
+}
+
+/// Test that an RPM override holds regardless of temperature, escalates to max without flapping, and can be cleared
+#[tokio::test]
+async fn rpm_override() {
+    let sensor = StubSensor::new(20.0);
+
+    common::run_fan(MockFan::new(), &sensor, common::fan_config(), async |fan, events| {
+        fan.set_rpm_override(Some(2000)).await.unwrap();
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 2000);
+
+        // Held as temperature changes below the max temperature
+        sensor.set_temperature(45.0);
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 2000);
+
+        // Escalated at the max temperature
+        sensor.set_temperature(50.0);
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.info().await.state, State::On(OnState::Max));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 6000);
+
+        // Still escalated within the hysteresis band
+        sensor.set_temperature(49.0);
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.info().await.state, State::On(OnState::Max));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 6000);
+
+        // Back to the override below it
+        sensor.set_temperature(47.0);
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.info().await.state, State::On(OnState::Ramping));
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 2000);
+        assert_eq!(fan.rpm_override().await, Some(2000));
+
+        // Clearing the override resumes automatic control
+        sensor.set_temperature(20.0);
+        fan.set_rpm_override(None).await.unwrap();
+        Timer::after(PERIOD * 3).await;
+        assert_eq!(fan.rpm_override().await, None);
+        assert_eq!(fan.info().await.state, State::Off);
+        assert_eq!(fan.rpm_immediate().await.unwrap(), 0);
+        assert!(common::drain(events).is_empty());
+    })
+    .await;
+}
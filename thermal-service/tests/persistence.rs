@@ -0,0 +1,151 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use core::cell::Cell;
+
+use embedded_sensors_hal_async::temperature::DegreesCelsius;
+use embedded_services::event::NoopSender;
+use thermal_service::mock::fan::MockFan;
+use thermal_service_interface::fan::{FanService, OnState};
+use thermal_service_interface::sensor::{SensorService, Threshold};
+
+use crate::common::{AMBIENT, CellNvram, Readings, SAMPLE_BUF_LEN, ScriptedSensor, StubSensor};
+
+mod common;
+
+const PROCHOT: DegreesCelsius = 50.0;
+const CRITICAL: DegreesCelsius = 60.0;
+
+/// Contents of an NVRAM cell that has been erased but never written
+const ERASED: u32 = 0xFFFF_FFFF;
+
+/// Create a sensor service backed by the given NVRAM cells and return its prochot and critical thresholds
+///
+/// If given, `set` is applied through the service afterwards.
+async fn sensor_thresholds(
+    prochot: &Cell<u32>,
+    critical: &Cell<u32>,
+    set: Option<(DegreesCelsius, DegreesCelsius)>,
+) -> (DegreesCelsius, DegreesCelsius) {
+    let mut prochot = CellNvram::new(prochot);
+    let mut critical = CellNvram::new(critical);
+    let readings = Readings::default();
+    let mut senders: [NoopSender; 0] = [];
+    let mut resources = thermal_service::sensor::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (sensor, _runner) = thermal_service::sensor::Service::new(
+        &mut resources,
+        thermal_service::sensor::InitParams {
+            driver: ScriptedSensor::new(&readings, AMBIENT),
+            config: thermal_service::sensor::Config {
+                prochot_threshold: PROCHOT,
+                critical_threshold: CRITICAL,
+                ..common::sensor_config()
+            },
+            event_senders: &mut senders,
+            persistent_thresholds: Some(thermal_service::sensor::PersistentThresholds {
+                prochot: &mut prochot,
+                critical: &mut critical,
+            }),
+        },
+    )
+    .await
+    .unwrap();
+
+    let thresholds = (
+        sensor.threshold(Threshold::Prochot).await,
+        sensor.threshold(Threshold::Critical).await,
+    );
+    if let Some((prochot, critical)) = set {
+        sensor.set_threshold(Threshold::Prochot, prochot).await;
+        sensor.set_threshold(Threshold::Critical, critical).await;
+    }
+    thresholds
+}
+
+/// Create a fan service backed by the given NVRAM cells and return its min, ramp and max state temperatures
+///
+/// If given, `set` is applied through the service afterwards.
+async fn fan_state_temps(cells: &[Cell<u32>; 3], set: Option<[DegreesCelsius; 3]>) -> [DegreesCelsius; 3] {
+    let [mut min, mut ramp, mut max] = cells.each_ref().map(CellNvram::new);
+    let sensor = StubSensor::new(AMBIENT);
+    let mut senders: [NoopSender; 0] = [];
+    let mut resources = thermal_service::fan::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (fan, _runner) = thermal_service::fan::Service::new(
+        &mut resources,
+        thermal_service::fan::InitParams {
+            driver: MockFan::new(),
+            config: common::fan_config(),
+            sensor_service: &sensor,
+            event_senders: &mut senders,
+            persistent_state_temps: Some(thermal_service::fan::PersistentStateTemps {
+                min: &mut min,
+                ramp: &mut ramp,
+                max: &mut max,
+            }),
+        },
+    )
+    .await
+    .unwrap();
+
+    let on_states = [OnState::Min, OnState::Ramping, OnState::Max];
+    let mut temps = [0.0; 3];
+    for (temp, on_state) in temps.iter_mut().zip(on_states) {
+        *temp = fan.state_temp(on_state).await;
+    }
+    if let Some(set) = set {
+        for (temp, on_state) in set.into_iter().zip(on_states) {
+            fan.set_state_temp(on_state, temp).await;
+        }
+    }
+    temps
+}
+
+/// Test that thresholds set through the sensor service are restored by the next service over the same cells
+#[tokio::test]
+async fn sensor_thresholds_round_trip() {
+    let prochot = Cell::new(ERASED);
+    let critical = Cell::new(ERASED);
+
+    assert_eq!(
+        sensor_thresholds(&prochot, &critical, Some((55.5, 72.5))).await,
+        (PROCHOT, CRITICAL)
+    );
+    assert_ne!(prochot.get(), ERASED);
+    assert_ne!(critical.get(), ERASED);
+
+    assert_eq!(sensor_thresholds(&prochot, &critical, None).await, (55.5, 72.5));
+}
+
+/// Test that erased or invalid cells leave the configured thresholds in place
+#[tokio::test]
+async fn sensor_thresholds_invalid_storage() {
+    for raw in [ERASED, 0, 0x1234_5678] {
+        let prochot = Cell::new(raw);
+        let critical = Cell::new(raw);
+        assert_eq!(sensor_thresholds(&prochot, &critical, None).await, (PROCHOT, CRITICAL));
+        // Nothing is written back until a threshold is set
+        assert_eq!(prochot.get(), raw);
+        assert_eq!(critical.get(), raw);
+    }
+}
+
+/// Test that state temperatures set through the fan service are restored by the next service over the same cells
+#[tokio::test]
+async fn fan_state_temps_round_trip() {
+    let cells = [Cell::new(ERASED), Cell::new(ERASED), Cell::new(ERASED)];
+
+    assert_eq!(
+        fan_state_temps(&cells, Some([32.5, 45.0, 57.5])).await,
+        [30.0, 40.0, 50.0]
+    );
+    assert_eq!(fan_state_temps(&cells, None).await, [32.5, 45.0, 57.5]);
+}
+
+/// Test that erased or invalid cells leave the configured state temperatures in place
+#[tokio::test]
+async fn fan_state_temps_invalid_storage() {
+    for raw in [ERASED, 0, 0x1234_5678] {
+        let cells = [Cell::new(raw), Cell::new(raw), Cell::new(raw)];
+        assert_eq!(fan_state_temps(&cells, None).await, [30.0, 40.0, 50.0]);
+    }
+}
@@ -0,0 +1,285 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use odp_service_common::runnable_service::ServiceRunner;
+use thermal_service::mock::sensor::MockSensor;
+use thermal_service_interface::sensor::{
+    Error, Event, SensorService, Threshold, ThresholdCrossing, ThresholdDirection,
+};
+
+use crate::common::{Readings, SAMPLE_BUF_LEN, SensorEventChannel};
+
+mod common;
+
+const CRITICAL: f32 = 60.0;
+
+fn config() -> thermal_service::sensor::Config {
+    thermal_service::sensor::Config {
+        critical_threshold: CRITICAL,
+        ..common::sensor_config()
+    }
+}
+
+/// Test that consecutive sampling failures escalate to critical once, and clear after a good sample
+#[tokio::test]
+async fn sample_failures_escalate_to_critical() {
+    const FAILURES: u8 = 3;
+
+    let readings = Readings::default();
+    // A good sample restarts the count
+    for _ in 1..FAILURES {
+        readings.push_failure();
+    }
+    readings.push(30.0);
+    for _ in 0..FAILURES {
+        readings.push_failure();
+    }
+    readings.push(30.0);
+
+    let config = thermal_service::sensor::Config {
+        failures_before_critical: FAILURES,
+        ..config()
+    };
+    common::run_sensor(&readings, config, async |_sensor, events| {
+        readings.consumed().await;
+
+        let failure = Event::Failure(Error::RetryExhausted);
+        assert_eq!(
+            common::drain(events),
+            [
+                failure,
+                failure,
+                failure,
+                failure,
+                failure,
+                // The last reading can't be trusted, so the configured critical threshold is reported
+                Event::ThresholdExceeded(ThresholdCrossing::new(Threshold::Critical, CRITICAL)),
+                Event::ThresholdCleared(ThresholdCrossing::new(Threshold::Critical, 30.0)),
+                Event::Normal(30.0),
+            ]
+        );
+    })
+    .await;
+}
+
+/// Test that without a failure threshold, sampling is disabled on the first failure
+#[tokio::test]
+async fn sample_failure_disables_sampling() {
+    let readings = Readings::default();
+    readings.push_failure();
+
+    common::run_sensor(&readings, config(), async |sensor, events| {
+        readings.consumed().await;
+
+        assert_eq!(common::drain(events), [Event::Failure(Error::RetryExhausted)]);
+        assert!(!sensor.info().await.sampling_enabled);
+    })
+    .await;
+}
+
+/// Test that a single Normal event follows the last cleared threshold
+#[tokio::test]
+async fn normal_after_last_threshold_clears() {
+    let readings = Readings::default();
+    for temp in [30.0, 45.0, 50.0, 44.0, 39.0, 35.0, 30.0] {
+        readings.push(temp);
+    }
+
+    let config = thermal_service::sensor::Config {
+        warn_high_threshold: 40.0,
+        prochot_threshold: 48.0,
+        hysteresis: 2.0,
+        ..config()
+    };
+    common::run_sensor(&readings, config, async |_sensor, events| {
+        readings.consumed().await;
+
+        assert_eq!(
+            common::drain(events),
+            [
+                Event::ThresholdExceeded(ThresholdCrossing::new(Threshold::WarnHigh, 45.0)),
+                Event::ThresholdExceeded(ThresholdCrossing::new(Threshold::Prochot, 50.0)),
+                // Still above the warning threshold, so not yet normal
+                Event::ThresholdCleared(ThresholdCrossing::new(Threshold::Prochot, 44.0)),
+                Event::ThresholdCleared(ThresholdCrossing::new(Threshold::WarnHigh, 35.0)),
+                Event::Normal(35.0),
+            ]
+        );
+    })
+    .await;
+}
+
+/// Test that throttling is requested once between prochot and critical, and released below the threshold
+#[tokio::test]
+async fn throttle_requested_once() {
+    let readings = Readings::default();
+    for temp in [50.0, 55.0, 57.0, 56.0, 58.0, 53.0, 51.0, 45.0] {
+        readings.push(temp);
+    }
+
+    let config = thermal_service::sensor::Config {
+        prochot_threshold: 48.0,
+        throttle_threshold: 54.0,
+        hysteresis: 2.0,
+        ..config()
+    };
+    common::run_sensor(&readings, config, async |_sensor, events| {
+        readings.consumed().await;
+
+        assert_eq!(
+            common::drain(events),
+            [
+                Event::ThresholdExceeded(ThresholdCrossing::new(Threshold::Prochot, 50.0)),
+                // Not repeated while the temperature stays above the throttle threshold
+                Event::ThrottleRequest(ThresholdCrossing::new(Threshold::Throttle, 55.0)),
+                // Not released until the temperature drops below the hysteresis band
+                Event::ThrottleRelease(ThresholdCrossing::new(Threshold::Throttle, 51.0)),
+                Event::ThresholdCleared(ThresholdCrossing::new(Threshold::Prochot, 45.0)),
+                Event::Normal(45.0),
+            ]
+        );
+    })
+    .await;
+}
+
+/// Test that out of range and excessive slew readings are discarded rather than cached and averaged
+#[tokio::test]
+async fn implausible_samples_discarded() {
+    let readings = Readings::default();
+    for temp in [30.0, 31.0, 32.0, 33.0, 500.0, 95.0] {
+        readings.push(temp);
+    }
+
+    let config = thermal_service::sensor::Config {
+        plausible_min: -40.0,
+        plausible_max: 150.0,
+        max_slew: 10.0,
+        ..config()
+    };
+    common::run_sensor(&readings, config, async |sensor, events| {
+        readings.consumed().await;
+
+        // Neither is treated as critical, and two in a row aren't enough to report a fault
+        assert!(common::drain(events).is_empty());
+        assert_eq!(sensor.temperature().await, 33.0);
+        assert_eq!(sensor.temperature_average().await, 31.5);
+
+        // A reading within the slew of the last good one is accepted again
+        readings.push(35.0);
+        readings.consumed().await;
+        assert_eq!(sensor.temperature().await, 35.0);
+        assert_eq!(sensor.temperature_average().await, 32.75);
+    })
+    .await;
+}
+
+/// Test that a sustained step larger than the slew limit is accepted once a second reading confirms it
+#[tokio::test]
+async fn sustained_step_rebaselines_slew() {
+    let readings = Readings::default();
+    for temp in [30.0, 31.0, 50.0, 51.0, 52.0] {
+        readings.push(temp);
+    }
+
+    let config = thermal_service::sensor::Config {
+        max_slew: 10.0,
+        ..config()
+    };
+    common::run_sensor(&readings, config, async |sensor, events| {
+        readings.consumed().await;
+
+        // Only the first reading of the step is discarded
+        assert!(common::drain(events).is_empty());
+        assert_eq!(sensor.temperature().await, 52.0);
+        assert_eq!(sensor.temperature_average().await, 41.0);
+    })
+    .await;
+}
+
+/// Test that a fault is reported once after consecutive implausible readings, and clears after a good one
+#[tokio::test]
+async fn implausible_samples_report_fault() {
+    let readings = Readings::default();
+    readings.push(30.0);
+    for _ in 0..4 {
+        readings.push(500.0);
+    }
+    readings.push(30.0);
+    for _ in 0..3 {
+        readings.push(500.0);
+    }
+
+    let config = thermal_service::sensor::Config {
+        plausible_max: 150.0,
+        implausible_before_fault: 3,
+        ..config()
+    };
+    common::run_sensor(&readings, config, async |sensor, events| {
+        readings.consumed().await;
+
+        let fault = Event::Failure(Error::Implausible);
+        assert_eq!(common::drain(events), [fault, fault]);
+        assert_eq!(sensor.temperature().await, 30.0);
+    })
+    .await;
+}
+
+/// Test that threshold events carry the direction and temperature of each crossing
+#[tokio::test]
+async fn threshold_crossing_direction() {
+    let events = SensorEventChannel::new();
+    let mut senders = [events.dyn_sender()];
+    let mut resources = thermal_service::sensor::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (_sensor, runner) = thermal_service::sensor::Service::new(
+        &mut resources,
+        thermal_service::sensor::InitParams {
+            // Rises by 1 C per reading from 20 C to 40 C, then falls back
+            driver: MockSensor::new(),
+            config: thermal_service::sensor::Config {
+                warn_low_threshold: 22.0,
+                warn_high_threshold: 38.0,
+                hysteresis: 1.0,
+                ..common::sensor_config()
+            },
+            event_senders: &mut senders,
+            persistent_thresholds: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    tokio::select! {
+        never = runner.run() => match never {},
+        _ = async {
+            let mut received = Vec::new();
+            for _ in 0..7 {
+                received.push(events.receive().await);
+            }
+
+            assert_eq!(
+                received,
+                [
+                    Event::ThresholdExceeded(ThresholdCrossing {
+                        threshold: Threshold::WarnLow,
+                        direction: ThresholdDirection::Below,
+                        temperature: 20.0,
+                    }),
+                    Event::ThresholdCleared(ThresholdCrossing::new(Threshold::WarnLow, 24.0)),
+                    Event::Normal(24.0),
+                    Event::ThresholdExceeded(ThresholdCrossing {
+                        threshold: Threshold::WarnHigh,
+                        direction: ThresholdDirection::Above,
+                        temperature: 38.0,
+                    }),
+                    Event::ThresholdCleared(ThresholdCrossing {
+                        threshold: Threshold::WarnHigh,
+                        direction: ThresholdDirection::Above,
+                        temperature: 36.0,
+                    }),
+                    Event::Normal(36.0),
+                    Event::ThresholdExceeded(ThresholdCrossing::new(Threshold::WarnLow, 22.0)),
+                ]
+            );
+        } => {}
+    }
+}
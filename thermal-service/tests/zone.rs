@@ -0,0 +1,304 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_time::{Duration, Timer};
+use embedded_services::event::NoopSender;
+use odp_service_common::runnable_service::ServiceRunner;
+use thermal_service::mock::fan::MockFan;
+use thermal_service_interface::ThermalService;
+use thermal_service_interface::fan::{self, FanService};
+use thermal_service_interface::sensor::{self, SensorService};
+
+use crate::common::{
+    AMBIENT, FanEventChannel, PERIOD, Readings, SAMPLE_BUF_LEN, ScriptedSensor, SensorEventChannel, StubSensor,
+    TestFan, TestSensor,
+};
+
+mod common;
+
+/// Test that a disabled zone keeps sampling but raises no events and leaves its fan alone
+#[tokio::test]
+async fn disabled_zone_takes_no_action() {
+    let readings = Readings::default();
+    // Hot enough to exceed every threshold and run the fan at max
+    readings.push(70.0);
+
+    let sensor_events = SensorEventChannel::new();
+    let mut sensor_senders = [sensor_events.dyn_sender()];
+    let mut sensor_resources = thermal_service::sensor::Resources::<_, 4>::default();
+    let (sensor, sensor_runner) = thermal_service::sensor::Service::new(
+        &mut sensor_resources,
+        thermal_service::sensor::InitParams {
+            driver: ScriptedSensor::new(&readings, 25.0),
+            config: thermal_service::sensor::Config {
+                warn_high_threshold: 40.0,
+                prochot_threshold: 50.0,
+                critical_threshold: 60.0,
+                ..common::sensor_config()
+            },
+            event_senders: &mut sensor_senders,
+            persistent_thresholds: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut fan_senders: [NoopSender; 0] = [];
+    let mut fan_resources = thermal_service::fan::Resources::<_, 4>::default();
+    let (fan, fan_runner) = thermal_service::fan::Service::new(
+        &mut fan_resources,
+        thermal_service::fan::InitParams {
+            driver: MockFan::new(),
+            config: thermal_service::fan::Config {
+                update_period: PERIOD,
+                ..MockFan::config()
+            },
+            sensor_service: sensor,
+            event_senders: &mut fan_senders,
+            persistent_state_temps: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let sensors = [sensor];
+    let fans = [fan];
+    let mut resources = thermal_service::Resources::default();
+    let service = thermal_service::Service::init(
+        &mut resources,
+        thermal_service::InitParams {
+            sensors: &sensors,
+            fans: &fans,
+        },
+    );
+
+    assert!(service.set_zone_enabled(0, false).await);
+    assert!(!service.set_zone_enabled(1, false).await);
+
+    tokio::select! {
+        never = sensor_runner.run() => match never {},
+        never = fan_runner.run() => match never {},
+        _ = async {
+            readings.consumed().await;
+            Timer::after(PERIOD * 3).await;
+
+            // The temperature is still reported
+            assert_eq!(sensor.temperature().await, 70.0);
+            assert!(!sensor.is_enabled().await);
+            assert!(common::drain(&sensor_events).is_empty());
+            assert!(!fan.is_enabled().await);
+            assert_eq!(fan.info().await.state, fan::State::Off);
+            assert_eq!(fan.rpm_immediate().await.unwrap(), 0);
+
+            // Re-enabling the zone picks up where it left off
+            assert!(service.set_zone_enabled(0, true).await);
+            Timer::after(PERIOD * 5).await;
+
+            let events = common::drain(&sensor_events);
+            assert!(events.contains(&sensor::Event::ThresholdExceeded(sensor::ThresholdCrossing::new(
+                sensor::Threshold::Critical,
+                70.0
+            ))));
+            assert_ne!(fan.info().await.state, fan::State::Off);
+        } => {}
+    }
+}
+
+/// Test that a zone snapshot includes whichever of a sensor and fan the zone has
+#[tokio::test]
+async fn dump_state() {
+    let readings = Readings::default();
+    let sensor_events = SensorEventChannel::new();
+    let mut sensor_senders = [sensor_events.dyn_sender()];
+    let mut sensor_resources = thermal_service::sensor::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (sensor, _sensor_runner) = thermal_service::sensor::Service::new(
+        &mut sensor_resources,
+        thermal_service::sensor::InitParams {
+            driver: ScriptedSensor::new(&readings, AMBIENT),
+            config: thermal_service::sensor::Config {
+                prochot_threshold: 50.0,
+                ..common::sensor_config()
+            },
+            event_senders: &mut sensor_senders,
+            persistent_thresholds: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let stub_sensor = StubSensor::new(AMBIENT);
+    let fan_events = FanEventChannel::new();
+    let mut fan_senders = [fan_events.dyn_sender()];
+    let mut fan_resources = thermal_service::fan::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (fan, _fan_runner) = thermal_service::fan::Service::new(
+        &mut fan_resources,
+        thermal_service::fan::InitParams {
+            driver: MockFan::new(),
+            config: thermal_service::fan::Config {
+                acoustic_rpm_limit: Some(4000),
+                ..common::fan_config()
+            },
+            sensor_service: &stub_sensor,
+            event_senders: &mut fan_senders,
+            persistent_state_temps: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Sensor only
+    let sensors = [sensor];
+    let no_fans: [TestFan<'_, MockFan>; 0] = [];
+    let mut resources = thermal_service::Resources::default();
+    let service = thermal_service::Service::init(
+        &mut resources,
+        thermal_service::InitParams {
+            sensors: &sensors,
+            fans: &no_fans,
+        },
+    );
+    let zone = service.dump_state(0).await.unwrap();
+    assert_eq!(zone.sensor, Some(sensor.info().await));
+    assert_eq!(zone.sensor.unwrap().prochot_threshold, 50.0);
+    assert_eq!(zone.fan, None);
+    assert_eq!(service.dump_state(1).await, None);
+
+    // Fan only
+    let no_sensors: [TestSensor<'_>; 0] = [];
+    let fans = [fan];
+    let mut resources = thermal_service::Resources::default();
+    let service = thermal_service::Service::init(
+        &mut resources,
+        thermal_service::InitParams {
+            sensors: &no_sensors,
+            fans: &fans,
+        },
+    );
+    let zone = service.dump_state(0).await.unwrap();
+    assert_eq!(zone.sensor, None);
+    assert_eq!(zone.fan, Some(fan.info().await));
+    assert_eq!(zone.fan.unwrap().acoustic_rpm_limit, Some(4000));
+    assert_eq!(service.dump_state(1).await, None);
+}
+
+/// Test that each zone's sample period applies to both its sensor and fan, independently of other zones
+#[tokio::test]
+async fn set_zone_sample_period() {
+    /// Ratio of the slow zone's period to the fast zone's
+    const RATIO: u32 = 4;
+    const FAST_PERIOD: Duration = PERIOD;
+    /// Number of slow zone samples to measure over
+    const SLOW_SAMPLES: u32 = 4;
+
+    let slow_period = FAST_PERIOD * RATIO;
+
+    let fast_readings = Readings::default();
+    let mut fast_sensor_senders: [NoopSender; 0] = [];
+    let mut fast_sensor_resources = thermal_service::sensor::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (fast_sensor, fast_sensor_runner) = thermal_service::sensor::Service::new(
+        &mut fast_sensor_resources,
+        thermal_service::sensor::InitParams {
+            driver: ScriptedSensor::new(&fast_readings, AMBIENT),
+            config: common::sensor_config(),
+            event_senders: &mut fast_sensor_senders,
+            persistent_thresholds: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let slow_readings = Readings::default();
+    let mut slow_sensor_senders: [NoopSender; 0] = [];
+    let mut slow_sensor_resources = thermal_service::sensor::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (slow_sensor, slow_sensor_runner) = thermal_service::sensor::Service::new(
+        &mut slow_sensor_resources,
+        thermal_service::sensor::InitParams {
+            driver: ScriptedSensor::new(&slow_readings, AMBIENT),
+            config: common::sensor_config(),
+            event_senders: &mut slow_sensor_senders,
+            persistent_thresholds: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Each fan reads its stub sensor once per update
+    let fast_stub_sensor = StubSensor::new(AMBIENT);
+    let slow_stub_sensor = StubSensor::new(AMBIENT);
+    let mut fast_fan_senders: [NoopSender; 0] = [];
+    let mut fast_fan_resources = thermal_service::fan::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (fast_fan, fast_fan_runner) = thermal_service::fan::Service::new(
+        &mut fast_fan_resources,
+        thermal_service::fan::InitParams {
+            driver: MockFan::new(),
+            config: common::fan_config(),
+            sensor_service: &fast_stub_sensor,
+            event_senders: &mut fast_fan_senders,
+            persistent_state_temps: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut slow_fan_senders: [NoopSender; 0] = [];
+    let mut slow_fan_resources = thermal_service::fan::Resources::<_, SAMPLE_BUF_LEN>::default();
+    let (slow_fan, slow_fan_runner) = thermal_service::fan::Service::new(
+        &mut slow_fan_resources,
+        thermal_service::fan::InitParams {
+            driver: MockFan::new(),
+            config: common::fan_config(),
+            sensor_service: &slow_stub_sensor,
+            event_senders: &mut slow_fan_senders,
+            persistent_state_temps: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let sensors = [fast_sensor, slow_sensor];
+    let fans = [fast_fan, slow_fan];
+    let mut resources = thermal_service::Resources::default();
+    let service = thermal_service::Service::init(
+        &mut resources,
+        thermal_service::InitParams {
+            sensors: &sensors,
+            fans: &fans,
+        },
+    );
+
+    assert!(!service.set_zone_sample_period(2, slow_period).await);
+    assert!(service.set_zone_sample_period(0, FAST_PERIOD).await);
+    assert!(service.set_zone_sample_period(1, slow_period).await);
+    assert_eq!(fast_sensor.info().await.sample_period, FAST_PERIOD);
+    assert_eq!(slow_sensor.info().await.sample_period, slow_period);
+
+    // Wait until the slow zone's sensor and fan have each taken `count` more samples
+    let wait_for_slow_samples = async |count: u32| {
+        let target = (slow_readings.samples() + count, slow_stub_sensor.reads() + count);
+        while slow_readings.samples() < target.0 || slow_stub_sensor.reads() < target.1 {
+            Timer::after_millis(1).await;
+        }
+    };
+
+    tokio::select! {
+        never = fast_sensor_runner.run() => match never {},
+        never = slow_sensor_runner.run() => match never {},
+        never = fast_fan_runner.run() => match never {},
+        never = slow_fan_runner.run() => match never {},
+        _ = async {
+            // Let both zones finish any period started before the change
+            wait_for_slow_samples(2).await;
+
+            // Count the fast zone's sensor samples and fan updates while the slow zone takes `SLOW_SAMPLES` of each
+            let (fast_samples, fast_updates) = (fast_readings.samples(), fast_stub_sensor.reads());
+            wait_for_slow_samples(SLOW_SAMPLES).await;
+            let fast_samples = fast_readings.samples() - fast_samples;
+            let fast_updates = fast_stub_sensor.reads() - fast_updates;
+
+            // Allow one slow period of slack either way for scheduling jitter
+            let expected = (SLOW_SAMPLES - 1) * RATIO..=(SLOW_SAMPLES + 1) * RATIO;
+            assert!(expected.contains(&fast_samples), "{fast_samples} fast samples, expected {expected:?}");
+            assert!(expected.contains(&fast_updates), "{fast_updates} fast updates, expected {expected:?}");
+        } => {}
+    }
+}
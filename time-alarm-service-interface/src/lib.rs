@@ -109,6 +109,12 @@ pub trait TimeAlarmService {
     /// Change the current time.  Analogous to ACPI TAD's _SRT method.
     fn set_real_time(&self, timestamp: AcpiTimestamp) -> Result<(), DatetimeClockError>;
 
+    /// Query the current time zone and daylight savings time status without reading the clock.
+    fn get_time_zone(&self) -> (AcpiTimeZone, AcpiDaylightSavingsTimeStatus);
+
+    /// Change the time zone and daylight savings time status without changing the clock.
+    fn set_time_zone(&self, time_zone: AcpiTimeZone, dst_status: AcpiDaylightSavingsTimeStatus);
+
     /// Query the current wake status.  Analogous to ACPI TAD's _GWS method.
     fn get_wake_status(&self, timer_id: AcpiTimerId) -> TimerStatus;
 
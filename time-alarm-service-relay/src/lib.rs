@@ -3,7 +3,7 @@
 use time_alarm_service_interface::TimeAlarmService;
 
 mod serialization;
-pub use serialization::{AcpiTimeAlarmRequest, AcpiTimeAlarmResponse, AcpiTimeAlarmResult};
+pub use serialization::{AcpiTimeAlarmError, AcpiTimeAlarmRequest, AcpiTimeAlarmResponse, AcpiTimeAlarmResult};
 
 /// A relay handler that converts MCTP messages into function calls against the time-alarm service.
 pub struct TimeAlarmServiceRelayHandler<T: TimeAlarmService> {
@@ -33,6 +33,14 @@ impl<T: TimeAlarmService> embedded_services::relay::mctp::RelayServiceHandler fo
                 self.service.set_real_time(timestamp)?;
                 Ok(AcpiTimeAlarmResponse::OkNoData)
             }
+            AcpiTimeAlarmRequest::GetTimeZone => {
+                let (time_zone, dst_status) = self.service.get_time_zone();
+                Ok(AcpiTimeAlarmResponse::TimeZone(time_zone, dst_status))
+            }
+            AcpiTimeAlarmRequest::SetTimeZone(time_zone, dst_status) => {
+                self.service.set_time_zone(time_zone, dst_status);
+                Ok(AcpiTimeAlarmResponse::OkNoData)
+            }
             AcpiTimeAlarmRequest::GetWakeStatus(timer_id) => Ok(AcpiTimeAlarmResponse::TimerStatus(
                 self.service.get_wake_status(timer_id),
             )),
@@ -54,6 +62,7 @@ impl<T: TimeAlarmService> embedded_services::relay::mctp::RelayServiceHandler fo
             AcpiTimeAlarmRequest::GetTimerValue(timer_id) => Ok(AcpiTimeAlarmResponse::TimerSeconds(
                 self.service.get_timer_value(timer_id)?,
             )),
+            AcpiTimeAlarmRequest::InvalidCommand(command_code) => Err(AcpiTimeAlarmError::InvalidCommand(command_code)),
         }
     }
 }
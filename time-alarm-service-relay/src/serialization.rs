@@ -1,7 +1,7 @@
 use core::array::TryFromSliceError;
 use embedded_services::relay::{MessageSerializationError, SerializableMessage};
 use time_alarm_service_interface::{
-    AcpiDaylightSavingsTimeStatus, AcpiTimerId, AcpiTimestamp, AlarmExpiredWakePolicy, AlarmTimerSeconds,
+    AcpiDaylightSavingsTimeStatus, AcpiTimeZone, AcpiTimerId, AcpiTimestamp, AlarmExpiredWakePolicy, AlarmTimerSeconds,
     TimeAlarmDeviceCapabilities, TimerStatus,
 };
 
@@ -21,6 +21,9 @@ pub enum AcpiTimeAlarmRequest {
     GetTimerValue(AcpiTimerId),                                 // _TIV
     SetExpiredTimerPolicy(AcpiTimerId, AlarmExpiredWakePolicy), // _STP
     GetExpiredTimerPolicy(AcpiTimerId),                         // _TIP
+    GetTimeZone,                                                // No ACPI equivalent
+    SetTimeZone(AcpiTimeZone, AcpiDaylightSavingsTimeStatus),   // No ACPI equivalent
+    InvalidCommand(u16),                                        // Unknown command code or malformed payload
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, num_enum::IntoPrimitive, num_enum::TryFromPrimitive)]
@@ -36,6 +39,8 @@ enum AcpiTimeAlarmRequestDiscriminant {
     GetTimerValue = 7,
     SetExpiredTimerPolicy = 8,
     GetExpiredTimerPolicy = 9,
+    GetTimeZone = 10,
+    SetTimeZone = 11,
 }
 
 impl SerializableMessage for AcpiTimeAlarmRequest {
@@ -67,6 +72,9 @@ impl SerializableMessage for AcpiTimeAlarmRequest {
                 safe_put_u32(buffer, 4, alarm_expired_wake_policy.0)?;
                 Ok(8)
             }
+            Self::GetTimeZone => Ok(0),
+            Self::SetTimeZone(time_zone, dst_status) => safe_put_time_zone(buffer, 0, time_zone, dst_status),
+            Self::InvalidCommand(_) => Ok(0),
         }
     }
 
@@ -85,10 +93,21 @@ impl SerializableMessage for AcpiTimeAlarmRequest {
             AcpiTimeAlarmRequest::GetExpiredTimerPolicy(_) => {
                 AcpiTimeAlarmRequestDiscriminant::GetExpiredTimerPolicy.into()
             }
+            AcpiTimeAlarmRequest::GetTimeZone => AcpiTimeAlarmRequestDiscriminant::GetTimeZone.into(),
+            AcpiTimeAlarmRequest::SetTimeZone(_, _) => AcpiTimeAlarmRequestDiscriminant::SetTimeZone.into(),
+            AcpiTimeAlarmRequest::InvalidCommand(command_code) => *command_code,
         }
     }
 
+    /// Unknown command codes and malformed payloads deserialize to [`AcpiTimeAlarmRequest::InvalidCommand`] so that
+    /// the host receives an error response carrying the offending command code instead of no response at all.
     fn deserialize(discriminant: u16, buffer: &[u8]) -> Result<Self, MessageSerializationError> {
+        Ok(Self::deserialize_command(discriminant, buffer).unwrap_or(Self::InvalidCommand(discriminant)))
+    }
+}
+
+impl AcpiTimeAlarmRequest {
+    fn deserialize_command(discriminant: u16, buffer: &[u8]) -> Result<Self, MessageSerializationError> {
         let discriminant = AcpiTimeAlarmRequestDiscriminant::try_from(discriminant)
             .map_err(|_| MessageSerializationError::UnknownMessageDiscriminant(discriminant))?;
         match discriminant {
@@ -98,6 +117,11 @@ impl SerializableMessage for AcpiTimeAlarmRequest {
                 AcpiTimestamp::try_from_bytes(buffer)
                     .map_err(|_| MessageSerializationError::InvalidPayload("Could not deserialize timestamp"))?,
             )),
+            AcpiTimeAlarmRequestDiscriminant::GetTimeZone => Ok(AcpiTimeAlarmRequest::GetTimeZone),
+            AcpiTimeAlarmRequestDiscriminant::SetTimeZone => {
+                let (time_zone, dst_status) = safe_get_time_zone(buffer, 0)?;
+                Ok(AcpiTimeAlarmRequest::SetTimeZone(time_zone, dst_status))
+            }
             _ => {
                 let (timer_id, buffer) = buffer
                     .split_at_checked(4)
@@ -170,7 +194,10 @@ pub enum AcpiTimeAlarmResponse {
     /// Response to a _TIV request, containing the current timer value for the specified timer.
     TimerSeconds(AlarmTimerSeconds),
 
-    /// Operation succeeded, but there's no data to return - response to methods that just return a boolean - _SRT, _CWS, _STP, _STV
+    /// Response to a GetTimeZone request, containing the current time zone and daylight savings time status.
+    TimeZone(AcpiTimeZone, AcpiDaylightSavingsTimeStatus),
+
+    /// Operation succeeded, but there's no data to return - response to methods that just return a boolean - _SRT, _CWS, _STP, _STV, SetTimeZone
     OkNoData,
 }
 
@@ -183,6 +210,7 @@ enum AcpiTimeAlarmResponseDiscriminant {
     WakePolicy = 4,
     TimerSeconds = 5,
     OkNoData = 6,
+    TimeZone = 7,
 }
 
 impl SerializableMessage for AcpiTimeAlarmResponse {
@@ -201,6 +229,7 @@ impl SerializableMessage for AcpiTimeAlarmResponse {
             Self::TimerStatus(timer_status) => safe_put_u32(buffer, 0, timer_status.0),
             Self::WakePolicy(wake_policy) => safe_put_u32(buffer, 0, wake_policy.0),
             Self::TimerSeconds(timer_seconds) => safe_put_u32(buffer, 0, timer_seconds.0),
+            Self::TimeZone(time_zone, dst_status) => safe_put_time_zone(buffer, 0, time_zone, dst_status),
             Self::OkNoData => Ok(0),
         }
     }
@@ -212,6 +241,7 @@ impl SerializableMessage for AcpiTimeAlarmResponse {
             Self::TimerStatus(_) => AcpiTimeAlarmResponseDiscriminant::TimerStatus.into(),
             Self::WakePolicy(_) => AcpiTimeAlarmResponseDiscriminant::WakePolicy.into(),
             Self::TimerSeconds(_) => AcpiTimeAlarmResponseDiscriminant::TimerSeconds.into(),
+            Self::TimeZone(_, _) => AcpiTimeAlarmResponseDiscriminant::TimeZone.into(),
             Self::OkNoData => AcpiTimeAlarmResponseDiscriminant::OkNoData.into(),
         }
     }
@@ -237,35 +267,67 @@ impl SerializableMessage for AcpiTimeAlarmResponse {
             AcpiTimeAlarmResponseDiscriminant::TimerSeconds => {
                 Ok(Self::TimerSeconds(AlarmTimerSeconds(safe_get_u32(buffer, 0)?)))
             }
+            AcpiTimeAlarmResponseDiscriminant::TimeZone => {
+                let (time_zone, dst_status) = safe_get_time_zone(buffer, 0)?;
+                Ok(Self::TimeZone(time_zone, dst_status))
+            }
             AcpiTimeAlarmResponseDiscriminant::OkNoData => Ok(Self::OkNoData),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, num_enum::IntoPrimitive, num_enum::TryFromPrimitive)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-#[repr(u16)]
 pub enum AcpiTimeAlarmError {
+    UnspecifiedFailure,
+    /// The request had an unknown command code or a malformed payload, contains the command code that was sent.
+    InvalidCommand(u16),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, num_enum::IntoPrimitive, num_enum::TryFromPrimitive)]
+#[repr(u16)]
+enum AcpiTimeAlarmErrorDiscriminant {
     UnspecifiedFailure = 1,
+    InvalidCommand = 2,
 }
 
 impl SerializableMessage for AcpiTimeAlarmError {
-    fn serialize(self, _buffer: &mut [u8]) -> Result<usize, MessageSerializationError> {
+    fn serialize(self, buffer: &mut [u8]) -> Result<usize, MessageSerializationError> {
         match self {
             Self::UnspecifiedFailure => Ok(0),
+            Self::InvalidCommand(command_code) => {
+                let command_code = command_code.to_le_bytes();
+                buffer
+                    .get_mut(..command_code.len())
+                    .ok_or(MessageSerializationError::BufferTooSmall)?
+                    .copy_from_slice(&command_code);
+                Ok(command_code.len())
+            }
         }
     }
 
     fn discriminant(&self) -> u16 {
-        (*self).into()
+        match self {
+            Self::UnspecifiedFailure => AcpiTimeAlarmErrorDiscriminant::UnspecifiedFailure.into(),
+            Self::InvalidCommand(_) => AcpiTimeAlarmErrorDiscriminant::InvalidCommand.into(),
+        }
     }
 
-    fn deserialize(discriminant: u16, _buffer: &[u8]) -> Result<Self, MessageSerializationError> {
-        let discriminant = AcpiTimeAlarmError::try_from(discriminant)
+    fn deserialize(discriminant: u16, buffer: &[u8]) -> Result<Self, MessageSerializationError> {
+        let discriminant = AcpiTimeAlarmErrorDiscriminant::try_from(discriminant)
             .map_err(|_| MessageSerializationError::UnknownMessageDiscriminant(discriminant))?;
 
         match discriminant {
-            AcpiTimeAlarmError::UnspecifiedFailure => Ok(AcpiTimeAlarmError::UnspecifiedFailure),
+            AcpiTimeAlarmErrorDiscriminant::UnspecifiedFailure => Ok(AcpiTimeAlarmError::UnspecifiedFailure),
+            AcpiTimeAlarmErrorDiscriminant::InvalidCommand => {
+                Ok(AcpiTimeAlarmError::InvalidCommand(u16::from_le_bytes(
+                    buffer
+                        .get(..2)
+                        .ok_or(MessageSerializationError::BufferTooSmall)?
+                        .try_into()
+                        .map_err(|_| MessageSerializationError::BufferTooSmall)?,
+                )))
+            }
         }
     }
 }
@@ -313,3 +375,126 @@ fn safe_get_u32(buffer: &[u8], index: usize) -> Result<u32, MessageSerialization
         .map_err(|_| MessageSerializationError::BufferTooSmall)?;
     Ok(u32::from_le_bytes(bytes))
 }
+
+/// Time zone is serialized as a little-endian i16 offset in minutes (2047 if unknown) followed by the u8 daylight savings time status,
+/// matching the layout of those fields in the ACPI timestamp.
+fn safe_put_time_zone(
+    buffer: &mut [u8],
+    index: usize,
+    time_zone: AcpiTimeZone,
+    dst_status: AcpiDaylightSavingsTimeStatus,
+) -> Result<usize, MessageSerializationError> {
+    buffer
+        .get_mut(index..index + 2)
+        .ok_or(MessageSerializationError::BufferTooSmall)?
+        .copy_from_slice(&i16::from(time_zone).to_le_bytes());
+    *buffer
+        .get_mut(index + 2)
+        .ok_or(MessageSerializationError::BufferTooSmall)? = dst_status.into();
+    Ok(3)
+}
+
+fn safe_get_time_zone(
+    buffer: &[u8],
+    index: usize,
+) -> Result<(AcpiTimeZone, AcpiDaylightSavingsTimeStatus), MessageSerializationError> {
+    let time_zone_bytes = buffer
+        .get(index..index + 2)
+        .ok_or(MessageSerializationError::BufferTooSmall)?
+        .try_into()
+        .map_err(|_| MessageSerializationError::BufferTooSmall)?;
+    let time_zone = AcpiTimeZone::try_from(i16::from_le_bytes(time_zone_bytes))
+        .map_err(|_| MessageSerializationError::InvalidPayload("Could not deserialize time zone"))?;
+    let dst_status = AcpiDaylightSavingsTimeStatus::try_from(
+        *buffer.get(index + 2).ok_or(MessageSerializationError::BufferTooSmall)?,
+    )
+    .map_err(|_| MessageSerializationError::InvalidPayload("Could not deserialize daylight savings time status"))?;
+    Ok((time_zone, dst_status))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    /// Serialize `message` and deserialize the result, checking the serialized payload against `expected_payload`
+    fn round_trip<T: SerializableMessage + Copy>(message: T, expected_payload: &[u8]) -> T {
+        let mut buffer = [0u8; 16];
+        let len = message.serialize(&mut buffer).unwrap();
+        let payload = buffer.get(..len).unwrap();
+        assert_eq!(payload, expected_payload);
+        T::deserialize(message.discriminant(), payload).unwrap()
+    }
+
+    #[test]
+    fn error_discriminants() {
+        assert_eq!(
+            AcpiTimeAlarmErrorDiscriminant::try_from(1).unwrap(),
+            AcpiTimeAlarmErrorDiscriminant::UnspecifiedFailure
+        );
+        assert_eq!(
+            AcpiTimeAlarmErrorDiscriminant::try_from(2).unwrap(),
+            AcpiTimeAlarmErrorDiscriminant::InvalidCommand
+        );
+        assert!(AcpiTimeAlarmErrorDiscriminant::try_from(0).is_err());
+        assert!(AcpiTimeAlarmErrorDiscriminant::try_from(3).is_err());
+
+        assert_eq!(AcpiTimeAlarmError::UnspecifiedFailure.discriminant(), 1);
+        assert_eq!(AcpiTimeAlarmError::InvalidCommand(0x1234).discriminant(), 2);
+    }
+
+    #[test]
+    fn error_round_trip() {
+        assert_eq!(
+            round_trip(AcpiTimeAlarmError::UnspecifiedFailure, &[]),
+            AcpiTimeAlarmError::UnspecifiedFailure
+        );
+
+        // The offending command code is sent as a little-endian u16
+        assert_eq!(
+            round_trip(AcpiTimeAlarmError::InvalidCommand(0x1234), &[0x34, 0x12]),
+            AcpiTimeAlarmError::InvalidCommand(0x1234)
+        );
+    }
+
+    #[test]
+    fn error_invalid_encoding() {
+        assert!(matches!(
+            AcpiTimeAlarmError::InvalidCommand(0x1234).serialize(&mut [0u8; 1]),
+            Err(MessageSerializationError::BufferTooSmall)
+        ));
+        assert!(matches!(
+            AcpiTimeAlarmError::deserialize(2, &[0x34]),
+            Err(MessageSerializationError::BufferTooSmall)
+        ));
+        assert!(matches!(
+            AcpiTimeAlarmError::deserialize(3, &[]),
+            Err(MessageSerializationError::UnknownMessageDiscriminant(3))
+        ));
+    }
+
+    #[test]
+    fn invalid_command_request() {
+        // Unknown command code
+        let request = AcpiTimeAlarmRequest::deserialize(0x99, &[]).unwrap();
+        assert_eq!(request, AcpiTimeAlarmRequest::InvalidCommand(0x99));
+        assert_eq!(request.discriminant(), 0x99);
+        assert_eq!(request.serialize(&mut [0u8; 4]).unwrap(), 0);
+
+        // Known command code with a malformed payload
+        let get_wake_status = AcpiTimeAlarmRequestDiscriminant::GetWakeStatus.into();
+        assert_eq!(
+            AcpiTimeAlarmRequest::deserialize(get_wake_status, &[0x01]).unwrap(),
+            AcpiTimeAlarmRequest::InvalidCommand(get_wake_status)
+        );
+
+        // The error response carries the command code back to the host
+        assert_eq!(
+            round_trip(
+                AcpiTimeAlarmError::InvalidCommand(request.discriminant()),
+                &[0x99, 0x00]
+            ),
+            AcpiTimeAlarmError::InvalidCommand(0x99)
+        );
+    }
+}
@@ -6,7 +6,7 @@ use embassy_sync::signal::Signal;
 use embedded_mcu_hal::nvram::NvramStorage;
 use embedded_mcu_hal::time::{Datetime, DatetimeClock, DatetimeClockError};
 use embedded_services::GlobalRawMutex;
-use embedded_services::{info, warn};
+use embedded_services::{error, info, warn};
 use time_alarm_service_interface::*;
 
 mod timer;
@@ -69,9 +69,183 @@ use time_zone_data::TimeZoneData;
 
 // -------------------------------------------------
 
+/// Controls how outstanding timers are affected when the current time is changed via [`TimeAlarmService::set_real_time`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SetRealTimeTimerPolicy {
+    /// Leave absolute expiration times unchanged, so timers fire when the new wall-clock time reaches them.
+    #[default]
+    PreserveExpirationTime,
+    /// Shift expiration times by the same amount as the clock, so the time remaining until each timer fires is preserved.
+    ///
+    /// If the previous time can't be read, e.g. because the clock is invalid on first boot, expiration times are left unchanged.
+    PreserveTimeRemaining,
+}
+
+/// Configuration for the time-alarm service.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// How outstanding timers are adjusted when the current time is changed.
+    pub set_real_time_timer_policy: SetRealTimeTimerPolicy,
+}
+
+/// NVRAM cells the time-alarm service persists its state in, one `u32` each.
+pub struct Storage<'hw> {
+    /// Time zone and daylight savings time status.
+    pub time_zone: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// AC timer expiration time.
+    pub ac_expiration: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// AC timer expired wake policy.
+    pub ac_policy: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// DC timer expiration time.
+    pub dc_expiration: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// DC timer expired wake policy.
+    pub dc_policy: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// Clock drift compensation.
+    pub drift: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// Number of times the service has been initialized.
+    pub boot_count: &'hw mut dyn NvramStorage<'hw, u32>,
+    /// Time at which the current boot was recorded.
+    pub boot_time: &'hw mut dyn NvramStorage<'hw, u32>,
+}
+
+// -------------------------------------------------
+
+/// Boot diagnostics persisted in NVRAM.
+mod boot_record {
+    use crate::NvramStorage;
+    use embedded_mcu_hal::time::Datetime;
+
+    /// Boot counter and time, updated once at service initialization.
+    pub struct BootRecord {
+        pub boot_count: u32,
+        boot_time_secs: u32,
+    }
+
+    impl BootRecord {
+        /// Increments the boot counter and stores `now` as the last boot time.
+        ///
+        /// A stored boot time of zero means the NVRAM has never been initialized, in which case the counter is
+        /// restarted rather than trusting whatever value the counter storage holds.
+        pub fn record(
+            now: Datetime,
+            boot_count_storage: &mut dyn NvramStorage<'_, u32>,
+            boot_time_storage: &mut dyn NvramStorage<'_, u32>,
+        ) -> Self {
+            let boot_count = if boot_time_storage.read() == 0 {
+                1
+            } else {
+                boot_count_storage.read().saturating_add(1)
+            };
+
+            // Clamp rather than wrap so a clock past 2106 can't record a time that looks like an uninitialized NVRAM
+            let boot_time_secs = u32::try_from(now.unix_timestamp()).unwrap_or(u32::MAX).max(1);
+            boot_count_storage.write(boot_count);
+            boot_time_storage.write(boot_time_secs);
+
+            Self {
+                boot_count,
+                boot_time_secs,
+            }
+        }
+
+        pub fn boot_time(&self) -> Datetime {
+            Datetime::from_unix_timestamp(u64::from(self.boot_time_secs))
+        }
+    }
+}
+use boot_record::BootRecord;
+
+// -------------------------------------------------
+
+/// Number of attempts made to deliver a wake notification before giving up.
+const WAKE_NOTIFICATION_ATTEMPTS: u32 = 4;
+
+/// Delay before the first wake notification retry. Doubled after each failed attempt.
+const WAKE_NOTIFICATION_INITIAL_BACKOFF: embassy_time::Duration = embassy_time::Duration::from_millis(10);
+
+/// Error returned when a wake notification could not be delivered to the host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WakeNotificationError;
+
+/// Delivers wake notifications to the host when a timer expires.
+pub trait WakeNotifier {
+    /// Notify the host that the given timer expired and the system should wake.
+    fn notify_wake(&mut self, timer_id: AcpiTimerId) -> impl Future<Output = Result<(), WakeNotificationError>>;
+}
+
+/// Wake notifier used when no host transport is attached. Only logs the expiry.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopWakeNotifier;
+
+impl WakeNotifier for NoopWakeNotifier {
+    async fn notify_wake(&mut self, timer_id: AcpiTimerId) -> Result<(), WakeNotificationError> {
+        warn!(
+            "[Time/Alarm] Timer {:?} expired and would trigger a wake now, but no wake notifier is attached so will currently do nothing",
+            timer_id
+        );
+        Ok(())
+    }
+}
+
+/// Sends a wake notification, retrying with exponential backoff if delivery fails.
+async fn notify_wake_with_retry<N: WakeNotifier>(
+    notifier: &embassy_sync::mutex::Mutex<GlobalRawMutex, N>,
+    timer_id: AcpiTimerId,
+) -> Result<(), WakeNotificationError> {
+    let mut backoff = WAKE_NOTIFICATION_INITIAL_BACKOFF;
+    for attempt in 1..=WAKE_NOTIFICATION_ATTEMPTS {
+        match notifier.lock().await.notify_wake(timer_id).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt == WAKE_NOTIFICATION_ATTEMPTS => return Err(e),
+            Err(_) => warn!(
+                "[Time/Alarm] Wake notification for timer {:?} failed on attempt {}, retrying",
+                timer_id, attempt
+            ),
+        }
+
+        embassy_time::Timer::after(backoff).await;
+        backoff = backoff * 2;
+    }
+
+    Err(WakeNotificationError)
+}
+
+// -------------------------------------------------
+
+/// Largest drift compensation magnitude accepted by [`Service::set_drift_compensation_ppm`], in parts per million.
+pub const MAX_DRIFT_COMPENSATION_PPM: i32 = 100_000;
+
+const PPM_SCALE: i64 = 1_000_000;
+
 struct ClockState<'hw> {
     datetime_clock: &'hw mut dyn DatetimeClock,
     tz_data: TimeZoneData<'hw>,
+
+    // Known drift of the backing clock in parts per million, stored as the bit pattern of an i32.
+    // Positive values mean the clock runs fast.
+    drift_ppm_storage: &'hw mut dyn NvramStorage<'hw, u32>,
+}
+
+impl ClockState<'_> {
+    fn drift_ppm(&self) -> i32 {
+        (self.drift_ppm_storage.read() as i32).clamp(-MAX_DRIFT_COMPENSATION_PPM, MAX_DRIFT_COMPENSATION_PPM)
+    }
+
+    /// Converts a true duration into the number of seconds the drifting clock will count over that duration.
+    fn clock_secs_for_duration(&self, secs: u32) -> u32 {
+        let scaled = (i64::from(secs) * (PPM_SCALE + i64::from(self.drift_ppm())) + PPM_SCALE / 2) / PPM_SCALE;
+        scaled.clamp(0, i64::from(u32::MAX)) as u32
+    }
+
+    /// Converts a number of seconds counted by the drifting clock into the true duration.
+    fn duration_for_clock_secs(&self, secs: u32) -> u32 {
+        let divisor = PPM_SCALE + i64::from(self.drift_ppm());
+        let scaled = (i64::from(secs) * PPM_SCALE + divisor / 2) / divisor;
+        scaled.clamp(0, i64::from(u32::MAX)) as u32
+    }
 }
 
 // -------------------------------------------------
@@ -89,6 +263,10 @@ impl<'hw> Timers<'hw> {
         }
     }
 
+    fn iter(&self) -> impl Iterator<Item = &Timer<'hw>> {
+        [&self.ac_timer, &self.dc_timer].into_iter()
+    }
+
     fn new(
         ac_expiration_storage: &'hw mut dyn NvramStorage<'hw, u32>,
         ac_policy_storage: &'hw mut dyn NvramStorage<'hw, u32>,
@@ -115,28 +293,31 @@ struct ServiceInner<'hw> {
     timers: Timers<'hw>,
 
     capabilities: TimeAlarmDeviceCapabilities,
+
+    config: Config,
+
+    boot_record: BootRecord,
 }
 
 impl<'hw> ServiceInner<'hw> {
     fn new(
+        config: Config,
         backing_clock: &'hw mut dyn DatetimeClock,
-        tz_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        ac_expiration_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        ac_policy_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        dc_expiration_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        dc_policy_storage: &'hw mut dyn NvramStorage<'hw, u32>,
+        storage: Storage<'hw>,
+        boot_record: BootRecord,
     ) -> Self {
         Self {
             clock_state: Mutex::new(RefCell::new(ClockState {
                 datetime_clock: backing_clock,
-                tz_data: TimeZoneData::new(tz_storage),
+                tz_data: TimeZoneData::new(storage.time_zone),
+                drift_ppm_storage: storage.drift,
             })),
             power_source_signal: Signal::new(),
             timers: Timers::new(
-                ac_expiration_storage,
-                ac_policy_storage,
-                dc_expiration_storage,
-                dc_policy_storage,
+                storage.ac_expiration,
+                storage.ac_policy,
+                storage.dc_expiration,
+                storage.dc_policy,
             ),
             capabilities: {
                 // TODO [CONFIG] We could consider making some of these user-configurable, e.g. if we want to support devices that don't have a battery
@@ -152,6 +333,8 @@ impl<'hw> ServiceInner<'hw> {
                 caps.set_dc_s5_wake_supported(true);
                 caps
             },
+            config,
+            boot_record,
         }
     }
 
@@ -176,12 +359,65 @@ impl<'hw> ServiceInner<'hw> {
 
     /// Change the current time.  Analogous to ACPI TAD's _SRT method.
     fn set_real_time(&self, timestamp: AcpiTimestamp) -> Result<(), DatetimeClockError> {
-        self.clock_state.lock(|clock_state| {
+        let preserve_time_remaining =
+            self.config.set_real_time_timer_policy == SetRealTimeTimerPolicy::PreserveTimeRemaining;
+        let expiration_shift_secs = self.clock_state.lock(|clock_state| {
             let mut clock_state = clock_state.borrow_mut();
+            // The previous time is only needed to shift timers, and can't be read while the clock is invalid (e.g. on
+            // first boot). Timers are left unshifted rather than failing to set the clock.
+            let previous_time = if preserve_time_remaining {
+                clock_state
+                    .datetime_clock
+                    .now()
+                    .inspect_err(|e| warn!("[Time/Alarm] Previous time unreadable, timers not shifted: {:?}", e))
+                    .ok()
+            } else {
+                None
+            };
             clock_state.datetime_clock.set(timestamp.datetime)?;
             clock_state.tz_data.set_data(timestamp.time_zone, timestamp.dst_status);
-            Ok(())
-        })
+            Ok::<_, DatetimeClockError>(previous_time.map(|previous_time| {
+                timestamp.datetime.unix_timestamp() as i64 - previous_time.unix_timestamp() as i64
+            }))
+        })?;
+
+        for timer in self.timers.iter() {
+            timer.handle_clock_change(&self.clock_state, expiration_shift_secs)?;
+        }
+
+        Ok(())
+    }
+
+    /// Move the clock forward by `seconds` and re-evaluate armed timers against the new time.
+    #[cfg(feature = "mock")]
+    fn advance_clock(&self, seconds: u32) -> Result<(), DatetimeClockError> {
+        self.clock_state.lock(|clock_state| {
+            let mut clock_state = clock_state.borrow_mut();
+            let now = clock_state.datetime_clock.now()?;
+            clock_state
+                .datetime_clock
+                .set(Datetime::from_unix_timestamp(now.unix_timestamp() + u64::from(seconds)))
+        })?;
+
+        // Expiration times are absolute, so timers that were passed by the advance fire immediately
+        for timer in self.timers.iter() {
+            timer.handle_clock_change(&self.clock_state, None)?;
+        }
+
+        Ok(())
+    }
+
+    /// Query the current time zone and daylight savings time status without reading the clock.
+    fn get_time_zone(&self) -> (AcpiTimeZone, AcpiDaylightSavingsTimeStatus) {
+        self.clock_state
+            .lock(|clock_state| clock_state.borrow().tz_data.get_data())
+    }
+
+    /// Change the time zone and daylight savings time status without changing the clock.
+    fn set_time_zone(&self, time_zone: AcpiTimeZone, dst_status: AcpiDaylightSavingsTimeStatus) {
+        self.clock_state.lock(|clock_state| {
+            clock_state.borrow_mut().tz_data.set_data(time_zone, dst_status);
+        });
     }
 
     /// Query the current wake status.  Analogous to ACPI TAD's _GWS method.
@@ -213,15 +449,69 @@ impl<'hw> ServiceInner<'hw> {
 
     /// Change the expiry time for the given timer.  Analogous to ACPI TAD's _STV method.
     fn set_timer_value(&self, timer_id: AcpiTimerId, timer_value: AlarmTimerSeconds) -> Result<(), DatetimeClockError> {
+        // Programming the timer through ACPI always reverts it to one-shot behavior
+        self.timers.get_timer(timer_id).set_period(None);
+        self.program_timer(timer_id, timer_value)
+    }
+
+    /// Program the given timer to repeatedly expire every `period`, or disable it if `period` is [`AlarmTimerSeconds::DISABLED`].
+    fn set_periodic_timer_value(
+        &self,
+        timer_id: AcpiTimerId,
+        period: AlarmTimerSeconds,
+    ) -> Result<(), DatetimeClockError> {
+        let period_secs = match period {
+            AlarmTimerSeconds::DISABLED => None,
+            AlarmTimerSeconds(secs) => Some(
+                self.clock_state
+                    .lock(|clock_state| clock_state.borrow().clock_secs_for_duration(secs)),
+            ),
+        };
+        self.timers.get_timer(timer_id).set_period(period_secs);
+        self.program_timer(timer_id, period)
+    }
+
+    /// Disarm every timer and clear its wake status.
+    fn disarm_all(&self) {
+        for timer in self.timers.iter() {
+            timer.disarm();
+        }
+    }
+
+    /// Query the drift compensation applied to timers, in parts per million.
+    fn get_drift_compensation_ppm(&self) -> i32 {
+        self.clock_state.lock(|clock_state| clock_state.borrow().drift_ppm())
+    }
+
+    /// Change the drift compensation applied to timers programmed from now on, in parts per million.
+    fn set_drift_compensation_ppm(&self, ppm: i32) {
+        let clamped = ppm.clamp(-MAX_DRIFT_COMPENSATION_PPM, MAX_DRIFT_COMPENSATION_PPM);
+        if clamped != ppm {
+            warn!(
+                "[Time/Alarm] Drift compensation of {} ppm out of range, clamping to {} ppm",
+                ppm, clamped
+            );
+        }
+
+        self.clock_state.lock(|clock_state| {
+            clock_state.borrow_mut().drift_ppm_storage.write(clamped as u32);
+        });
+    }
+
+    fn program_timer(&self, timer_id: AcpiTimerId, timer_value: AlarmTimerSeconds) -> Result<(), DatetimeClockError> {
         let new_expiration_time = match timer_value {
             AlarmTimerSeconds::DISABLED => None,
             AlarmTimerSeconds(secs) => {
-                let current_time = self
-                    .clock_state
-                    .lock(|clock_state| clock_state.borrow().datetime_clock.now())?;
+                let (current_time, clock_secs) = self.clock_state.lock(|clock_state| {
+                    let clock_state = clock_state.borrow();
+                    Ok::<_, DatetimeClockError>((
+                        clock_state.datetime_clock.now()?,
+                        clock_state.clock_secs_for_duration(secs),
+                    ))
+                })?;
 
                 Some(Datetime::from_unix_timestamp(
-                    current_time.unix_timestamp() + u64::from(secs),
+                    current_time.unix_timestamp() + u64::from(clock_secs),
                 ))
             }
         };
@@ -236,17 +526,18 @@ impl<'hw> ServiceInner<'hw> {
     fn get_timer_value(&self, timer_id: AcpiTimerId) -> Result<AlarmTimerSeconds, DatetimeClockError> {
         let expiration_time = self.timers.get_timer(timer_id).get_expiration_time();
         match expiration_time {
-            Some(expiration_time) => {
-                let current_time = self
-                    .clock_state
-                    .lock(|clock_state| clock_state.borrow().datetime_clock.now())?;
+            Some(expiration_time) => self.clock_state.lock(|clock_state| {
+                let clock_state = clock_state.borrow();
+                let current_time = clock_state.datetime_clock.now()?;
 
                 Ok(AlarmTimerSeconds(
-                    expiration_time
-                        .unix_timestamp()
-                        .saturating_sub(current_time.unix_timestamp()) as u32,
+                    clock_state.duration_for_clock_secs(
+                        expiration_time
+                            .unix_timestamp()
+                            .saturating_sub(current_time.unix_timestamp()) as u32,
+                    ),
                 ))
-            }
+            }),
             None => Ok(AlarmTimerSeconds::DISABLED),
         }
     }
@@ -265,7 +556,11 @@ impl<'hw> ServiceInner<'hw> {
         }
     }
 
-    async fn handle_timer(&'hw self, timer_id: AcpiTimerId) -> ! {
+    async fn handle_timer<N: WakeNotifier>(
+        &'hw self,
+        timer_id: AcpiTimerId,
+        notifier: &embassy_sync::mutex::Mutex<GlobalRawMutex, N>,
+    ) -> ! {
         let timer = self.timers.get_timer(timer_id);
         loop {
             timer.wait_until_wake(&self.clock_state).await;
@@ -279,11 +574,12 @@ impl<'hw> ServiceInner<'hw> {
                     );
                 });
 
-            warn!(
-                "[Time/Alarm] Timer {:?} expired and would trigger a wake now, but the power service is not yet implemented so will currently do nothing",
-                timer_id
-            );
-            // TODO [COMMS] We can't currently trigger a wake because the power service isn't implemented yet - when it is, we need to notify it here
+            if notify_wake_with_retry(notifier, timer_id).await.is_err() {
+                error!(
+                    "[Time/Alarm] Failed to deliver wake notification for timer {:?} after {} attempts",
+                    timer_id, WAKE_NOTIFICATION_ATTEMPTS
+                );
+            }
         }
     }
 }
@@ -295,18 +591,29 @@ pub struct Resources<'hw> {
 }
 
 /// A task runner for the time/alarm service. Users of the service must run this object in an embassy task or similar async execution context.
-pub struct Runner<'hw> {
+pub struct Runner<'hw, N: WakeNotifier = NoopWakeNotifier> {
     service: &'hw ServiceInner<'hw>,
+    notifier: embassy_sync::mutex::Mutex<GlobalRawMutex, N>,
 }
 
-impl<'hw> odp_service_common::runnable_service::ServiceRunner<'hw> for Runner<'hw> {
+impl<'hw> Runner<'hw> {
+    /// Attach a notifier used to deliver wake notifications to the host when a timer expires.
+    pub fn with_wake_notifier<N: WakeNotifier>(self, notifier: N) -> Runner<'hw, N> {
+        Runner {
+            service: self.service,
+            notifier: embassy_sync::mutex::Mutex::new(notifier),
+        }
+    }
+}
+
+impl<'hw, N: WakeNotifier> odp_service_common::runnable_service::ServiceRunner<'hw> for Runner<'hw, N> {
     /// Run the service.
     async fn run(self) -> embedded_services::Never {
         loop {
             embassy_futures::select::select3(
                 self.service.handle_power_source_updates(),
-                self.service.handle_timer(AcpiTimerId::AcPower),
-                self.service.handle_timer(AcpiTimerId::DcPower),
+                self.service.handle_timer(AcpiTimerId::AcPower, &self.notifier),
+                self.service.handle_timer(AcpiTimerId::DcPower, &self.notifier),
             )
             .await;
         }
@@ -334,6 +641,16 @@ impl<'hw> TimeAlarmService for Service<'hw> {
         self.inner.set_real_time(timestamp)
     }
 
+    /// Query the current time zone and daylight savings time status without reading the clock.
+    fn get_time_zone(&self) -> (AcpiTimeZone, AcpiDaylightSavingsTimeStatus) {
+        self.inner.get_time_zone()
+    }
+
+    /// Change the time zone and daylight savings time status without changing the clock.
+    fn set_time_zone(&self, time_zone: AcpiTimeZone, dst_status: AcpiDaylightSavingsTimeStatus) {
+        self.inner.set_time_zone(time_zone, dst_status);
+    }
+
     /// Query the current wake status.  Analogous to ACPI TAD's _GWS method.
     fn get_wake_status(&self, timer_id: AcpiTimerId) -> TimerStatus {
         self.inner.get_wake_status(timer_id)
@@ -369,36 +686,93 @@ impl<'hw> TimeAlarmService for Service<'hw> {
     }
 }
 
+impl<'hw> Service<'hw> {
+    /// Program the given timer to expire every `period` seconds, re-arming itself each time it triggers a wake.
+    ///
+    /// This is an OEM extension to the ACPI one-shot timers - setting the timer through [`TimeAlarmService::set_timer_value`]
+    /// reverts it to one-shot behavior. Passing [`AlarmTimerSeconds::DISABLED`] disables the timer.
+    pub fn set_periodic_timer_value(
+        &self,
+        timer_id: AcpiTimerId,
+        period: AlarmTimerSeconds,
+    ) -> Result<(), DatetimeClockError> {
+        self.inner.set_periodic_timer_value(timer_id, period)
+    }
+
+    /// Disarm both the AC and DC timers and clear their wake status in one operation.
+    ///
+    /// The cleared expiration times are persisted to NVRAM, and periodic timers revert to one-shot behavior.
+    pub fn disarm_all(&self) {
+        self.inner.disarm_all();
+    }
+
+    /// Query the drift compensation applied to timers, in parts per million.
+    pub fn drift_compensation_ppm(&self) -> i32 {
+        self.inner.get_drift_compensation_ppm()
+    }
+
+    /// Set the known drift of the backing clock in parts per million, where positive values mean the clock runs fast.
+    ///
+    /// The setting is persisted and applied when converting timer values to and from clock time, so a timer programmed for
+    /// `T` seconds fires after `T` true seconds. Timers that are already armed keep their current expiration time.
+    /// Values beyond [`MAX_DRIFT_COMPENSATION_PPM`] are clamped.
+    pub fn set_drift_compensation_ppm(&self, ppm: i32) {
+        self.inner.set_drift_compensation_ppm(ppm);
+    }
+
+    /// Fast-forward the clock by `seconds` so tests don't have to wait in real time for timers to expire.
+    ///
+    /// Armed timers are re-evaluated against the new time, so any timer whose expiration time was passed fires
+    /// immediately. Unlike [`TimeAlarmService::set_real_time`], expiration times are never shifted.
+    #[cfg(feature = "mock")]
+    pub fn advance_clock(&self, seconds: u32) -> Result<(), DatetimeClockError> {
+        self.inner.advance_clock(seconds)
+    }
+
+    /// Query the number of times the service has been initialized, including the current boot.
+    ///
+    /// The counter is persisted in NVRAM and restarts from 1 if the NVRAM was uninitialized.
+    pub fn boot_count(&self) -> u32 {
+        self.inner.boot_record.boot_count
+    }
+
+    /// Query the wall-clock time at which the current boot was recorded.
+    pub fn last_boot_time(&self) -> Datetime {
+        self.inner.boot_record.boot_time()
+    }
+}
+
 impl<'hw> odp_service_common::runnable_service::Service<'hw> for Service<'hw> {
     type Runner = Runner<'hw>;
     type Resources = Resources<'hw>;
 }
 
 impl<'hw> Service<'hw> {
-    /// Initializes an instance of the time-alarm service.
+    /// Initializes an instance of the time-alarm service, persisting its state in `storage`.
     pub async fn new(
         service_storage: &'hw mut Resources<'hw>,
+        config: Config,
         backing_clock: &'hw mut dyn DatetimeClock,
-        tz_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        ac_expiration_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        ac_policy_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        dc_expiration_storage: &'hw mut dyn NvramStorage<'hw, u32>,
-        dc_policy_storage: &'hw mut dyn NvramStorage<'hw, u32>,
+        mut storage: Storage<'hw>,
     ) -> Result<(Self, Runner<'hw>), DatetimeClockError> {
-        let service = service_storage.inner.insert(ServiceInner::new(
-            backing_clock,
-            tz_storage,
-            ac_expiration_storage,
-            ac_policy_storage,
-            dc_expiration_storage,
-            dc_policy_storage,
-        ));
+        let boot_record = BootRecord::record(backing_clock.now()?, storage.boot_count, storage.boot_time);
+        info!("[Time/Alarm] Boot {} recorded", boot_record.boot_count);
+
+        let service = service_storage
+            .inner
+            .insert(ServiceInner::new(config, backing_clock, storage, boot_record));
 
         // TODO [POWER_SOURCE] we need to subscribe to messages that tell us if we're on AC or DC power so we can decide which alarms to trigger, but those notifications are not yet implemented - revisit when they are.
         // TODO [POWER_SOURCE] if it's possible to learn which power source is active at init time, we should set that one active rather than defaulting to the AC timer.
         service.timers.ac_timer.start(&service.clock_state, true)?;
         service.timers.dc_timer.start(&service.clock_state, false)?;
 
-        Ok((Self { inner: service }, Runner { service }))
+        Ok((
+            Self { inner: service },
+            Runner {
+                service,
+                notifier: embassy_sync::mutex::Mutex::new(NoopWakeNotifier),
+            },
+        ))
     }
 }
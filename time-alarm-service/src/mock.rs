@@ -1,7 +1,12 @@
 #![allow(dead_code)] // We have some functionality in these mocks that isn't used yet but will be in future tests.
 
+use embassy_sync::signal::Signal;
 use embedded_mcu_hal::nvram::NvramStorage;
 use embedded_mcu_hal::time::{Datetime, DatetimeClock, DatetimeClockError};
+use embedded_services::GlobalRawMutex;
+use time_alarm_service_interface::AcpiTimerId;
+
+use crate::{WakeNotificationError, WakeNotifier};
 
 // Used for `cargo test` runs in an std environment
 #[cfg(test)]
@@ -118,3 +123,35 @@ impl<'a> NvramStorage<'a, u32> for MockNvramStorage<'a> {
         self.value = value;
     }
 }
+
+/// Wake notifier that fails a set number of times before delivering the notification.
+pub struct MockWakeNotifier<'a> {
+    failures_remaining: u32,
+    attempts: u32,
+    /// Signaled with the timer ID and the number of attempts once a notification is delivered.
+    delivered: &'a Signal<GlobalRawMutex, (AcpiTimerId, u32)>,
+}
+
+impl<'a> MockWakeNotifier<'a> {
+    pub fn new(failures: u32, delivered: &'a Signal<GlobalRawMutex, (AcpiTimerId, u32)>) -> Self {
+        Self {
+            failures_remaining: failures,
+            attempts: 0,
+            delivered,
+        }
+    }
+}
+
+impl WakeNotifier for MockWakeNotifier<'_> {
+    async fn notify_wake(&mut self, timer_id: AcpiTimerId) -> Result<(), WakeNotificationError> {
+        self.attempts += 1;
+        if self.failures_remaining > 0 {
+            self.failures_remaining -= 1;
+            return Err(WakeNotificationError);
+        }
+
+        self.delivered.signal((timer_id, self.attempts));
+        self.attempts = 0;
+        Ok(())
+    }
+}
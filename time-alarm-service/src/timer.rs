@@ -90,6 +90,9 @@ struct TimerState<'hw> {
     // Whether or not this timer is currently active (i.e. the system is on the power source this timer manages)
     // Even if it's not active, it still counts down if it's programmed - it just won't trigger a wake event if it expires while inactive.
     is_active: bool,
+
+    // If set, the timer re-arms itself this many seconds after triggering a wake instead of clearing. Not part of ACPI, so not persisted.
+    period_secs: Option<u32>,
 }
 
 pub(crate) struct Timer<'hw> {
@@ -109,6 +112,7 @@ impl<'hw> Timer<'hw> {
                 wake_state: WakeState::Clear,
                 timer_status: Default::default(),
                 is_active: false,
+                period_secs: None,
             })),
             timer_signal: Signal::new(),
         }
@@ -202,6 +206,67 @@ impl<'hw> Timer<'hw> {
         })
     }
 
+    /// Disarms the timer and clears its wake status, reverting it to one-shot behavior.
+    pub fn disarm(&self) {
+        self.timer_state.lock(|timer_state| {
+            let mut timer_state = timer_state.borrow_mut();
+            timer_state.timer_status = Default::default();
+            timer_state.period_secs = None;
+            self.clear_expiration_time(&mut timer_state);
+        });
+    }
+
+    /// Sets the period at which the timer re-arms itself after triggering a wake, or `None` for ACPI one-shot behavior.
+    ///
+    /// This does not change the currently programmed expiration time.
+    pub fn set_period(&self, period_secs: Option<u32>) {
+        self.timer_state.lock(|timer_state| {
+            timer_state.borrow_mut().period_secs = period_secs;
+        });
+    }
+
+    /// Updates an armed timer after the wall clock has been changed.
+    ///
+    /// If `expiration_shift_secs` is given, the expiration time is shifted by it, typically by as much as the clock moved so the
+    /// timer fires after the same amount of time it otherwise would have. The wait is always rescheduled against the new time.
+    pub fn handle_clock_change(
+        &self,
+        clock_state: &Mutex<GlobalRawMutex, RefCell<ClockState<'hw>>>,
+        expiration_shift_secs: Option<i64>,
+    ) -> Result<(), DatetimeClockError> {
+        self.timer_state.lock(|timer_state| {
+            let mut timer_state = timer_state.borrow_mut();
+
+            // Expired timers are already running down their power source policy, which is measured in elapsed time rather than wall-clock time.
+            if timer_state.wake_state != WakeState::Armed {
+                return Ok(());
+            }
+
+            let Some(mut expiration_time) = timer_state.persistent_storage.get_expiration_time() else {
+                return Ok(());
+            };
+
+            if let Some(expiration_shift_secs) = expiration_shift_secs {
+                expiration_time = Datetime::from_unix_timestamp(
+                    expiration_time
+                        .unix_timestamp()
+                        .saturating_add_signed(expiration_shift_secs),
+                );
+                timer_state
+                    .persistent_storage
+                    .set_expiration_time(Some(expiration_time));
+            }
+
+            self.timer_signal.signal(Some(
+                expiration_time
+                    .unix_timestamp()
+                    .saturating_sub(Self::now(clock_state)?.unix_timestamp()) as u32,
+            ));
+
+            Ok(())
+        })
+    }
+
     pub fn get_expiration_time(&self) -> Option<Datetime> {
         self.timer_state
             .lock(|timer_state| timer_state.borrow().persistent_storage.get_expiration_time())
@@ -355,10 +420,23 @@ impl<'hw> Timer<'hw> {
                     timer_state.timer_status.set_timer_expired(true);
                     if timer_state.is_active {
                         timer_state.timer_status.set_timer_triggered_wake(true);
-                        timer_state
-                            .persistent_storage
-                            .set_timer_wake_policy(AlarmExpiredWakePolicy::NEVER);
-                        self.clear_expiration_time(&mut timer_state);
+                        if let Some(period_secs) = timer_state.period_secs {
+                            // Periodic timers re-arm for the next period rather than clearing. If the clock is broken, fall back to
+                            // scheduling relative to the expiration time we just hit.
+                            let now = Self::now(clock_state).unwrap_or(expiration_time);
+                            timer_state
+                                .persistent_storage
+                                .set_expiration_time(Some(Datetime::from_unix_timestamp(
+                                    now.unix_timestamp() + u64::from(period_secs),
+                                )));
+                            timer_state.wake_state = WakeState::Armed;
+                            self.timer_signal.signal(Some(period_secs));
+                        } else {
+                            timer_state
+                                .persistent_storage
+                                .set_timer_wake_policy(AlarmExpiredWakePolicy::NEVER);
+                            self.clear_expiration_time(&mut timer_state);
+                        }
                         return true;
                     } else {
                         if timer_state.persistent_storage.get_timer_wake_policy() == AlarmExpiredWakePolicy::NEVER {
@@ -4,11 +4,20 @@
 
 #[cfg(test)]
 mod test {
-    use embassy_time::Timer;
-    use embedded_mcu_hal::time::{Datetime, DatetimeClock};
+    use core::cell::Cell;
+
+    use embassy_sync::signal::Signal;
+    use embassy_time::{Timer, with_timeout};
+    use embedded_mcu_hal::nvram::NvramStorage;
+    use embedded_mcu_hal::time::{Datetime, DatetimeClock, DatetimeClockError};
+    use embedded_services::GlobalRawMutex;
     use odp_service_common::runnable_service::ServiceRunner;
 
-    use time_alarm_service_interface::{AcpiDaylightSavingsTimeStatus, AcpiTimeZone, AcpiTimestamp, TimeAlarmService};
+    use time_alarm_service::{Config, SetRealTimeTimerPolicy};
+    use time_alarm_service_interface::{
+        AcpiDaylightSavingsTimeStatus, AcpiTimeZone, AcpiTimeZoneOffset, AcpiTimerId, AcpiTimestamp, AlarmTimerSeconds,
+        TimeAlarmService,
+    };
 
     use time_alarm_service::mock::*;
 
@@ -19,18 +28,27 @@ mod test {
         let mut ac_pol_storage = MockNvramStorage::new(0);
         let mut dc_exp_storage = MockNvramStorage::new(0);
         let mut dc_pol_storage = MockNvramStorage::new(0);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
 
         let mut clock = MockDatetimeClock::new_running();
         let mut storage = Default::default();
 
         let (service, runner) = time_alarm_service::Service::new(
             &mut storage,
+            Default::default(),
             &mut clock,
-            &mut tz_storage,
-            &mut ac_exp_storage,
-            &mut ac_pol_storage,
-            &mut dc_exp_storage,
-            &mut dc_pol_storage,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
         )
         .await
         .unwrap();
@@ -64,6 +82,9 @@ mod test {
         let mut ac_pol_storage = MockNvramStorage::new(0);
         let mut dc_exp_storage = MockNvramStorage::new(0);
         let mut dc_pol_storage = MockNvramStorage::new(0);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
 
         let mut clock = MockDatetimeClock::new_paused();
         const TEST_UNIX_TIME: u64 = 1_234_567_890;
@@ -73,12 +94,18 @@ mod test {
 
         let (service, runner) = time_alarm_service::Service::new(
             &mut storage,
+            Default::default(),
             &mut clock,
-            &mut tz_storage,
-            &mut ac_exp_storage,
-            &mut ac_pol_storage,
-            &mut dc_exp_storage,
-            &mut dc_pol_storage,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
         )
         .await
         .unwrap();
@@ -103,4 +130,605 @@ mod test {
             } => {}
         }
     }
+
+    #[tokio::test]
+    async fn test_set_time_zone() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(0);
+        let mut ac_pol_storage = MockNvramStorage::new(0);
+        let mut dc_exp_storage = MockNvramStorage::new(0);
+        let mut dc_pol_storage = MockNvramStorage::new(0);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        let mut clock = MockDatetimeClock::new_paused();
+        const TEST_UNIX_TIME: u64 = 1_234_567_890;
+        clock.set(Datetime::from_unix_timestamp(TEST_UNIX_TIME)).unwrap();
+
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Default::default(),
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                let time_zone = AcpiTimeZone::MinutesFromUtc(AcpiTimeZoneOffset::new(60 * -8).unwrap());
+                service.set_time_zone(time_zone, AcpiDaylightSavingsTimeStatus::Adjusted);
+
+                assert_eq!(service.get_time_zone(), (time_zone, AcpiDaylightSavingsTimeStatus::Adjusted));
+
+                // The clock itself should be untouched
+                let timestamp = service.get_real_time().unwrap();
+                assert_eq!(timestamp.datetime.unix_timestamp(), TEST_UNIX_TIME);
+                assert_eq!(timestamp.time_zone, time_zone);
+                assert_eq!(timestamp.dst_status, AcpiDaylightSavingsTimeStatus::Adjusted);
+            } => {}
+        }
+    }
+
+    /// Programs a timer, moves the clock by `clock_delta_secs`, and checks the time remaining on the timer afterwards.
+    async fn test_set_real_time_timer_policy(
+        policy: SetRealTimeTimerPolicy,
+        clock_delta_secs: i64,
+        expected_remaining_secs: u32,
+    ) {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        let mut clock = MockDatetimeClock::new_paused();
+        const TEST_UNIX_TIME: u64 = 1_234_567_890;
+        clock.set(Datetime::from_unix_timestamp(TEST_UNIX_TIME)).unwrap();
+
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Config {
+                set_real_time_timer_policy: policy,
+            },
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                const TIMER_SECS: u32 = 100;
+                service.set_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(TIMER_SECS)).unwrap();
+                assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds(TIMER_SECS));
+
+                service.set_real_time(AcpiTimestamp {
+                    datetime: Datetime::from_unix_timestamp(TEST_UNIX_TIME.saturating_add_signed(clock_delta_secs)),
+                    time_zone: AcpiTimeZone::Unknown,
+                    dst_status: AcpiDaylightSavingsTimeStatus::NotObserved,
+                }).unwrap();
+
+                assert_eq!(
+                    service.get_timer_value(AcpiTimerId::AcPower).unwrap(),
+                    AlarmTimerSeconds(expected_remaining_secs)
+                );
+
+                // Disabled timers must not be affected by the clock change
+                assert_eq!(service.get_timer_value(AcpiTimerId::DcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+            } => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_real_time_preserve_expiration_time() {
+        // Expiration time is unchanged, so moving the clock changes the time remaining
+        test_set_real_time_timer_policy(SetRealTimeTimerPolicy::PreserveExpirationTime, 50, 50).await;
+        test_set_real_time_timer_policy(SetRealTimeTimerPolicy::PreserveExpirationTime, -50, 150).await;
+    }
+
+    #[tokio::test]
+    async fn test_set_real_time_preserve_time_remaining() {
+        // Expiration time moves with the clock, so the time remaining is unchanged
+        test_set_real_time_timer_policy(SetRealTimeTimerPolicy::PreserveTimeRemaining, 50, 100).await;
+        test_set_real_time_timer_policy(SetRealTimeTimerPolicy::PreserveTimeRemaining, -50, 100).await;
+    }
+
+    /// Clock that can't be read once invalidated until it is set again, like an RTC that lost power.
+    struct InvalidatableClock<'a> {
+        clock: MockDatetimeClock,
+        valid: &'a Cell<bool>,
+    }
+
+    impl DatetimeClock for InvalidatableClock<'_> {
+        fn now(&self) -> Result<Datetime, DatetimeClockError> {
+            if self.valid.get() {
+                self.clock.now()
+            } else {
+                Err(DatetimeClockError::Unknown)
+            }
+        }
+
+        fn set(&mut self, datetime: Datetime) -> Result<(), DatetimeClockError> {
+            self.clock.set(datetime)?;
+            self.valid.set(true);
+            Ok(())
+        }
+
+        fn resolution_hz(&self) -> u32 {
+            self.clock.resolution_hz()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_real_time_invalid_clock() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        const TEST_UNIX_TIME: u64 = 1_234_567_890;
+        let valid = Cell::new(true);
+        let mut clock = InvalidatableClock {
+            clock: MockDatetimeClock::new_paused(),
+            valid: &valid,
+        };
+        clock.set(Datetime::from_unix_timestamp(TEST_UNIX_TIME)).unwrap();
+
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Config {
+                set_real_time_timer_policy: SetRealTimeTimerPolicy::PreserveTimeRemaining,
+            },
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                service.set_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(100)).unwrap();
+
+                // The clock can be set even though the previous time can't be read to shift the timer
+                valid.set(false);
+                service.set_real_time(AcpiTimestamp {
+                    datetime: Datetime::from_unix_timestamp(TEST_UNIX_TIME + 50),
+                    time_zone: AcpiTimeZone::Unknown,
+                    dst_status: AcpiDaylightSavingsTimeStatus::NotObserved,
+                }).unwrap();
+                assert_eq!(service.get_real_time().unwrap().datetime.unix_timestamp(), TEST_UNIX_TIME + 50);
+
+                // The expiration time is left unchanged
+                assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds(50));
+            } => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wake_notification_retry() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        let mut clock = MockDatetimeClock::new_running();
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Default::default(),
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Fail the first two sends so the service has to retry before the notification gets through
+        let delivered = Signal::<GlobalRawMutex, (AcpiTimerId, u32)>::new();
+        let runner = runner.with_wake_notifier(MockWakeNotifier::new(2, &delivered));
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                service.set_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(1)).unwrap();
+
+                let (timer_id, attempts) = with_timeout(embassy_time::Duration::from_secs(5), delivered.wait())
+                    .await
+                    .expect("wake notification was not delivered");
+                assert_eq!(timer_id, AcpiTimerId::AcPower);
+                assert_eq!(attempts, 3);
+            } => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_periodic_timer() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        let mut clock = MockDatetimeClock::new_running();
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Default::default(),
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        let delivered = Signal::<GlobalRawMutex, (AcpiTimerId, u32)>::new();
+        let runner = runner.with_wake_notifier(MockWakeNotifier::new(0, &delivered));
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                const PERIOD_SECS: u32 = 1;
+                service.set_periodic_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(PERIOD_SECS)).unwrap();
+
+                // The timer should keep firing and re-arming itself rather than clearing after the first wake
+                for _ in 0..3 {
+                    let (timer_id, _) = with_timeout(embassy_time::Duration::from_secs(5), delivered.wait())
+                        .await
+                        .expect("periodic timer did not fire");
+                    assert_eq!(timer_id, AcpiTimerId::AcPower);
+                    assert_ne!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+                }
+
+                // Programming the timer through ACPI reverts it to one-shot behavior
+                service.set_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(PERIOD_SECS)).unwrap();
+                with_timeout(embassy_time::Duration::from_secs(5), delivered.wait())
+                    .await
+                    .expect("one-shot timer did not fire");
+                assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+            } => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drift_compensation() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        let mut clock = MockDatetimeClock::new_paused();
+        const TEST_UNIX_TIME: u64 = 1_234_567_890;
+        clock.set(Datetime::from_unix_timestamp(TEST_UNIX_TIME)).unwrap();
+
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Default::default(),
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        let delivered = Signal::<GlobalRawMutex, (AcpiTimerId, u32)>::new();
+        let runner = runner.with_wake_notifier(MockWakeNotifier::new(0, &delivered));
+
+        let set_clock = |secs: u64| {
+            service
+                .set_real_time(AcpiTimestamp {
+                    datetime: Datetime::from_unix_timestamp(TEST_UNIX_TIME + secs),
+                    time_zone: AcpiTimeZone::Unknown,
+                    dst_status: AcpiDaylightSavingsTimeStatus::NotObserved,
+                })
+                .unwrap();
+        };
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                // Clock runs 10% fast, so it counts 110 seconds over a true 100 seconds
+                const DRIFT_PPM: i32 = 100_000;
+                const TIMER_SECS: u32 = 100;
+                service.set_drift_compensation_ppm(DRIFT_PPM);
+                assert_eq!(service.drift_compensation_ppm(), DRIFT_PPM);
+
+                service.set_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(TIMER_SECS)).unwrap();
+                assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds(TIMER_SECS));
+
+                // An uncompensated timer would have expired by now
+                set_clock(105);
+                assert!(with_timeout(embassy_time::Duration::from_millis(500), delivered.wait()).await.is_err());
+
+                set_clock(110);
+                let (timer_id, _) = with_timeout(embassy_time::Duration::from_secs(5), delivered.wait())
+                    .await
+                    .expect("compensated timer did not fire");
+                assert_eq!(timer_id, AcpiTimerId::AcPower);
+
+                // Out of range values are clamped
+                service.set_drift_compensation_ppm(i32::MIN);
+                assert_eq!(
+                    service.drift_compensation_ppm(),
+                    -time_alarm_service::MAX_DRIFT_COMPENSATION_PPM
+                );
+            } => {}
+        }
+    }
+
+    /// Initializes the service against NVRAM holding `boot_count` and `boot_time` with the clock at `now`, returning
+    /// the boot count and last boot time (in seconds since the epoch) the service recorded.
+    async fn simulate_boot(now: u64, boot_count: u32, boot_time: u32) -> (u32, u64) {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(0);
+        let mut ac_pol_storage = MockNvramStorage::new(0);
+        let mut dc_exp_storage = MockNvramStorage::new(0);
+        let mut dc_pol_storage = MockNvramStorage::new(0);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(boot_count);
+        let mut boot_time_storage = MockNvramStorage::new(boot_time);
+
+        let mut clock = MockDatetimeClock::new_paused();
+        clock.set(Datetime::from_unix_timestamp(now)).unwrap();
+
+        let mut storage = Default::default();
+
+        let (service, _runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Default::default(),
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        (service.boot_count(), service.last_boot_time().unix_timestamp())
+    }
+
+    #[tokio::test]
+    async fn test_boot_count() {
+        const FIRST_BOOT_TIME: u64 = 1_234_567_890;
+        const SECOND_BOOT_TIME: u64 = FIRST_BOOT_TIME + 60;
+
+        // Uninitialized NVRAM is treated as the first boot
+        let (count, time) = simulate_boot(FIRST_BOOT_TIME, 0, 0).await;
+        assert_eq!(count, 1);
+        assert_eq!(time, FIRST_BOOT_TIME);
+
+        // Carry the recorded values across a simulated reset
+        let (count, time) = simulate_boot(SECOND_BOOT_TIME, count, time as u32).await;
+        assert_eq!(count, 2);
+        assert_eq!(time, SECOND_BOOT_TIME);
+
+        let (count, _) = simulate_boot(SECOND_BOOT_TIME + 60, count, time as u32).await;
+        assert_eq!(count, 3);
+
+        // A counter with no recorded boot time is garbage left in uninitialized NVRAM, so the count restarts
+        let (count, time) = simulate_boot(FIRST_BOOT_TIME, 0xdead_beef, 0).await;
+        assert_eq!(count, 1);
+        assert_eq!(time, FIRST_BOOT_TIME);
+    }
+
+    #[tokio::test]
+    async fn test_advance_clock() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        // Paused so only the fast-forward moves the clock
+        let mut clock = MockDatetimeClock::new_paused();
+        let mut storage = Default::default();
+
+        let (service, runner) = time_alarm_service::Service::new(
+            &mut storage,
+            Default::default(),
+            &mut clock,
+            time_alarm_service::Storage {
+                time_zone: &mut tz_storage,
+                ac_expiration: &mut ac_exp_storage,
+                ac_policy: &mut ac_pol_storage,
+                dc_expiration: &mut dc_exp_storage,
+                dc_policy: &mut dc_pol_storage,
+                drift: &mut drift_storage,
+                boot_count: &mut boot_count_storage,
+                boot_time: &mut boot_time_storage,
+            },
+        )
+        .await
+        .unwrap();
+
+        let delivered = Signal::<GlobalRawMutex, (AcpiTimerId, u32)>::new();
+        let runner = runner.with_wake_notifier(MockWakeNotifier::new(0, &delivered));
+
+        tokio::select! {
+            _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+            _ = async {
+                const ALARM_SECS: u32 = 24 * 60 * 60;
+                let begin = service.get_real_time().unwrap().datetime.unix_timestamp();
+                service.set_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(ALARM_SECS)).unwrap();
+
+                // Stopping just short of the alarm doesn't fire it
+                service.advance_clock(ALARM_SECS - 1).unwrap();
+                assert_eq!(service.get_real_time().unwrap().datetime.unix_timestamp(), begin + u64::from(ALARM_SECS) - 1);
+                assert!(with_timeout(embassy_time::Duration::from_millis(500), delivered.wait()).await.is_err());
+                assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds(1));
+
+                // Advancing past the alarm fires it without waiting in real time
+                service.advance_clock(2).unwrap();
+                let (timer_id, _) = with_timeout(embassy_time::Duration::from_millis(500), delivered.wait())
+                    .await
+                    .expect("alarm did not fire after advancing the clock past it");
+                assert_eq!(timer_id, AcpiTimerId::AcPower);
+                assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+            } => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disarm_all() {
+        let mut tz_storage = MockNvramStorage::new(0);
+        let mut ac_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut ac_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_exp_storage = MockNvramStorage::new(u32::MAX);
+        let mut dc_pol_storage = MockNvramStorage::new(u32::MAX);
+        let mut drift_storage = MockNvramStorage::new(0);
+        let mut boot_count_storage = MockNvramStorage::new(0);
+        let mut boot_time_storage = MockNvramStorage::new(0);
+
+        // Scoped so the NVRAM can be inspected once the service is gone
+        {
+            let mut clock = MockDatetimeClock::new_paused();
+            let mut storage = Default::default();
+
+            let (service, runner) = time_alarm_service::Service::new(
+                &mut storage,
+                Default::default(),
+                &mut clock,
+                time_alarm_service::Storage {
+                    time_zone: &mut tz_storage,
+                    ac_expiration: &mut ac_exp_storage,
+                    ac_policy: &mut ac_pol_storage,
+                    dc_expiration: &mut dc_exp_storage,
+                    dc_policy: &mut dc_pol_storage,
+                    drift: &mut drift_storage,
+                    boot_count: &mut boot_count_storage,
+                    boot_time: &mut boot_time_storage,
+                },
+            )
+            .await
+            .unwrap();
+
+            let delivered = Signal::<GlobalRawMutex, (AcpiTimerId, u32)>::new();
+            let runner = runner.with_wake_notifier(MockWakeNotifier::new(0, &delivered));
+
+            tokio::select! {
+                _ = runner.run() => unreachable!("time alarm service task finished unexpectedly"),
+                _ = async {
+                    // A periodic timer stays armed after firing, so it has both an expiration time and a wake status
+                    service.set_periodic_timer_value(AcpiTimerId::AcPower, AlarmTimerSeconds(10)).unwrap();
+                    service.set_timer_value(AcpiTimerId::DcPower, AlarmTimerSeconds(24 * 60 * 60)).unwrap();
+                    service.advance_clock(11).unwrap();
+                    with_timeout(embassy_time::Duration::from_millis(500), delivered.wait())
+                        .await
+                        .expect("periodic timer did not fire");
+                    assert!(service.get_wake_status(AcpiTimerId::AcPower).timer_triggered_wake());
+                    assert_ne!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+                    assert_ne!(service.get_timer_value(AcpiTimerId::DcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+
+                    service.disarm_all();
+
+                    for timer_id in [AcpiTimerId::AcPower, AcpiTimerId::DcPower] {
+                        assert_eq!(service.get_timer_value(timer_id).unwrap(), AlarmTimerSeconds::DISABLED);
+                        assert_eq!(service.get_wake_status(timer_id), Default::default());
+                    }
+
+                    // The periodic timer doesn't re-arm itself after being disarmed
+                    service.advance_clock(11).unwrap();
+                    assert!(with_timeout(embassy_time::Duration::from_millis(500), delivered.wait()).await.is_err());
+                    assert_eq!(service.get_timer_value(AcpiTimerId::AcPower).unwrap(), AlarmTimerSeconds::DISABLED);
+                } => {}
+            }
+        }
+
+        // No expiration time is persisted for either timer
+        assert_eq!(ac_exp_storage.read(), u32::MAX);
+        assert_eq!(dc_exp_storage.read(), u32::MAX);
+    }
 }
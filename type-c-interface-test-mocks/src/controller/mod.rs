@@ -6,12 +6,22 @@ use embedded_services::named::Named;
 use embedded_usb_pd::{PdError, ado::Ado};
 use type_c_interface::control::{
     dp::DpStatus,
+    mode::ModeEntryStatus,
     pd::PortStatus,
+    pdo::Pdos,
+    retimer::RetimerFwUpdateState,
+    usb::UsbDataStatus,
     vdm::{AttnVdm, OtherVdm},
 };
+use type_c_interface::port::event::PortEventBitfield;
 
 pub mod max_sink_voltage;
 pub mod pd;
+pub mod port_enable;
+pub mod power;
+pub mod retimer;
+pub mod role_swap;
+pub mod source_pdp;
 pub mod ucsi;
 
 /// Contains a controller function call and its arguments
@@ -19,6 +29,15 @@ pub enum FnCall {
     Pd(pd::FnCall),
     Ucsi(ucsi::FnCall),
     MaxSinkVoltage(max_sink_voltage::FnCall),
+    RoleSwap(role_swap::FnCall),
+    Power(power::FnCall),
+    Retimer(retimer::FnCall),
+    SourcePdp(source_pdp::FnCall),
+    PortEnable(port_enable::FnCall),
+    /// [`type_c_interface::controller::Controller::reset_controller`]
+    ResetController,
+    /// [`type_c_interface::controller::Controller::get_die_temperature`]
+    GetDieTemperature,
 }
 
 /// Mock PD controller for use in tests
@@ -32,10 +51,16 @@ pub struct Mock {
     pub next_result_clear_dead_battery_flag: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::enable_sink_path`]
     pub next_result_enable_sink_path: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::set_source_capability`]
+    pub next_result_set_source_capability: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::max_sink_voltage::MaxSinkVoltage::set_max_sink_voltage`]
     pub next_result_set_max_sink_voltage: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::get_pd_alert`]
     pub next_result_get_pd_alert: VecDeque<Result<Option<Ado>, PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::get_event_mask`]
+    pub next_result_get_event_mask: VecDeque<Result<PortEventBitfield, PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::set_event_mask`]
+    pub next_result_set_event_mask: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::set_unconstrained_power`]
     pub next_result_set_unconstrained_power: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::get_other_vdm`]
@@ -52,14 +77,24 @@ pub struct Mock {
     pub next_result_set_dp_config: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::set_tbt_config`]
     pub next_result_set_tbt_config: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::enter_data_mode`]
+    pub next_result_enter_data_mode: VecDeque<Result<ModeEntryStatus, PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::exit_data_mode`]
+    pub next_result_exit_data_mode: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::set_billboard_indication`]
+    pub next_result_set_billboard_indication: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::set_usb_control`]
     pub next_result_set_usb_control: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::get_usb_data_status`]
+    pub next_result_get_usb_data_status: VecDeque<Result<UsbDataStatus, PdError>>,
     /// Next results to return for [`type_c_interface::ucsi::Lpm::execute_lpm_command`]
     pub next_result_execute_lpm_command: VecDeque<Result<Option<embedded_usb_pd::ucsi::lpm::ResponseData>, PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::hard_reset`]
     pub next_result_hard_reset: VecDeque<Result<(), PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::get_discovered_svids`]
     pub next_result_get_discovered_svids: VecDeque<Result<type_c_interface::control::svid::DiscoveredSvids, PdError>>,
+    /// Next results to return for [`type_c_interface::controller::pd::Pd::get_pdos`]
+    pub next_result_get_pdos: VecDeque<Result<Pdos, PdError>>,
     /// Next results to return for [`type_c_interface::controller::pd::Pd::get_discover_identity_sop_response`]
     pub next_result_get_discover_identity_sop_response:
         VecDeque<Result<embedded_usb_pd::vdm::structured::command::discover_identity::sop::ResponseVdos, PdError>>,
@@ -67,6 +102,30 @@ pub struct Mock {
     pub next_result_get_discover_identity_sop_prime_response: VecDeque<
         Result<embedded_usb_pd::vdm::structured::command::discover_identity::sop_prime::ResponseVdos, PdError>,
     >,
+    /// Next results to return for [`type_c_interface::controller::role_swap::RoleSwap::request_power_role_swap`]
+    pub next_result_request_power_role_swap: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::power::SystemPowerStateStatus::set_system_power_state_status`]
+    pub next_result_set_system_power_state_status: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::retimer::Retimer::get_rt_fw_update_status`]
+    pub next_result_get_rt_fw_update_status: VecDeque<Result<RetimerFwUpdateState, PdError>>,
+    /// Next results to return for [`type_c_interface::controller::retimer::Retimer::set_rt_fw_update_state`]
+    pub next_result_set_rt_fw_update_state: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::retimer::Retimer::clear_rt_fw_update_state`]
+    pub next_result_clear_rt_fw_update_state: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::retimer::Retimer::set_rt_compliance`]
+    pub next_result_set_rt_compliance: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::retimer::Retimer::reconfigure_retimer`]
+    pub next_result_reconfigure_retimer: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::source_pdp::SourcePdp::get_source_pdp`]
+    pub next_result_get_source_pdp: VecDeque<Result<u32, PdError>>,
+    /// Next results to return for [`type_c_interface::controller::source_pdp::SourcePdp::set_source_pdp`]
+    pub next_result_set_source_pdp: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::port_enable::PortEnable::set_port_enabled`]
+    pub next_result_set_port_enabled: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::Controller::reset_controller`]
+    pub next_result_reset_controller: VecDeque<Result<(), PdError>>,
+    /// Next results to return for [`type_c_interface::controller::Controller::get_die_temperature`]
+    pub next_result_get_die_temperature: VecDeque<Result<i16, PdError>>,
 }
 
 impl Mock {
@@ -78,8 +137,11 @@ impl Mock {
             next_result_get_port_status: VecDeque::new(),
             next_result_clear_dead_battery_flag: VecDeque::new(),
             next_result_enable_sink_path: VecDeque::new(),
+            next_result_set_source_capability: VecDeque::new(),
             next_result_set_max_sink_voltage: VecDeque::new(),
             next_result_get_pd_alert: VecDeque::new(),
+            next_result_get_event_mask: VecDeque::new(),
+            next_result_set_event_mask: VecDeque::new(),
             next_result_set_unconstrained_power: VecDeque::new(),
             next_result_get_other_vdm: VecDeque::new(),
             next_result_get_attn_vdm: VecDeque::new(),
@@ -88,12 +150,29 @@ impl Mock {
             next_result_get_dp_status: VecDeque::new(),
             next_result_set_dp_config: VecDeque::new(),
             next_result_set_tbt_config: VecDeque::new(),
+            next_result_enter_data_mode: VecDeque::new(),
+            next_result_exit_data_mode: VecDeque::new(),
+            next_result_set_billboard_indication: VecDeque::new(),
             next_result_set_usb_control: VecDeque::new(),
+            next_result_get_usb_data_status: VecDeque::new(),
             next_result_execute_lpm_command: VecDeque::new(),
             next_result_hard_reset: VecDeque::new(),
             next_result_get_discovered_svids: VecDeque::new(),
+            next_result_get_pdos: VecDeque::new(),
             next_result_get_discover_identity_sop_response: VecDeque::new(),
             next_result_get_discover_identity_sop_prime_response: VecDeque::new(),
+            next_result_request_power_role_swap: VecDeque::new(),
+            next_result_set_system_power_state_status: VecDeque::new(),
+            next_result_get_rt_fw_update_status: VecDeque::new(),
+            next_result_set_rt_fw_update_state: VecDeque::new(),
+            next_result_clear_rt_fw_update_state: VecDeque::new(),
+            next_result_set_rt_compliance: VecDeque::new(),
+            next_result_reconfigure_retimer: VecDeque::new(),
+            next_result_get_source_pdp: VecDeque::new(),
+            next_result_set_source_pdp: VecDeque::new(),
+            next_result_set_port_enabled: VecDeque::new(),
+            next_result_reset_controller: VecDeque::new(),
+            next_result_get_die_temperature: VecDeque::new(),
         }
     }
 }
@@ -103,3 +182,19 @@ impl Named for Mock {
         self.name
     }
 }
+
+impl type_c_interface::controller::Controller for Mock {
+    async fn reset_controller(&mut self) -> Result<(), PdError> {
+        self.fn_calls.push_back(FnCall::ResetController);
+        self.next_result_reset_controller
+            .pop_front()
+            .expect("next_result_reset_controller not set")
+    }
+
+    async fn get_die_temperature(&mut self) -> Result<i16, PdError> {
+        self.fn_calls.push_back(FnCall::GetDieTemperature);
+        self.next_result_get_die_temperature
+            .pop_front()
+            .expect("next_result_get_die_temperature not set")
+    }
+}
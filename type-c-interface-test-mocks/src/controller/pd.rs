@@ -1,15 +1,19 @@
 //! Mock implementation of [`type_c_interface::controller::pd::Pd`]
 
 use embedded_usb_pd::{LocalPortId, PdError, ado::Ado};
+use power_policy_interface::capability::PowerCapability;
 use type_c_interface::{
     control::{
         dp::{DpConfig, DpStatus},
+        mode::{BillboardIndication, DataMode, ModeEntryStatus},
         pd::PortStatus,
+        pdo::{PdoQuery, Pdos},
         tbt::TbtConfig,
-        usb::UsbControlConfig,
+        usb::{UsbControlConfig, UsbDataStatus},
         vdm::{AttnVdm, OtherVdm, SendVdm},
     },
     controller::pd::Pd,
+    port::event::PortEventBitfield,
 };
 
 use super::FnCall as ControllerFnCall;
@@ -21,7 +25,10 @@ pub enum FnCall {
     GetPortStatus(LocalPortId),
     ClearDeadBatteryFlag(LocalPortId),
     EnableSinkPath(LocalPortId, bool),
+    SetSourceCapability(LocalPortId, PowerCapability),
     GetPdAlert(LocalPortId),
+    GetEventMask(LocalPortId),
+    SetEventMask(LocalPortId, PortEventBitfield),
     SetUnconstrainedPower(LocalPortId, bool),
     GetOtherVdm(LocalPortId),
     GetAttnVdm(LocalPortId),
@@ -30,9 +37,14 @@ pub enum FnCall {
     GetDpStatus(LocalPortId),
     SetDpConfig(LocalPortId, DpConfig),
     SetTbtConfig(LocalPortId, TbtConfig),
+    EnterDataMode(LocalPortId, DataMode),
+    ExitDataMode(LocalPortId, DataMode),
+    SetBillboardIndication(LocalPortId, BillboardIndication),
     SetUsbControl(LocalPortId, UsbControlConfig),
+    GetUsbDataStatus(LocalPortId),
     HardReset(LocalPortId),
     GetDiscoveredSvids(LocalPortId),
+    GetPdos(LocalPortId, PdoQuery),
     GetDiscoverIdentitySopResponse(LocalPortId),
     GetDiscoverIdentitySopPrimeResponse(LocalPortId),
 }
@@ -62,6 +74,14 @@ impl Pd for Mock {
             .expect("next_result_enable_sink_path not set")
     }
 
+    async fn set_source_capability(&mut self, port: LocalPortId, capability: PowerCapability) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::SetSourceCapability(port, capability)));
+        self.next_result_set_source_capability
+            .pop_front()
+            .expect("next_result_set_source_capability not set")
+    }
+
     async fn get_pd_alert(&mut self, port: LocalPortId) -> Result<Option<Ado>, PdError> {
         self.fn_calls.push_back(ControllerFnCall::Pd(FnCall::GetPdAlert(port)));
         self.next_result_get_pd_alert
@@ -69,6 +89,22 @@ impl Pd for Mock {
             .expect("next_result_get_pd_alert not set")
     }
 
+    async fn get_event_mask(&mut self, port: LocalPortId) -> Result<PortEventBitfield, PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::GetEventMask(port)));
+        self.next_result_get_event_mask
+            .pop_front()
+            .expect("next_result_get_event_mask not set")
+    }
+
+    async fn set_event_mask(&mut self, port: LocalPortId, mask: PortEventBitfield) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::SetEventMask(port, mask)));
+        self.next_result_set_event_mask
+            .pop_front()
+            .expect("next_result_set_event_mask not set")
+    }
+
     async fn set_unconstrained_power(&mut self, port: LocalPortId, unconstrained: bool) -> Result<(), PdError> {
         self.fn_calls
             .push_back(ControllerFnCall::Pd(FnCall::SetUnconstrainedPower(port, unconstrained)));
@@ -129,6 +165,34 @@ impl Pd for Mock {
             .expect("next_result_set_tbt_config not set")
     }
 
+    async fn enter_data_mode(&mut self, port: LocalPortId, mode: DataMode) -> Result<ModeEntryStatus, PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::EnterDataMode(port, mode)));
+        self.next_result_enter_data_mode
+            .pop_front()
+            .expect("next_result_enter_data_mode not set")
+    }
+
+    async fn exit_data_mode(&mut self, port: LocalPortId, mode: DataMode) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::ExitDataMode(port, mode)));
+        self.next_result_exit_data_mode
+            .pop_front()
+            .expect("next_result_exit_data_mode not set")
+    }
+
+    async fn set_billboard_indication(
+        &mut self,
+        port: LocalPortId,
+        indication: BillboardIndication,
+    ) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::SetBillboardIndication(port, indication)));
+        self.next_result_set_billboard_indication
+            .pop_front()
+            .expect("next_result_set_billboard_indication not set")
+    }
+
     async fn set_usb_control(&mut self, port: LocalPortId, config: UsbControlConfig) -> Result<(), PdError> {
         self.fn_calls
             .push_back(ControllerFnCall::Pd(FnCall::SetUsbControl(port, config)));
@@ -137,6 +201,14 @@ impl Pd for Mock {
             .expect("next_result_set_usb_control not set")
     }
 
+    async fn get_usb_data_status(&mut self, port: LocalPortId) -> Result<UsbDataStatus, PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::GetUsbDataStatus(port)));
+        self.next_result_get_usb_data_status
+            .pop_front()
+            .expect("next_result_get_usb_data_status not set")
+    }
+
     async fn hard_reset(&mut self, port: LocalPortId) -> Result<(), PdError> {
         self.fn_calls.push_back(ControllerFnCall::Pd(FnCall::HardReset(port)));
         self.next_result_hard_reset
@@ -155,6 +227,14 @@ impl Pd for Mock {
             .expect("next_result_get_discovered_svids not set")
     }
 
+    async fn get_pdos(&mut self, port: LocalPortId, query: PdoQuery) -> Result<Pdos, PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Pd(FnCall::GetPdos(port, query)));
+        self.next_result_get_pdos
+            .pop_front()
+            .expect("next_result_get_pdos not set")
+    }
+
     async fn get_discover_identity_sop_response(
         &mut self,
         port: LocalPortId,
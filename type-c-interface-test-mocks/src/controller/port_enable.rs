@@ -0,0 +1,23 @@
+//! Mock implementation of [`type_c_interface::controller::port_enable::PortEnable`]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::controller::port_enable::PortEnable;
+
+use super::FnCall as ControllerFnCall;
+use super::Mock;
+
+/// Contains a [`PortEnable`] function call and its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FnCall {
+    SetPortEnabled(LocalPortId, bool),
+}
+
+impl PortEnable for Mock {
+    async fn set_port_enabled(&mut self, port: LocalPortId, enabled: bool) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::PortEnable(FnCall::SetPortEnabled(port, enabled)));
+        self.next_result_set_port_enabled
+            .pop_front()
+            .expect("next_result_set_port_enabled not set")
+    }
+}
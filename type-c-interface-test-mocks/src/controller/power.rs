@@ -0,0 +1,27 @@
+//! Mock implementation of [`type_c_interface::controller::power::SystemPowerStateStatus`]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::{control::power::SystemPowerState, controller::power::SystemPowerStateStatus};
+
+use super::FnCall as ControllerFnCall;
+use super::Mock;
+
+/// Contains a [`SystemPowerStateStatus`] function call and its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FnCall {
+    SetSystemPowerStateStatus(LocalPortId, SystemPowerState),
+}
+
+impl SystemPowerStateStatus for Mock {
+    async fn set_system_power_state_status(
+        &mut self,
+        port: LocalPortId,
+        state: SystemPowerState,
+    ) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Power(FnCall::SetSystemPowerStateStatus(port, state)));
+        self.next_result_set_system_power_state_status
+            .pop_front()
+            .expect("next_result_set_system_power_state_status not set")
+    }
+}
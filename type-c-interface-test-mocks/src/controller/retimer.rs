@@ -0,0 +1,60 @@
+//! Mock implementation of [`type_c_interface::controller::retimer::Retimer`]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::control::retimer::RetimerFwUpdateState;
+use type_c_interface::controller::retimer::Retimer;
+
+use super::FnCall as ControllerFnCall;
+use super::Mock;
+
+/// Contains a [`Retimer`] function call and its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FnCall {
+    GetRtFwUpdateStatus(LocalPortId),
+    SetRtFwUpdateState(LocalPortId),
+    ClearRtFwUpdateState(LocalPortId),
+    SetRtCompliance(LocalPortId),
+    ReconfigureRetimer(LocalPortId),
+}
+
+impl Retimer for Mock {
+    async fn get_rt_fw_update_status(&mut self, port: LocalPortId) -> Result<RetimerFwUpdateState, PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Retimer(FnCall::GetRtFwUpdateStatus(port)));
+        self.next_result_get_rt_fw_update_status
+            .pop_front()
+            .expect("next_result_get_rt_fw_update_status not set")
+    }
+
+    async fn set_rt_fw_update_state(&mut self, port: LocalPortId) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Retimer(FnCall::SetRtFwUpdateState(port)));
+        self.next_result_set_rt_fw_update_state
+            .pop_front()
+            .expect("next_result_set_rt_fw_update_state not set")
+    }
+
+    async fn clear_rt_fw_update_state(&mut self, port: LocalPortId) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Retimer(FnCall::ClearRtFwUpdateState(port)));
+        self.next_result_clear_rt_fw_update_state
+            .pop_front()
+            .expect("next_result_clear_rt_fw_update_state not set")
+    }
+
+    async fn set_rt_compliance(&mut self, port: LocalPortId) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Retimer(FnCall::SetRtCompliance(port)));
+        self.next_result_set_rt_compliance
+            .pop_front()
+            .expect("next_result_set_rt_compliance not set")
+    }
+
+    async fn reconfigure_retimer(&mut self, port: LocalPortId) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::Retimer(FnCall::ReconfigureRetimer(port)));
+        self.next_result_reconfigure_retimer
+            .pop_front()
+            .expect("next_result_reconfigure_retimer not set")
+    }
+}
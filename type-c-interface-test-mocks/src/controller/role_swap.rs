@@ -0,0 +1,23 @@
+//! Mock implementation of [`type_c_interface::controller::role_swap::RoleSwap`]
+
+use embedded_usb_pd::{LocalPortId, PdError, PowerRole};
+use type_c_interface::controller::role_swap::RoleSwap;
+
+use super::FnCall as ControllerFnCall;
+use super::Mock;
+
+/// Contains a [`RoleSwap`] function call and its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FnCall {
+    RequestPowerRoleSwap(LocalPortId, PowerRole),
+}
+
+impl RoleSwap for Mock {
+    async fn request_power_role_swap(&mut self, port: LocalPortId, role: PowerRole) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::RoleSwap(FnCall::RequestPowerRoleSwap(port, role)));
+        self.next_result_request_power_role_swap
+            .pop_front()
+            .expect("next_result_request_power_role_swap not set")
+    }
+}
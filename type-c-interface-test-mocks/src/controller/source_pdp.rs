@@ -0,0 +1,36 @@
+//! Mock implementation of [`type_c_interface::controller::source_pdp::SourcePdp`]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::controller::source_pdp::SourcePdp;
+
+use super::FnCall as ControllerFnCall;
+use super::Mock;
+
+/// Contains a [`SourcePdp`] function call and its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FnCall {
+    GetSourcePdp(LocalPortId),
+    SetSourcePdp(LocalPortId, u32, bool),
+}
+
+impl SourcePdp for Mock {
+    async fn get_source_pdp(&mut self, port: LocalPortId) -> Result<u32, PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::SourcePdp(FnCall::GetSourcePdp(port)));
+        self.next_result_get_source_pdp
+            .pop_front()
+            .expect("next_result_get_source_pdp not set")
+    }
+
+    async fn set_source_pdp(&mut self, port: LocalPortId, pdp_mw: u32, renegotiate: bool) -> Result<(), PdError> {
+        self.fn_calls
+            .push_back(ControllerFnCall::SourcePdp(FnCall::SetSourcePdp(
+                port,
+                pdp_mw,
+                renegotiate,
+            )));
+        self.next_result_set_source_pdp
+            .pop_front()
+            .expect("next_result_set_source_pdp not set")
+    }
+}
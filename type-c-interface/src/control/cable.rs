@@ -0,0 +1,153 @@
+//! Cable identity control types
+use embedded_usb_pd::vdm::structured::command::discover_identity::sop_prime::{
+    self, MaximumVbusVoltage, ProductTypeVdos, VbusCurrentHandlingCapability,
+};
+
+/// Cable type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CableType {
+    /// Passive cable
+    Passive,
+    /// Active cable
+    Active,
+}
+
+/// Cable VBUS current handling capability
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CableCurrent {
+    /// 3 A
+    Current3A,
+    /// 5 A
+    Current5A,
+}
+
+impl CableCurrent {
+    /// Returns the current capability in mA
+    pub const fn max_current_ma(self) -> u16 {
+        match self {
+            CableCurrent::Current3A => 3000,
+            CableCurrent::Current5A => 5000,
+        }
+    }
+}
+
+/// Parsed cable identity
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CableIdentity {
+    /// Cable type
+    pub cable_type: CableType,
+    /// VBUS current handling capability
+    pub current: CableCurrent,
+    /// Maximum VBUS voltage in mV
+    pub max_voltage_mv: u16,
+    /// Whether the cable is EPR capable
+    pub epr_capable: bool,
+}
+
+impl CableIdentity {
+    /// Identify the cable from its Discover Identity (SOP') response, returns `None` if the response doesn't describe
+    /// a cable
+    pub fn from_response(response: &sop_prime::ResponseVdos) -> Option<Self> {
+        let (cable_type, vbus_current, max_vbus_voltage, epr_capable) = match response.product_type_vdos {
+            ProductTypeVdos::PassiveCable(vdo) => (
+                CableType::Passive,
+                vdo.vbus_current_handling_capability,
+                vdo.maximum_vbus_voltage,
+                vdo.epr_capable,
+            ),
+            ProductTypeVdos::ActiveCable(vdo, _) => (
+                CableType::Active,
+                vdo.vbus_current_handling_capability,
+                vdo.maximum_vbus_voltage,
+                vdo.epr_capable,
+            ),
+            _ => return None,
+        };
+
+        let current = match vbus_current {
+            VbusCurrentHandlingCapability::ThreeAmps => CableCurrent::Current3A,
+            VbusCurrentHandlingCapability::FiveAmps => CableCurrent::Current5A,
+        };
+
+        let max_voltage_mv = match max_vbus_voltage {
+            MaximumVbusVoltage::TwentyVolts => 20000,
+            MaximumVbusVoltage::ThirtyVolts => 30000,
+            MaximumVbusVoltage::FortyVolts => 40000,
+            MaximumVbusVoltage::FiftyVolts => 50000,
+        };
+
+        Some(Self {
+            cable_type,
+            current,
+            max_voltage_mv,
+            epr_capable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_usb_pd::vdm::structured::command::discover_identity::sop_prime::{ActiveCableVdo1, PassiveCableVdo};
+
+    use super::*;
+
+    #[test]
+    fn test_passive_3a() {
+        let response = sop_prime::ResponseVdos {
+            product_type_vdos: ProductTypeVdos::PassiveCable(PassiveCableVdo {
+                vbus_current_handling_capability: VbusCurrentHandlingCapability::ThreeAmps,
+                maximum_vbus_voltage: MaximumVbusVoltage::TwentyVolts,
+                epr_capable: false,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            CableIdentity::from_response(&response),
+            Some(CableIdentity {
+                cable_type: CableType::Passive,
+                current: CableCurrent::Current3A,
+                max_voltage_mv: 20000,
+                epr_capable: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_active_5a_epr() {
+        let response = sop_prime::ResponseVdos {
+            product_type_vdos: ProductTypeVdos::ActiveCable(
+                ActiveCableVdo1 {
+                    vbus_current_handling_capability: VbusCurrentHandlingCapability::FiveAmps,
+                    maximum_vbus_voltage: MaximumVbusVoltage::FiftyVolts,
+                    epr_capable: true,
+                    ..Default::default()
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        };
+        assert_eq!(
+            CableIdentity::from_response(&response),
+            Some(CableIdentity {
+                cable_type: CableType::Active,
+                current: CableCurrent::Current5A,
+                max_voltage_mv: 50000,
+                epr_capable: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_not_a_cable() {
+        // VCONN-powered USB device
+        let response = sop_prime::ResponseVdos {
+            product_type_vdos: ProductTypeVdos::Vpd(Default::default()),
+            ..Default::default()
+        };
+        assert_eq!(CableIdentity::from_response(&response), None);
+    }
+}
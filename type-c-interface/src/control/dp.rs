@@ -1,4 +1,6 @@
 //! DP-related control types
+use embedded_usb_pd::PdError;
+
 /// DisplayPort pin configuration
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -30,3 +32,25 @@ pub struct DpConfig {
     /// Set DP DFP pin config
     pub dfp_d_pin_cfg: DpPinConfig,
 }
+
+/// Outcome of a DisplayPort status query
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DpStatusQuery {
+    /// DP status was read successfully
+    Status(DpStatus),
+    /// DP isn't supported by the controller or port
+    NotSupported,
+    /// The query failed for another reason
+    Failed(PdError),
+}
+
+impl From<Result<DpStatus, PdError>> for DpStatusQuery {
+    fn from(result: Result<DpStatus, PdError>) -> Self {
+        match result {
+            Ok(status) => DpStatusQuery::Status(status),
+            Err(PdError::InvalidMode | PdError::UnrecognizedCommand) => DpStatusQuery::NotSupported,
+            Err(e) => DpStatusQuery::Failed(e),
+        }
+    }
+}
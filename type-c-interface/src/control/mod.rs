@@ -1,6 +1,9 @@
 //! Shared types for controlling a PD port
+pub mod cable;
 pub mod dp;
+pub mod mode;
 pub mod pd;
+pub mod pdo;
 pub mod power;
 pub mod retimer;
 pub mod svid;
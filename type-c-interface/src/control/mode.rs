@@ -0,0 +1,76 @@
+//! USB4 and Thunderbolt mode entry types
+
+use embedded_usb_pd::PdError;
+
+/// High speed data mode that can be explicitly entered or exited
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataMode {
+    /// USB4, entered through Enter_USB
+    Usb4,
+    /// Thunderbolt 3 alternate mode
+    Thunderbolt,
+}
+
+/// Number of high speed lanes in use
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LaneConfig {
+    /// Single lane
+    #[default]
+    X1,
+    /// Dual lane
+    X2,
+}
+
+impl LaneConfig {
+    /// Returns the number of lanes
+    pub const fn lane_count(self) -> u8 {
+        match self {
+            LaneConfig::X1 => 1,
+            LaneConfig::X2 => 2,
+        }
+    }
+}
+
+/// Result of a successful mode entry
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeEntryStatus {
+    /// Mode that was entered
+    pub mode: DataMode,
+    /// Negotiated lane configuration
+    pub lanes: LaneConfig,
+}
+
+/// Reason a mode couldn't be entered, reported to the host through the USB Billboard device
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeEntryFailure {
+    /// The mode isn't supported by this system
+    Unsupported,
+    /// PD communication with the partner failed
+    PdCommunicationFailure,
+    /// Mode entry was attempted but didn't succeed
+    EntryFailed,
+}
+
+impl From<PdError> for ModeEntryFailure {
+    fn from(error: PdError) -> Self {
+        match error {
+            PdError::InvalidMode => ModeEntryFailure::Unsupported,
+            PdError::Timeout => ModeEntryFailure::PdCommunicationFailure,
+            _ => ModeEntryFailure::EntryFailed,
+        }
+    }
+}
+
+/// Billboard indication for a mode that couldn't be entered
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BillboardIndication {
+    /// Mode that failed
+    pub mode: DataMode,
+    /// Why entry failed
+    pub reason: ModeEntryFailure,
+}
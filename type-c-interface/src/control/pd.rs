@@ -1,7 +1,7 @@
 //! Control types for core PD functionality
 
 use embedded_usb_pd::{
-    DataRole, PlugOrientation, PowerRole,
+    DataRole, PdError, PlugOrientation, PowerRole,
     pdinfo::{AltMode, PowerPathStatus},
     type_c::ConnectionState,
 };
@@ -32,6 +32,8 @@ pub struct PortStatus {
     pub epr: bool,
     /// Port partner is unconstrained
     pub unconstrained_power: bool,
+    /// Negotiated PD specification revision, `None` if no PD contract is in place or the revision is unknown
+    pub pd_revision: Option<PdRevision>,
 }
 
 impl PortStatus {
@@ -50,6 +52,7 @@ impl PortStatus {
             power_path: PowerPathStatus::none(),
             epr: false,
             unconstrained_power: false,
+            pd_revision: None,
         }
     }
 
@@ -75,6 +78,18 @@ impl Default for PortStatus {
     }
 }
 
+/// USB PD specification revision
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PdRevision {
+    /// Revision 1.0
+    Rev1,
+    /// Revision 2.0
+    Rev2,
+    /// Revision 3.x
+    Rev3,
+}
+
 /// PD state-machine configuration
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, Clone, Default, Copy, PartialEq)]
@@ -82,3 +97,28 @@ pub struct PdStateMachineConfig {
     /// Enable or disable the PD state-machine
     pub enabled: bool,
 }
+
+/// Completion status of a data reset
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataResetStatus {
+    /// Data reset completed
+    Completed,
+    /// Data reset isn't supported by the controller or port partner
+    NotSupported,
+    /// Data reset didn't complete in time
+    TimedOut,
+    /// Data reset failed for another reason
+    Failed(PdError),
+}
+
+impl From<Result<(), PdError>> for DataResetStatus {
+    fn from(result: Result<(), PdError>) -> Self {
+        match result {
+            Ok(()) => DataResetStatus::Completed,
+            Err(PdError::InvalidMode | PdError::UnrecognizedCommand) => DataResetStatus::NotSupported,
+            Err(PdError::Timeout) => DataResetStatus::TimedOut,
+            Err(e) => DataResetStatus::Failed(e),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Power data object (PDO) types, as reported by the UCSI `GET_PDOS` command
+use embedded_usb_pd::PowerRole;
+
+/// Which side of the connection to read PDOs from
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdoOwner {
+    /// PDOs advertised by the local port
+    Local,
+    /// PDOs advertised by the port partner
+    Partner,
+}
+
+/// Selects a set of PDOs to read
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PdoQuery {
+    /// Side of the connection
+    pub owner: PdoOwner,
+    /// Source or sink PDOs
+    pub role: PowerRole,
+}
+
+/// A single parsed PDO
+///
+/// Current and power fields are the maximum for source PDOs and the operational value for sink PDOs.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pdo {
+    /// Fixed supply
+    Fixed { voltage_mv: u16, current_ma: u16 },
+    /// Battery supply
+    Battery {
+        min_voltage_mv: u16,
+        max_voltage_mv: u16,
+        power_mw: u32,
+    },
+    /// Variable supply
+    Variable {
+        min_voltage_mv: u16,
+        max_voltage_mv: u16,
+        current_ma: u16,
+    },
+    /// SPR programmable power supply augmented PDO
+    Pps {
+        min_voltage_mv: u16,
+        max_voltage_mv: u16,
+        current_ma: u16,
+    },
+    /// Augmented PDO of a type that isn't parsed, contains the raw value
+    OtherAugmented(u32),
+}
+
+impl Pdo {
+    /// Parse a raw PDO
+    pub const fn from_raw(raw: u32) -> Self {
+        /// Extract `width` bits starting at `shift` as a u16, widths used here are at most 10 bits
+        const fn field(raw: u32, shift: u32, width: u32) -> u16 {
+            ((raw >> shift) & ((1 << width) - 1)) as u16
+        }
+
+        match raw >> 30 {
+            0b00 => Pdo::Fixed {
+                voltage_mv: field(raw, 10, 10) * 50,
+                current_ma: field(raw, 0, 10) * 10,
+            },
+            0b01 => Pdo::Battery {
+                min_voltage_mv: field(raw, 10, 10) * 50,
+                max_voltage_mv: field(raw, 20, 10) * 50,
+                power_mw: field(raw, 0, 10) as u32 * 250,
+            },
+            0b10 => Pdo::Variable {
+                min_voltage_mv: field(raw, 10, 10) * 50,
+                max_voltage_mv: field(raw, 20, 10) * 50,
+                current_ma: field(raw, 0, 10) * 10,
+            },
+            _ => {
+                if (raw >> 28) & 0b11 == 0b00 {
+                    Pdo::Pps {
+                        min_voltage_mv: field(raw, 8, 8) * 100,
+                        max_voltage_mv: field(raw, 17, 8) * 100,
+                        current_ma: field(raw, 0, 7) * 50,
+                    }
+                } else {
+                    Pdo::OtherAugmented(raw)
+                }
+            }
+        }
+    }
+}
+
+/// PDOs returned by the `GET_PDOS` command, stored raw and parsed on access
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pdos {
+    num_pdos: usize,
+    pdos: [u32; Self::MAX_PDOS],
+}
+
+impl Pdos {
+    /// Maximum number of PDOs that can be reported, matches the PD limit of 7 PDOs per message
+    pub const MAX_PDOS: usize = 7;
+
+    /// Create a new response from raw PDOs, in the order they were advertised
+    ///
+    /// PDOs beyond [`Self::MAX_PDOS`] are ignored.
+    pub fn from_raw(raw: &[u32]) -> Self {
+        let mut pdos = [0; Self::MAX_PDOS];
+        let mut num_pdos = 0;
+        for (src, dest) in raw.iter().zip(pdos.iter_mut()) {
+            *dest = *src;
+            num_pdos += 1;
+        }
+
+        Self { num_pdos, pdos }
+    }
+
+    /// Returns the number of PDOs
+    pub fn len(&self) -> usize {
+        self.num_pdos
+    }
+
+    /// Returns true if there are no PDOs
+    pub fn is_empty(&self) -> bool {
+        self.num_pdos == 0
+    }
+
+    /// Returns an iterator over the raw PDOs
+    pub fn raw(&self) -> impl ExactSizeIterator<Item = u32> {
+        self.pdos.iter().copied().take(self.num_pdos)
+    }
+
+    /// Returns an iterator over the parsed PDOs
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Pdo> {
+        self.raw().map(Pdo::from_raw)
+    }
+}
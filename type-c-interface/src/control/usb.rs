@@ -21,3 +21,57 @@ impl Default for UsbControlConfig {
         }
     }
 }
+
+/// Negotiated USB data mode and signaling rate
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsbDataMode {
+    /// No USB data connection
+    #[default]
+    None,
+    /// USB 2.0, 480 Mbps
+    Usb2,
+    /// USB 3.2 Gen 1, 5 Gbps
+    Usb3Gen1,
+    /// USB 3.2 Gen 2, 10 Gbps
+    Usb3Gen2,
+    /// USB 3.2 Gen 2x2, 20 Gbps
+    Usb3Gen2x2,
+    /// USB4 Gen 2, 20 Gbps
+    Usb4Gen2,
+    /// USB4 Gen 3, 40 Gbps
+    Usb4Gen3,
+    /// USB4 Gen 4, 80 Gbps
+    Usb4Gen4,
+}
+
+impl UsbDataMode {
+    /// Returns the maximum data rate for this mode in Mbps
+    pub const fn max_rate_mbps(self) -> u32 {
+        match self {
+            UsbDataMode::None => 0,
+            UsbDataMode::Usb2 => 480,
+            UsbDataMode::Usb3Gen1 => 5_000,
+            UsbDataMode::Usb3Gen2 => 10_000,
+            UsbDataMode::Usb3Gen2x2 | UsbDataMode::Usb4Gen2 => 20_000,
+            UsbDataMode::Usb4Gen3 => 40_000,
+            UsbDataMode::Usb4Gen4 => 80_000,
+        }
+    }
+
+    /// Returns true if this is a USB4 mode
+    pub const fn is_usb4(self) -> bool {
+        matches!(
+            self,
+            UsbDataMode::Usb4Gen2 | UsbDataMode::Usb4Gen3 | UsbDataMode::Usb4Gen4
+        )
+    }
+}
+
+/// USB data connection status
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsbDataStatus {
+    /// Currently negotiated data mode
+    pub mode: UsbDataMode,
+}
@@ -6,8 +6,11 @@ use embedded_usb_pd::PdError;
 pub mod electrical_disconnect;
 pub mod max_sink_voltage;
 pub mod pd;
+pub mod port_enable;
 pub mod power;
 pub mod retimer;
+pub mod role_swap;
+pub mod source_pdp;
 pub mod type_c;
 
 /// Controller ID
@@ -19,4 +22,11 @@ pub struct ControllerId(pub u8);
 pub trait Controller: Named {
     /// Reset the controller
     fn reset_controller(&mut self) -> impl Future<Output = Result<(), PdError>>;
+
+    /// Read the controller's die temperature, in degrees Celsius
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers without a temperature sensor.
+    fn get_die_temperature(&mut self) -> impl Future<Output = Result<i16, PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
 }
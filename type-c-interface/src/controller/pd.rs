@@ -4,12 +4,15 @@ use embedded_usb_pd::{LocalPortId, PdError, ado::Ado};
 
 use crate::control::{
     dp::{DpConfig, DpStatus},
+    mode::{BillboardIndication, DataMode, ModeEntryStatus},
     pd::{PdStateMachineConfig, PortStatus},
+    pdo::{PdoQuery, Pdos},
     svid::DiscoveredSvids,
     tbt::TbtConfig,
-    usb::UsbControlConfig,
+    usb::{UsbControlConfig, UsbDataStatus},
     vdm::{AttnVdm, OtherVdm, SendVdm},
 };
+use crate::port::event::PortEventBitfield;
 
 /// Trait for basic functionality from the PD spec.
 pub trait Pd: Named {
@@ -22,9 +25,37 @@ pub trait Pd: Named {
     /// Enable or disable sink path
     fn enable_sink_path(&mut self, port: LocalPortId, enable: bool) -> impl Future<Output = Result<(), PdError>>;
 
+    /// Set the source capability advertised on the given port
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers that can't change it.
+    fn set_source_capability(
+        &mut self,
+        _port: LocalPortId,
+        _capability: power_policy_interface::capability::PowerCapability,
+    ) -> impl Future<Output = Result<(), PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+
     /// Get current PD alert
     fn get_pd_alert(&mut self, port: LocalPortId) -> impl Future<Output = Result<Option<Ado>, PdError>>;
 
+    /// Get the mask of events the controller is configured to report for the given port
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers without a configurable event mask.
+    fn get_event_mask(&mut self, _port: LocalPortId) -> impl Future<Output = Result<PortEventBitfield, PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+    /// Set the mask of events the controller should report for the given port
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers without a configurable event mask.
+    fn set_event_mask(
+        &mut self,
+        _port: LocalPortId,
+        _mask: PortEventBitfield,
+    ) -> impl Future<Output = Result<(), PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+
     /// Set port unconstrained status
     fn set_unconstrained_power(
         &mut self,
@@ -51,6 +82,33 @@ pub trait Pd: Named {
     /// Set Thunderbolt configuration for the given port
     fn set_tbt_config(&mut self, port: LocalPortId, config: TbtConfig) -> impl Future<Output = Result<(), PdError>>;
 
+    /// Request entry into a USB4 or Thunderbolt mode on the given port
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers without USB4 or Thunderbolt support.
+    fn enter_data_mode(
+        &mut self,
+        _port: LocalPortId,
+        _mode: DataMode,
+    ) -> impl Future<Output = Result<ModeEntryStatus, PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+    /// Request exit from a USB4 or Thunderbolt mode on the given port
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers without USB4 or Thunderbolt support.
+    fn exit_data_mode(&mut self, _port: LocalPortId, _mode: DataMode) -> impl Future<Output = Result<(), PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+    /// Enumerate the Billboard device on the given port to report a mode that couldn't be entered
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers without a Billboard device.
+    fn set_billboard_indication(
+        &mut self,
+        _port: LocalPortId,
+        _indication: BillboardIndication,
+    ) -> impl Future<Output = Result<(), PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+
     /// Set USB control configuration for the given port
     fn set_usb_control(
         &mut self,
@@ -58,9 +116,23 @@ pub trait Pd: Named {
         config: UsbControlConfig,
     ) -> impl Future<Output = Result<(), PdError>>;
 
+    /// Get the negotiated USB data status for the given port
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers that don't report it.
+    fn get_usb_data_status(&mut self, _port: LocalPortId) -> impl Future<Output = Result<UsbDataStatus, PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+
     /// Get the given port's discovered SVIDs
     fn get_discovered_svids(&mut self, port: LocalPortId) -> impl Future<Output = Result<DiscoveredSvids, PdError>>;
 
+    /// Get the source or sink PDOs of the given port or its partner
+    ///
+    /// Returns [`PdError::UnrecognizedCommand`] by default, for controllers that can't read back PDOs.
+    fn get_pdos(&mut self, _port: LocalPortId, _query: PdoQuery) -> impl Future<Output = Result<Pdos, PdError>> {
+        async { Err(PdError::UnrecognizedCommand) }
+    }
+
     /// Get the latest response from the Discover Identity command targeting SOP.
     fn get_discover_identity_sop_response(
         &mut self,
@@ -0,0 +1,11 @@
+use embedded_usb_pd::{LocalPortId, PdError};
+
+use crate::controller::pd::Pd;
+
+/// Functionality related to electrically enabling or disabling a port.
+pub trait PortEnable: Pd {
+    /// Enable or disable the given port
+    ///
+    /// A disabled port presents no terminations and can't source or sink power.
+    fn set_port_enabled(&mut self, port: LocalPortId, enabled: bool) -> impl Future<Output = Result<(), PdError>>;
+}
@@ -0,0 +1,15 @@
+use embedded_usb_pd::{LocalPortId, PdError, PowerRole};
+
+use crate::controller::pd::Pd;
+
+/// Functionality related to initiating role swaps on a port.
+pub trait RoleSwap: Pd {
+    /// Initiate a power role swap on the given port towards the given role
+    ///
+    /// Completion is reported through the normal port status change notifications.
+    fn request_power_role_swap(
+        &mut self,
+        port: LocalPortId,
+        role: PowerRole,
+    ) -> impl Future<Output = Result<(), PdError>>;
+}
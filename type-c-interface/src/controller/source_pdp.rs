@@ -0,0 +1,20 @@
+use embedded_usb_pd::{LocalPortId, PdError};
+
+use crate::controller::pd::Pd;
+
+/// Functionality related to the source power budget of a port.
+pub trait SourcePdp: Pd {
+    /// Get the maximum power the given port may offer as a source, in mW
+    fn get_source_pdp(&mut self, port: LocalPortId) -> impl Future<Output = Result<u32, PdError>>;
+
+    /// Set the maximum power the given port may offer as a source, in mW
+    ///
+    /// If `renegotiate` is true the controller re-advertises its source capabilities so an existing contract
+    /// moves to the new budget.
+    fn set_source_pdp(
+        &mut self,
+        port: LocalPortId,
+        pdp_mw: u32,
+        renegotiate: bool,
+    ) -> impl Future<Output = Result<(), PdError>>;
+}
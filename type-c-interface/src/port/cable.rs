@@ -0,0 +1,11 @@
+use embedded_usb_pd::PdError;
+
+use crate::control::cable::CableIdentity;
+use crate::port::pd::Pd;
+
+/// Functionality related to identifying the attached cable.
+pub trait Cable: Pd {
+    /// Get the identity of the cable attached to this port, returns `None` if the SOP' Discover Identity response
+    /// doesn't describe a cable
+    fn get_cable_identity(&mut self) -> impl Future<Output = Result<Option<CableIdentity>, PdError>>;
+}
@@ -1,8 +1,12 @@
 //! Type-C port related code
+pub mod cable;
 pub mod electrical_disconnect;
 pub mod event;
 pub mod max_sink_voltage;
 pub mod pd;
+pub mod port_enable;
 pub mod power;
 pub mod retimer;
+pub mod role_swap;
+pub mod source_pdp;
 pub mod type_c;
@@ -4,18 +4,29 @@ use embedded_usb_pd::{PdError, ado::Ado};
 
 use crate::control::{
     dp::{DpConfig, DpStatus},
+    mode::{BillboardIndication, DataMode, ModeEntryStatus},
     pd::{PdStateMachineConfig, PortStatus},
+    pdo::{PdoQuery, Pdos},
     svid::DiscoveredSvids,
     tbt::TbtConfig,
-    usb::UsbControlConfig,
+    usb::{UsbControlConfig, UsbDataStatus},
     vdm::{AttnVdm, OtherVdm, SendVdm},
 };
+use crate::port::event::PortEventBitfield;
 
 /// Trait for basic functionality from the PD spec.
 pub trait Pd: Named {
     /// Returns the port status
     fn get_port_status(&mut self) -> impl Future<Output = Result<PortStatus, PdError>>;
 
+    /// Returns the last port status observed by this port without querying the controller
+    fn get_cached_port_status(&self) -> PortStatus;
+
+    /// Read the port status from the controller and process any change from the cached status immediately
+    ///
+    /// Returns the new port status, which is also reflected by [`Self::get_cached_port_status`].
+    fn sync_status(&mut self) -> impl Future<Output = Result<PortStatus, PdError>>;
+
     /// Clear the dead battery flag for this port.
     fn clear_dead_battery_flag(&mut self) -> impl Future<Output = Result<(), PdError>>;
 
@@ -25,6 +36,11 @@ pub trait Pd: Named {
     /// Get current PD alert
     fn get_pd_alert(&mut self) -> impl Future<Output = Result<Option<Ado>, PdError>>;
 
+    /// Get the mask of events the controller is configured to report for this port
+    fn get_event_mask(&mut self) -> impl Future<Output = Result<PortEventBitfield, PdError>>;
+    /// Set the mask of events the controller should report for this port
+    fn set_event_mask(&mut self, mask: PortEventBitfield) -> impl Future<Output = Result<(), PdError>>;
+
     /// Set port unconstrained status
     fn set_unconstrained_power(&mut self, unconstrained: bool) -> impl Future<Output = Result<(), PdError>>;
 
@@ -47,12 +63,30 @@ pub trait Pd: Named {
     /// Set Thunderbolt configuration for this port
     fn set_tbt_config(&mut self, config: TbtConfig) -> impl Future<Output = Result<(), PdError>>;
 
+    /// Request entry into a USB4 or Thunderbolt mode on this port
+    fn enter_data_mode(&mut self, mode: DataMode) -> impl Future<Output = Result<ModeEntryStatus, PdError>>;
+    /// Request exit from a USB4 or Thunderbolt mode on this port
+    fn exit_data_mode(&mut self, mode: DataMode) -> impl Future<Output = Result<(), PdError>>;
+    /// Enumerate the Billboard device on this port to report a mode that couldn't be entered
+    ///
+    /// Failed calls to [`Self::enter_data_mode`] request this automatically.
+    fn set_billboard_indication(
+        &mut self,
+        indication: BillboardIndication,
+    ) -> impl Future<Output = Result<(), PdError>>;
+
     /// Set USB control configuration for this port
     fn set_usb_control(&mut self, config: UsbControlConfig) -> impl Future<Output = Result<(), PdError>>;
 
+    /// Get the negotiated USB data status for this port
+    fn get_usb_data_status(&mut self) -> impl Future<Output = Result<UsbDataStatus, PdError>>;
+
     /// Get this port's discovered SVIDs
     fn get_discovered_svids(&mut self) -> impl Future<Output = Result<DiscoveredSvids, PdError>>;
 
+    /// Get the source or sink PDOs of this port or its partner
+    fn get_pdos(&mut self, query: PdoQuery) -> impl Future<Output = Result<Pdos, PdError>>;
+
     /// Get the latest response from the Discover Identity command targeting SOP.
     fn get_discover_identity_sop_response(&mut self) -> impl Future<Output = Result<sop::ResponseVdos, PdError>>;
 
@@ -0,0 +1,14 @@
+use embedded_usb_pd::PdError;
+
+use crate::port::pd::Pd;
+
+/// Functionality related to electrically enabling or disabling a port.
+pub trait PortEnable: Pd {
+    /// Enable or disable this port
+    ///
+    /// Disabling a port detaches any connected partner and suppresses events until the port is enabled again.
+    fn set_port_enabled(&mut self, enabled: bool) -> impl Future<Output = Result<(), PdError>>;
+
+    /// Returns true if this port is enabled
+    fn is_port_enabled(&self) -> bool;
+}
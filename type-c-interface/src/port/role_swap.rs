@@ -0,0 +1,11 @@
+use embedded_usb_pd::{PdError, PowerRole};
+
+use crate::port::pd::Pd;
+
+/// Functionality related to initiating role swaps on a port.
+pub trait RoleSwap: Pd {
+    /// Initiate a power role swap on this port towards the given role
+    ///
+    /// Completion is reported through the normal port status change notifications.
+    fn request_power_role_swap(&mut self, role: PowerRole) -> impl Future<Output = Result<(), PdError>>;
+}
@@ -0,0 +1,14 @@
+use embedded_usb_pd::PdError;
+
+use crate::port::pd::Pd;
+
+/// Functionality related to the source power budget of a port.
+pub trait SourcePdp: Pd {
+    /// Get the maximum power this port may offer as a source, in mW
+    fn get_source_pdp(&mut self) -> impl Future<Output = Result<u32, PdError>>;
+
+    /// Set the maximum power this port may offer as a source, in mW
+    ///
+    /// This triggers a renegotiation if the port is currently sourcing power
+    fn set_source_pdp(&mut self, pdp_mw: u32) -> impl Future<Output = Result<(), PdError>>;
+}
@@ -43,13 +43,22 @@ pub enum PortEventData {
 }
 
 /// Struct containing a complete port event
-#[derive(Copy, Clone, Debug)]
+#[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PortEvent<'port, Port: Lockable<Inner: Pd>> {
     pub port: &'port Port,
     pub event: PortEventData,
 }
 
+// Manual impls so copying doesn't require `Port: Copy`, only the reference is copied
+impl<'port, Port: Lockable<Inner: Pd>> Clone for PortEvent<'port, Port> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'port, Port: Lockable<Inner: Pd>> Copy for PortEvent<'port, Port> {}
+
 /// Message generated when a debug accessory is connected or disconnected
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -68,6 +77,30 @@ pub struct UsciChangeIndicatorData {
     pub notify_opm: bool,
 }
 
+/// Port fault condition reported by a PD alert
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PortFault {
+    /// Over-voltage protection triggered
+    OverVoltage,
+    /// Over-current protection triggered
+    OverCurrent,
+    /// Over-temperature protection triggered
+    OverTemperature,
+}
+
+impl PortFault {
+    /// Returns the fault carried by the given ADO, if it is a fault-type alert
+    pub fn from_ado(ado: Ado) -> Option<Self> {
+        match ado {
+            Ado::Ovp => Some(PortFault::OverVoltage),
+            Ado::Ocp => Some(PortFault::OverCurrent),
+            Ado::Otp => Some(PortFault::OverTemperature),
+            _ => None,
+        }
+    }
+}
+
 /// Top-level comms message
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -75,6 +108,8 @@ pub struct UsciChangeIndicatorData {
 pub enum EventData {
     DebugAccessory(DebugAccessoryData),
     UsciChangeIndicator(UsciChangeIndicatorData),
+    /// Port fault reported by a PD alert
+    PortFault(PortFault),
 }
 
 /// Top-level comms message
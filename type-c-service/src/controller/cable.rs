@@ -0,0 +1,32 @@
+//! Cable port trait implementation
+use embedded_services::{event::NonBlockingSender, sync::Lockable};
+use embedded_usb_pd::PdError;
+use type_c_interface::control::cable::CableIdentity;
+
+use super::*;
+use crate::controller::state::SharedState;
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> type_c_interface::port::cable::Cable for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    async fn get_cable_identity(&mut self) -> Result<Option<CableIdentity>, PdError> {
+        let response = self
+            .controller
+            .lock()
+            .await
+            .get_discover_identity_sop_prime_response(self.port)
+            .await?;
+
+        let identity = CableIdentity::from_response(&response);
+        if identity.is_none() {
+            debug!("({}): SOP' response doesn't describe a cable", self.name);
+        }
+        Ok(identity)
+    }
+}
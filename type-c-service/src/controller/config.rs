@@ -1,3 +1,5 @@
+use type_c_interface::control::pd::PdRevision;
+
 /// Configuration for Type-C controller wrapper
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -5,6 +7,53 @@
 pub struct Config {
     /// Unconstrained behavior for sink role
     pub unconstrained_sink: UnconstrainedSink,
+    /// Default limit on the source capability advertised when entering a provider state
+    ///
+    /// The capability chosen by the power policy is advertised, capped to this voltage and current. If `None`, the
+    /// controller's own source capability configuration is left untouched.
+    pub default_source_capability: Option<power_policy_interface::capability::PowerCapability>,
+    /// Automatic dead battery flag clearing behavior
+    pub dead_battery_clear: DeadBatteryClear,
+    /// Minimum negotiated PD revision required to accept a power contract
+    ///
+    /// If `None`, contracts are accepted at any revision. Contracts for which the controller doesn't report a
+    /// revision are always accepted.
+    pub min_pd_revision: Option<PdRevision>,
+    /// Optional features supported by the controller for this port
+    ///
+    /// Commands that need a feature missing from this set are rejected without being sent to the controller.
+    /// Defaults to [`Features::all`].
+    pub features: Features,
+    /// Number of consecutive command timeouts after which the controller is reset and the port resynced
+    ///
+    /// If `None`, the controller is never reset automatically. See [`super::Port::recover_if_needed`].
+    pub timeout_recovery_threshold: Option<u8>,
+}
+
+bitflags::bitflags! {
+    /// Bitmask of optional controller features
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct Features: u8 {
+        /// Vendor defined messages
+        const VDM = 1 << 0;
+        /// DisplayPort alt-mode
+        const DP = 1 << 1;
+        /// Retimer control and FW update
+        const RETIMER = 1 << 2;
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Features {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "Features({=u8:#x})", self.bits())
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self::all()
+    }
 }
 
 /// Unconstrained behavior for sink role
@@ -20,3 +69,17 @@ pub enum UnconstrainedSink {
     /// Never signal unconstrained power
     Never,
 }
+
+/// Automatic dead battery flag clearing behavior
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum DeadBatteryClear {
+    /// Never clear the dead battery flag automatically
+    #[default]
+    Never,
+    /// Clear the dead battery flag once a consumer contract meets a power threshold in mW
+    ///
+    /// The flag is cleared at most once per attach.
+    ConsumerPowerThresholdMilliwatts(u32),
+}
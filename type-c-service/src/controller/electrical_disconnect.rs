@@ -19,10 +19,12 @@ impl<
     for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn execute_electrical_disconnect(&mut self, reconnect_time_s: Option<NonZeroU8>) -> Result<(), PdError> {
-        self.controller
-            .lock()
-            .await
-            .execute_electrical_disconnect(self.port, reconnect_time_s)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .execute_electrical_disconnect(self.port, reconnect_time_s)
+                .await,
+        )
     }
 }
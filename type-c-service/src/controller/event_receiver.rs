@@ -68,6 +68,9 @@ impl<R: Receiver<PortEventBitfield>, LoopbackReceiver: Receiver<Loopback>> PortE
                 embassy_futures::yield_now().await;
                 streaming_state
             } else {
+                // Drop safety: neither receiver dequeues an event until its future completes, and the
+                // event is stored in `streaming_state` before the next await point. Dropping this future
+                // at any await point therefore can't lose a software or hardware event.
                 let (Either::First(Loopback::PortEvent(events)) | Either::Second(events)) =
                     select(self.loopback_receiver.wait_next(), self.receiver.wait_next()).await;
                 self.streaming_state
@@ -118,23 +121,36 @@ impl<
     /// Wait for the next port event from any port.
     ///
     /// Returns the local port ID and the event bitfield.
+    ///
+    /// This future is safe to drop. Pending port events stay queued in their receivers, and the sink
+    /// ready deadline is only cleared once the synthesized event is returned. If this future is dropped
+    /// after the deadline passes, the next call regenerates the sink ready event immediately.
     pub async fn wait_event(&mut self) -> Event {
-        let timeout = self.shared_state.lock().await.sink_ready_timeout;
-        match select(self.port_event_receiver.wait_next(), async move {
-            if let Some(timeout) = timeout {
-                Timer::at(timeout).await;
-            } else {
-                pending::<()>().await;
-            }
-        })
-        .await
-        {
-            Either::First(event) => Event::PortEvent(event),
-            Either::Second(_) => {
-                let mut status_event = PortStatusEventBitfield::none();
-                status_event.set_sink_ready(true);
-                self.shared_state.lock().await.sink_ready_timeout = None;
-                Event::PortEvent(PortEvent::StatusChanged(status_event))
+        loop {
+            let timeout = self.shared_state.lock().await.sink_ready_timeout;
+            match select(self.port_event_receiver.wait_next(), async move {
+                if let Some(timeout) = timeout {
+                    Timer::at(timeout).await;
+                    timeout
+                } else {
+                    pending().await
+                }
+            })
+            .await
+            {
+                Either::First(event) => return Event::PortEvent(event),
+                Either::Second(deadline) => {
+                    let mut shared_state = self.shared_state.lock().await;
+                    // The deadline may have been cleared or restarted while waiting
+                    if shared_state.sink_ready_timeout != Some(deadline) {
+                        continue;
+                    }
+
+                    shared_state.sink_ready_timeout = None;
+                    let mut status_event = PortStatusEventBitfield::none();
+                    status_event.set_sink_ready(true);
+                    return Event::PortEvent(PortEvent::StatusChanged(status_event));
+                }
             }
         }
     }
@@ -33,7 +33,7 @@ impl<
 
         if disable_sink_path {
             debug!("({}): Disabling sink path before max sink voltage change", self.name);
-            self.controller.lock().await.enable_sink_path(self.port, false).await?;
+            self.track(self.controller.lock().await.enable_sink_path(self.port, false).await)?;
 
             // Move our local state out of the consumer state and notify the power policy so it stops
             // tracking us as the active consumer and broadcasts a ConsumerDisconnected event. The
@@ -52,10 +52,12 @@ impl<
             }
         }
 
-        self.controller
-            .lock()
-            .await
-            .set_max_sink_voltage(self.port, voltage_mv)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_max_sink_voltage(self.port, voltage_mv)
+                .await,
+        )
     }
 }
@@ -1,8 +1,12 @@
 //! Struct that manages per-port state, interfacing with a controller object that exposes multiple ports.
+use core::cell::Cell;
+
+use embassy_time::Duration;
 use embedded_services::{debug, error, event::NonBlockingSender, info, named::Named, sync::Lockable};
 use embedded_usb_pd::{LocalPortId, PdError};
 use power_policy_interface::psu::PsuState;
 use type_c_interface::control::pd::PortStatus;
+use type_c_interface::controller::Controller;
 use type_c_interface::controller::pd::Pd;
 use type_c_interface::port::event::PortEventBitfield;
 use type_c_interface::port::{event::PortEvent as InterfacePortEvent, event::PortStatusEventBitfield};
@@ -10,7 +14,9 @@ use type_c_interface::service::event::{PortEventData as ServicePortEventData, St
 
 use crate::controller::event::{Event, Loopback};
 use crate::controller::state::SharedState;
+use crate::controller::stats::PortStats;
 
+pub mod cable;
 pub mod config;
 pub mod electrical_disconnect;
 pub mod event;
@@ -18,9 +24,15 @@ pub mod event_receiver;
 pub mod macros;
 pub mod max_sink_voltage;
 mod pd;
+pub mod port_enable;
 mod power;
+mod recovery;
 pub mod retimer;
+pub mod role_swap;
+pub mod source_pdp;
 pub mod state;
+pub mod stats;
+mod temperature;
 pub mod type_c;
 pub mod ucsi;
 
@@ -35,6 +47,9 @@ pub struct Port<
     /// Local port
     port: LocalPortId,
     /// Controller
+    ///
+    /// Shared by every port on the same controller. Each command holds the lock for its duration, so at most one
+    /// command is in flight per controller and any others wait on the lock in turn.
     controller: &'device C,
     /// Per-port PSU state
     psu_state: power_policy_interface::psu::State,
@@ -52,6 +67,14 @@ pub struct Port<
     shared_state: &'device Shared,
     /// Loopback sender
     loopback_sender: LoopbackSender,
+    /// Whether the port is enabled, events are ignored while disabled
+    enabled: bool,
+    /// Reliability statistics
+    stats: PortStats,
+    /// Number of consecutive controller commands that timed out
+    consecutive_timeouts: Cell<u8>,
+    /// Whether the dead battery flag has been cleared since the partner attached
+    dead_battery_cleared: bool,
 }
 
 impl<
@@ -87,16 +110,67 @@ impl<
             shared_state,
             loopback_sender,
             type_c_sender,
+            enabled: true,
+            stats: PortStats::new(),
+            consecutive_timeouts: Cell::new(0),
+            dead_battery_cleared: false,
         }
     }
 
-    /// Top-level processing function
-    pub async fn process_event(&mut self, event: Event) -> Result<Option<ServicePortEventData>, PdError> {
+    /// Returns true if the controller supports every feature in `features` for this port
+    pub fn supports(&self, features: config::Features) -> bool {
+        self.config.features.contains(features)
+    }
+
+    /// Reject a command that needs `features` if the controller doesn't support them for this port
+    fn require_features(&self, features: config::Features) -> Result<(), PdError> {
+        if self.supports(features) {
+            Ok(())
+        } else {
+            error!("({}): Feature {:?} not supported", self.name, features);
+            Err(PdError::UnrecognizedCommand)
+        }
+    }
+
+    /// Process an event, see [`Self::process_event`]
+    async fn dispatch_event(&mut self, event: Event) -> Result<Option<ServicePortEventData>, PdError> {
+        if !self.enabled {
+            debug!("({}): Port disabled, ignoring event", self.name);
+            return Ok(None);
+        }
+
         match event {
             Event::PortEvent(port_event) => self.process_port_event(port_event).await,
         }
     }
 
+    /// Track consecutive controller timeouts in the result of a command, any other result breaks the streak
+    fn track<T>(&self, result: Result<T, PdError>) -> Result<T, PdError> {
+        if let Err(PdError::Timeout) = result {
+            let consecutive_timeouts = self.consecutive_timeouts.get().saturating_add(1);
+            self.consecutive_timeouts.set(consecutive_timeouts);
+            error!(
+                "({}): Controller timed out, {} consecutive timeouts",
+                self.name, consecutive_timeouts
+            );
+        } else {
+            self.consecutive_timeouts.set(0);
+        }
+        result
+    }
+
+    /// Number of consecutive controller commands that timed out
+    pub fn consecutive_timeouts(&self) -> u8 {
+        self.consecutive_timeouts.get()
+    }
+
+    /// Returns true if enough consecutive timeouts have occurred that the controller should be reset
+    pub fn recovery_needed(&self) -> bool {
+        self.config
+            .timeout_recovery_threshold
+            .is_some_and(|threshold| self.consecutive_timeouts.get() >= threshold)
+    }
+
     /// Process a port notification
     async fn process_port_event(&mut self, event: InterfacePortEvent) -> Result<Option<ServicePortEventData>, PdError> {
         match event {
@@ -119,7 +193,31 @@ impl<
         &mut self,
         status_event: PortStatusEventBitfield,
     ) -> Result<ServicePortEventData, PdError> {
-        let new_status = self.controller.lock().await.get_port_status(self.port).await?;
+        let new_status = self.track(self.controller.lock().await.get_port_status(self.port).await)?;
+        self.process_port_status(status_event, new_status).await
+    }
+
+    /// Inject a port status change as if the controller had raised `status_event` and reported `status`
+    ///
+    /// The event goes through the same processing as a real status change, including updating the cached status
+    /// and notifying the type-C and power policy services, but the controller isn't queried for its status.
+    /// Intended for testing consumers without a real controller.
+    #[cfg(feature = "event-injection")]
+    pub async fn inject_port_event(
+        &mut self,
+        status_event: PortStatusEventBitfield,
+        status: PortStatus,
+    ) -> Result<ServicePortEventData, PdError> {
+        debug!("({}) injecting status event", self.name);
+        self.process_port_status(status_event, status).await
+    }
+
+    /// Process a port status change given the new status
+    async fn process_port_status(
+        &mut self,
+        status_event: PortStatusEventBitfield,
+        new_status: PortStatus,
+    ) -> Result<ServicePortEventData, PdError> {
         debug!("({}) status: {:#?}", self.name, new_status);
         debug!("({}) status events: {:#?}", self.name, status_event);
 
@@ -165,6 +263,7 @@ impl<
         info!("Plug event");
         if new_status.is_connected() {
             info!("Plug inserted");
+            self.stats.record_attach();
             if self.psu_state.psu_state != PsuState::Detached {
                 info!("Device not in detached state, recovering");
                 self.psu_state.detach();
@@ -186,6 +285,7 @@ impl<
         } else {
             info!("Plug removed");
             self.psu_state.detach();
+            self.dead_battery_cleared = false;
             if self
                 .power_policy_sender
                 .try_send(power_policy_interface::psu::event::EventData::Detached)
@@ -203,29 +303,110 @@ impl<
         self.status
     }
 
+    /// Read the port status directly from the controller, update the cache, and return the fresh status
+    ///
+    /// This is intended for consumers that suspect the cached status is stale. No events are generated.
+    pub async fn refresh_port_status(&mut self) -> Result<PortStatus, PdError> {
+        let status = self.track(self.controller.lock().await.get_port_status(self.port).await)?;
+        debug!("({}) refreshed status: {:#?}", self.name, status);
+        self.status = status;
+        Ok(status)
+    }
+
+    /// Get the time remaining until this port's pending sink ready timeout expires, if one is pending
+    pub async fn sink_ready_timeout_remaining(&self) -> Option<Duration> {
+        self.shared_state.lock().await.sink_ready_timeout_remaining()
+    }
+
+    /// Clear this port's pending sink ready timeout, returns true if one was pending
+    ///
+    /// No sink ready event is generated for a cleared timeout.
+    pub async fn clear_sink_ready_timeout(&mut self) -> bool {
+        let cleared = self.shared_state.lock().await.clear_sink_ready_timeout();
+        if cleared {
+            info!("({}): Sink ready timeout cleared", self.name);
+        }
+        cleared
+    }
+
+    /// Get the reliability statistics for this port
+    pub fn stats(&self) -> PortStats {
+        self.stats
+    }
+
+    /// Reset the reliability statistics for this port to zero
+    pub fn reset_stats(&mut self) {
+        self.stats = PortStats::new();
+    }
+
     /// Synchronize the state between the controller and the internal state
     pub async fn sync_state(&mut self) -> Result<(), PdError> {
-        let status = self.controller.lock().await.get_port_status(self.port).await?;
+        let status = self.track(self.controller.lock().await.get_port_status(self.port).await)?;
 
         let mut event = PortEventBitfield::none();
-        let previous_status = self.status;
+        event.status = Self::status_changes(&self.status, &status);
+
+        if event != PortEventBitfield::none() && self.loopback_sender.try_send(Loopback::PortEvent(event)).is_none() {
+            error!("Failed to send loopback event");
+        }
+        Ok(())
+    }
+
+    /// Synchronize the state between the controller and the internal state, processing any change immediately
+    ///
+    /// Unlike [`Self::sync_state`], changes are processed before returning rather than through the event receiver,
+    /// so the cached status is valid as soon as this returns. Intended for use on startup.
+    pub async fn sync_status(&mut self) -> Result<PortStatus, PdError> {
+        let status = self.track(self.controller.lock().await.get_port_status(self.port).await)?;
+        let status_event = Self::status_changes(&self.status, &status);
+
+        if status_event == PortStatusEventBitfield::none() {
+            self.status = status;
+        } else {
+            self.process_port_status(status_event, status).await?;
+        }
+        Ok(self.status)
+    }
+
+    /// Status events describing the difference between `previous_status` and `status`
+    fn status_changes(previous_status: &PortStatus, status: &PortStatus) -> PortStatusEventBitfield {
+        let mut status_event = PortStatusEventBitfield::none();
 
         if previous_status.is_connected() != status.is_connected() {
-            event.status.set_plug_inserted_or_removed(true);
+            status_event.set_plug_inserted_or_removed(true);
         }
 
         if previous_status.available_sink_contract != status.available_sink_contract {
-            event.status.set_new_power_contract_as_consumer(true);
+            status_event.set_new_power_contract_as_consumer(true);
         }
 
         if previous_status.available_source_contract != status.available_source_contract {
-            event.status.set_new_power_contract_as_provider(true);
+            status_event.set_new_power_contract_as_provider(true);
         }
 
-        if event != PortEventBitfield::none() && self.loopback_sender.try_send(Loopback::PortEvent(event)).is_none() {
-            error!("Failed to send loopback event");
+        status_event
+    }
+}
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd + Controller>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    /// Top-level processing function
+    ///
+    /// If processing leaves too many consecutive command timeouts, the controller is reset before returning, see
+    /// [`Self::recover_if_needed`]. The result of processing the event is returned either way.
+    pub async fn process_event(&mut self, event: Event) -> Result<Option<ServicePortEventData>, PdError> {
+        let result = self.dispatch_event(event).await;
+        if let Err(e) = self.recover_if_needed().await {
+            error!("({}): Controller recovery failed: {:?}", self.name, e);
         }
-        Ok(())
+        result
     }
 }
 
@@ -1,21 +1,25 @@
 //! PD functionality unrelated to power contracts and general port status
+use embassy_time::{Duration, with_timeout};
 use embedded_services::{event::NonBlockingSender, sync::Lockable};
 use embedded_usb_pd::PdError;
 use embedded_usb_pd::ado::Ado;
 use embedded_usb_pd::vdm::structured::command::discover_identity::{sop, sop_prime};
 use type_c_interface::control::{
-    dp::{DpConfig, DpStatus},
-    pd::{PdStateMachineConfig, PortStatus},
+    dp::{DpConfig, DpStatus, DpStatusQuery},
+    mode::{BillboardIndication, DataMode, ModeEntryStatus},
+    pd::{DataResetStatus, PdStateMachineConfig, PortStatus},
+    pdo::{PdoQuery, Pdos},
     svid::DiscoveredSvids,
     tbt::TbtConfig,
-    usb::UsbControlConfig,
+    usb::{UsbControlConfig, UsbDataStatus},
     vdm::{AttnVdm, OtherVdm, SendVdm},
 };
 use type_c_interface::controller::pd::StateMachine;
-use type_c_interface::port::event::{VdmData, VdmNotification};
-use type_c_interface::service::event::PortEventData as ServicePortEventData;
+use type_c_interface::port::event::{PortEventBitfield, VdmData, VdmNotification};
+use type_c_interface::service::event::{PortEventData as ServicePortEventData, PortFault};
 
 use super::*;
+use crate::controller::config::Features;
 use crate::controller::state::SharedState;
 
 impl<
@@ -36,10 +40,14 @@ impl<
         let vdm_data = {
             let mut controller = self.controller.lock().await;
             match event {
-                VdmNotification::Entered => VdmData::Entered(controller.get_other_vdm(self.port).await?),
-                VdmNotification::Exited => VdmData::Exited(controller.get_other_vdm(self.port).await?),
-                VdmNotification::OtherReceived => VdmData::ReceivedOther(controller.get_other_vdm(self.port).await?),
-                VdmNotification::AttentionReceived => VdmData::ReceivedAttn(controller.get_attn_vdm(self.port).await?),
+                VdmNotification::Entered => VdmData::Entered(self.track(controller.get_other_vdm(self.port).await)?),
+                VdmNotification::Exited => VdmData::Exited(self.track(controller.get_other_vdm(self.port).await)?),
+                VdmNotification::OtherReceived => {
+                    VdmData::ReceivedOther(self.track(controller.get_other_vdm(self.port).await)?)
+                }
+                VdmNotification::AttentionReceived => {
+                    VdmData::ReceivedAttn(self.track(controller.get_attn_vdm(self.port).await)?)
+                }
                 _ => {
                     info!("({}): Received unknown VDM event: {:?}", self.name, event);
                     return Ok(None);
@@ -57,7 +65,7 @@ impl<
     /// Process a DisplayPort status update by retrieving the current DP status from the `controller` for the appropriate `port`.
     pub(super) async fn process_dp_status_update(&mut self) -> Result<ServicePortEventData, PdError> {
         debug!("({}): Processing DP status update event", self.name);
-        let status = self.controller.lock().await.get_dp_status(self.port).await?;
+        let status = self.track(self.controller.lock().await.get_dp_status(self.port).await)?;
         let event = ServicePortEventData::DpStatusUpdate(status);
         if self.type_c_sender.try_send(event).is_none() {
             error!("Failed to send DP status update type-C event");
@@ -66,9 +74,12 @@ impl<
     }
 
     pub(super) async fn process_pd_alert(&mut self) -> Result<Option<ServicePortEventData>, PdError> {
-        let ado = self.controller.lock().await.get_pd_alert(self.port).await?;
+        let ado = self.track(self.controller.lock().await.get_pd_alert(self.port).await)?;
         debug!("({}): PD alert: {:#?}", self.name, ado);
         if let Some(ado) = ado {
+            if PortFault::from_ado(ado).is_some() {
+                self.stats.record_fault();
+            }
             let event = ServicePortEventData::Alert(ado);
             if self.type_c_sender.try_send(event).is_none() {
                 error!("Failed to send PD alert type-C event");
@@ -79,6 +90,45 @@ impl<
             Ok(None)
         }
     }
+
+    /// Execute a data reset (DRST), giving up if it doesn't complete within `timeout`
+    ///
+    /// The timeout also covers waiting for access to the controller.
+    pub async fn data_reset(&mut self, timeout: Duration) -> DataResetStatus {
+        info!("({}): Executing data reset", self.name);
+        let result = self.track(
+            with_timeout(timeout, async {
+                self.controller.lock().await.execute_drst(self.port).await
+            })
+            .await
+            .unwrap_or(Err(PdError::Timeout)),
+        );
+
+        let status = DataResetStatus::from(result);
+        if status != DataResetStatus::Completed {
+            error!("({}): Data reset failed: {:?}", self.name, status);
+        }
+        status
+    }
+
+    /// Query DisplayPort status, distinguishing a controller or port without DP support from other failures
+    ///
+    /// Consumers can use [`DpStatusQuery::NotSupported`] to hide DP functionality rather than report an error.
+    pub async fn dp_status(&mut self) -> DpStatusQuery {
+        if !self.supports(Features::DP) {
+            debug!("({}): DP not supported", self.name);
+            return DpStatusQuery::NotSupported;
+        }
+
+        let result = self.track(self.controller.lock().await.get_dp_status(self.port).await);
+        let query = DpStatusQuery::from(result);
+        match query {
+            DpStatusQuery::NotSupported => debug!("({}): DP not supported", self.name),
+            DpStatusQuery::Failed(e) => error!("({}): Failed to get DP status: {:?}", self.name, e),
+            DpStatusQuery::Status(_) => {}
+        }
+        query
+    }
 }
 
 impl<
@@ -91,83 +141,157 @@ impl<
 > type_c_interface::port::pd::Pd for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn get_port_status(&mut self) -> Result<PortStatus, PdError> {
-        self.controller.lock().await.get_port_status(self.port).await
+        self.track(self.controller.lock().await.get_port_status(self.port).await)
+    }
+
+    fn get_cached_port_status(&self) -> PortStatus {
+        self.status
+    }
+
+    async fn sync_status(&mut self) -> Result<PortStatus, PdError> {
+        self.sync_status().await
     }
 
     async fn clear_dead_battery_flag(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.clear_dead_battery_flag(self.port).await
+        self.track(self.controller.lock().await.clear_dead_battery_flag(self.port).await)
     }
 
     async fn enable_sink_path(&mut self, enable: bool) -> Result<(), PdError> {
-        self.controller.lock().await.enable_sink_path(self.port, enable).await
+        self.track(self.controller.lock().await.enable_sink_path(self.port, enable).await)
     }
 
     async fn get_pd_alert(&mut self) -> Result<Option<Ado>, PdError> {
-        self.controller.lock().await.get_pd_alert(self.port).await
+        self.track(self.controller.lock().await.get_pd_alert(self.port).await)
+    }
+
+    async fn get_event_mask(&mut self) -> Result<PortEventBitfield, PdError> {
+        self.track(self.controller.lock().await.get_event_mask(self.port).await)
+    }
+
+    async fn set_event_mask(&mut self, mask: PortEventBitfield) -> Result<(), PdError> {
+        self.track(self.controller.lock().await.set_event_mask(self.port, mask).await)
     }
 
     async fn set_unconstrained_power(&mut self, unconstrained: bool) -> Result<(), PdError> {
-        self.controller
-            .lock()
-            .await
-            .set_unconstrained_power(self.port, unconstrained)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_unconstrained_power(self.port, unconstrained)
+                .await,
+        )
     }
 
     async fn get_other_vdm(&mut self) -> Result<OtherVdm, PdError> {
-        self.controller.lock().await.get_other_vdm(self.port).await
+        self.require_features(Features::VDM)?;
+        self.track(self.controller.lock().await.get_other_vdm(self.port).await)
     }
 
     async fn get_attn_vdm(&mut self) -> Result<AttnVdm, PdError> {
-        self.controller.lock().await.get_attn_vdm(self.port).await
+        self.require_features(Features::VDM)?;
+        self.track(self.controller.lock().await.get_attn_vdm(self.port).await)
     }
 
     async fn send_vdm(&mut self, tx_vdm: SendVdm) -> Result<(), PdError> {
-        self.controller.lock().await.send_vdm(self.port, tx_vdm).await
+        self.require_features(Features::VDM)?;
+        self.track(self.controller.lock().await.send_vdm(self.port, tx_vdm).await)
     }
 
     async fn execute_drst(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.execute_drst(self.port).await
+        self.track(self.controller.lock().await.execute_drst(self.port).await)
     }
 
     async fn get_dp_status(&mut self) -> Result<DpStatus, PdError> {
-        self.controller.lock().await.get_dp_status(self.port).await
+        self.require_features(Features::DP)?;
+        self.track(self.controller.lock().await.get_dp_status(self.port).await)
     }
 
     async fn set_dp_config(&mut self, config: DpConfig) -> Result<(), PdError> {
-        self.controller.lock().await.set_dp_config(self.port, config).await
+        self.require_features(Features::DP)?;
+        self.track(self.controller.lock().await.set_dp_config(self.port, config).await)
     }
 
     async fn set_tbt_config(&mut self, config: TbtConfig) -> Result<(), PdError> {
-        self.controller.lock().await.set_tbt_config(self.port, config).await
+        self.track(self.controller.lock().await.set_tbt_config(self.port, config).await)
+    }
+
+    async fn enter_data_mode(&mut self, mode: DataMode) -> Result<ModeEntryStatus, PdError> {
+        let result = self.track(self.controller.lock().await.enter_data_mode(self.port, mode).await);
+        match &result {
+            Ok(status) => info!(
+                "({}): Entered {:?} with {:?} lanes",
+                self.name, status.mode, status.lanes
+            ),
+            Err(e) => {
+                error!("({}): Failed to enter {:?}: {:?}", self.name, mode, e);
+                let indication = BillboardIndication {
+                    mode,
+                    reason: (*e).into(),
+                };
+                match self.set_billboard_indication(indication).await {
+                    Ok(()) => {}
+                    Err(PdError::UnrecognizedCommand) => {
+                        debug!("({}): No billboard device, not reporting mode entry failure", self.name)
+                    }
+                    Err(e) => error!("({}): Failed to set billboard indication: {:?}", self.name, e),
+                }
+            }
+        }
+        result
+    }
+
+    async fn exit_data_mode(&mut self, mode: DataMode) -> Result<(), PdError> {
+        self.track(self.controller.lock().await.exit_data_mode(self.port, mode).await)
+    }
+
+    async fn set_billboard_indication(&mut self, indication: BillboardIndication) -> Result<(), PdError> {
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_billboard_indication(self.port, indication)
+                .await,
+        )
     }
 
     async fn set_usb_control(&mut self, config: UsbControlConfig) -> Result<(), PdError> {
-        self.controller.lock().await.set_usb_control(self.port, config).await
+        self.track(self.controller.lock().await.set_usb_control(self.port, config).await)
+    }
+
+    async fn get_usb_data_status(&mut self) -> Result<UsbDataStatus, PdError> {
+        self.track(self.controller.lock().await.get_usb_data_status(self.port).await)
     }
 
     async fn hard_reset(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.hard_reset(self.port).await
+        self.track(self.controller.lock().await.hard_reset(self.port).await)
     }
 
     async fn get_discovered_svids(&mut self) -> Result<DiscoveredSvids, PdError> {
-        self.controller.lock().await.get_discovered_svids(self.port).await
+        self.track(self.controller.lock().await.get_discovered_svids(self.port).await)
+    }
+
+    async fn get_pdos(&mut self, query: PdoQuery) -> Result<Pdos, PdError> {
+        self.track(self.controller.lock().await.get_pdos(self.port, query).await)
     }
 
     async fn get_discover_identity_sop_response(&mut self) -> Result<sop::ResponseVdos, PdError> {
-        self.controller
-            .lock()
-            .await
-            .get_discover_identity_sop_response(self.port)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .get_discover_identity_sop_response(self.port)
+                .await,
+        )
     }
 
     async fn get_discover_identity_sop_prime_response(&mut self) -> Result<sop_prime::ResponseVdos, PdError> {
-        self.controller
-            .lock()
-            .await
-            .get_discover_identity_sop_prime_response(self.port)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .get_discover_identity_sop_prime_response(self.port)
+                .await,
+        )
     }
 }
 
@@ -181,10 +305,12 @@ impl<
 > type_c_interface::port::pd::StateMachine for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn set_pd_state_machine_config(&mut self, config: PdStateMachineConfig) -> Result<(), PdError> {
-        self.controller
-            .lock()
-            .await
-            .set_pd_state_machine_config(self.port, config)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_pd_state_machine_config(self.port, config)
+                .await,
+        )
     }
 }
@@ -0,0 +1,38 @@
+//! Port enable port trait implementation
+use embedded_services::{event::NonBlockingSender, sync::Lockable};
+use embedded_usb_pd::PdError;
+use type_c_interface::controller::port_enable::PortEnable;
+
+use super::*;
+use crate::controller::state::SharedState;
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd + PortEnable>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> type_c_interface::port::port_enable::PortEnable
+    for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    async fn set_port_enabled(&mut self, enabled: bool) -> Result<(), PdError> {
+        info!("({}): Setting port enabled: {}", self.name, enabled);
+        self.track(self.controller.lock().await.set_port_enabled(self.port, enabled).await)?;
+        self.enabled = enabled;
+
+        if !enabled && self.status.is_connected() {
+            // The partner is electrically detached, report it the same way as an unplug so that power is
+            // released and listeners see the disconnect
+            let mut status_event = PortStatusEventBitfield::none();
+            status_event.set_plug_inserted_or_removed(true);
+            self.process_port_status(status_event, PortStatus::default()).await?;
+        }
+
+        Ok(())
+    }
+
+    fn is_port_enabled(&self) -> bool {
+        self.enabled
+    }
+}
@@ -1,17 +1,18 @@
 //! Module for power policy related functionality
 use embassy_time::{Duration, Instant};
-use embedded_services::{debug, error, event::NonBlockingSender, info, sync::Lockable};
+use embedded_services::{debug, error, event::NonBlockingSender, info, sync::Lockable, warn};
 use embedded_usb_pd::{
     PdError,
     constants::{T_PS_TRANSITION_EPR_MS, T_PS_TRANSITION_SPR_MS},
 };
 use power_policy_interface::{
-    capability::{ConsumerDisconnect, ConsumerPowerCapability, ProviderPowerCapability, PsuType},
+    capability::{ConsumerDisconnect, ConsumerPowerCapability, PowerCapability, ProviderPowerCapability, PsuType},
     psu::{Error as PsuError, Psu, State},
 };
+use type_c_interface::control::power::SystemPowerState;
 use type_c_interface::controller::power::SystemPowerStateStatus;
 
-use crate::controller::config::UnconstrainedSink;
+use crate::controller::config::{DeadBatteryClear, UnconstrainedSink};
 use type_c_interface::util::power_policy_error_from_pd_error;
 
 use super::*;
@@ -28,17 +29,20 @@ impl<
     /// Handle a new contract as consumer
     pub(super) async fn process_new_consumer_contract(&mut self, new_status: &PortStatus) -> Result<(), PdError> {
         info!("Process new consumer contract");
-        let available_sink_contract = new_status.available_sink_contract.map(|c| {
-            let mut c: ConsumerPowerCapability = c.into();
-            let unconstrained = match self.config.unconstrained_sink {
-                UnconstrainedSink::Auto => new_status.unconstrained_power,
-                UnconstrainedSink::PowerThresholdMilliwatts(threshold) => c.capability.max_power_mw() >= threshold,
-                UnconstrainedSink::Never => false,
-            };
-            c.flags.set_unconstrained_power(unconstrained);
-            c.flags.set_psu_type(PsuType::TypeC);
-            c
-        });
+        let available_sink_contract = new_status
+            .available_sink_contract
+            .filter(|_| self.pd_revision_allowed(new_status))
+            .map(|c| {
+                let mut c: ConsumerPowerCapability = c.into();
+                let unconstrained = match self.config.unconstrained_sink {
+                    UnconstrainedSink::Auto => new_status.unconstrained_power,
+                    UnconstrainedSink::PowerThresholdMilliwatts(threshold) => c.capability.max_power_mw() >= threshold,
+                    UnconstrainedSink::Never => false,
+                };
+                c.flags.set_unconstrained_power(unconstrained);
+                c.flags.set_psu_type(PsuType::TypeC);
+                c
+            });
 
         if let Err(e) = self.psu_state.update_consumer_power_capability(available_sink_contract) {
             error!("Failed to update consumer power capability: {:?}", e);
@@ -51,21 +55,81 @@ impl<
         {
             error!("Failed to send updated consumer capability event");
         }
+
+        if let Some(contract) = available_sink_contract {
+            self.stats.record_contract();
+            self.auto_clear_dead_battery_flag(contract.capability.max_power_mw())
+                .await;
+        }
         Ok(())
     }
 
+    /// Clear the dead battery flag if configured to do so and the consumer contract provides enough power
+    ///
+    /// The flag is cleared at most once per attach, later contracts with the same partner leave it alone. Failure to
+    /// clear the flag is logged but does not affect the contract itself, the clear is retried on the next contract.
+    async fn auto_clear_dead_battery_flag(&mut self, contract_power_mw: u32) {
+        let DeadBatteryClear::ConsumerPowerThresholdMilliwatts(threshold) = self.config.dead_battery_clear else {
+            return;
+        };
+
+        if self.dead_battery_cleared {
+            debug!("({}): Dead battery flag already cleared for this attach", self.name);
+            return;
+        }
+
+        if contract_power_mw < threshold {
+            debug!(
+                "({}): Consumer contract of {} mW below dead battery clear threshold of {} mW",
+                self.name, contract_power_mw, threshold
+            );
+            return;
+        }
+
+        info!(
+            "({}): Sufficient consumer contract, clearing dead battery flag",
+            self.name
+        );
+        match self.track(self.controller.lock().await.clear_dead_battery_flag(self.port).await) {
+            Ok(()) => self.dead_battery_cleared = true,
+            Err(e) => error!("({}): Failed to clear dead battery flag: {:?}", self.name, e),
+        }
+    }
+
+    /// Check the negotiated PD revision of a new contract against the configured minimum
+    ///
+    /// A refused contract is reported to the power policy as no contract at all.
+    fn pd_revision_allowed(&self, new_status: &PortStatus) -> bool {
+        match (self.config.min_pd_revision, new_status.pd_revision) {
+            (Some(min), Some(revision)) if revision < min => {
+                warn!(
+                    "({}): Refusing contract at PD {:?}, minimum is {:?}",
+                    self.name, revision, min
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+
     /// Handle a new contract as provider
     pub(super) async fn process_new_provider_contract(&mut self, new_status: &PortStatus) -> Result<(), PdError> {
         info!("Process New provider contract");
-        let capability = new_status.available_source_contract.map(|caps| {
-            let mut caps = ProviderPowerCapability::from(caps);
-            caps.flags.set_psu_type(PsuType::TypeC);
-            caps
-        });
+        let capability = new_status
+            .available_source_contract
+            .filter(|_| self.pd_revision_allowed(new_status))
+            .map(|caps| {
+                let mut caps = ProviderPowerCapability::from(caps);
+                caps.flags.set_psu_type(PsuType::TypeC);
+                caps
+            });
         if let Err(e) = self.psu_state.update_requested_provider_power_capability(capability) {
             error!("Failed to update requested provider power capability: {:?}", e);
             return Err(PdError::Failed);
         }
+        if capability.is_some() {
+            self.stats.record_contract();
+        }
         if self
             .power_policy_sender
             .try_send(power_policy_interface::psu::event::EventData::RequestedProviderCapability(capability))
@@ -102,7 +166,7 @@ impl<
 
         // Disable the sink path if we were consuming power so the source can safely stop.
         if was_consumer {
-            self.controller.lock().await.enable_sink_path(self.port, false).await?;
+            self.track(self.controller.lock().await.enable_sink_path(self.port, false).await)?;
         }
 
         // Reset our local state and notify the power policy so it stops tracking us in the previous
@@ -123,6 +187,35 @@ impl<
         Ok(())
     }
 
+    /// Shut down power paths before the system enters a low power state
+    ///
+    /// Disables the sink path to avoid back-powering the system and, if connected in a power role,
+    /// resets the local PSU state and notifies the power policy of the disconnect.
+    pub(super) async fn shutdown_power_paths(&mut self) -> Result<(), PdError> {
+        info!("({}): Shutting down power paths", self.name);
+        self.track(self.controller.lock().await.enable_sink_path(self.port, false).await)?;
+
+        if matches!(
+            self.psu_state.psu_state,
+            PsuState::ConnectedConsumer(_) | PsuState::ConnectedProvider(_)
+        ) {
+            if let Err(e) = self.psu_state.disconnect(true) {
+                error!("({}): Error updating PSU state on shutdown: {:?}", self.name, e);
+            }
+            if self
+                .power_policy_sender
+                .try_send(power_policy_interface::psu::event::EventData::Disconnected(
+                    ConsumerDisconnect::none(),
+                ))
+                .is_none()
+            {
+                error!("({}): Failed to notify power policy of shutdown disconnect", self.name);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check the sink ready timeout
     ///
     /// After accepting a sink contract (new contract as consumer), the PD spec guarantees that the
@@ -177,11 +270,7 @@ impl<
 > Psu for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn disconnect(&mut self) -> Result<(), PsuError> {
-        self.controller
-            .lock()
-            .await
-            .enable_sink_path(self.port, false)
-            .await
+        self.track(self.controller.lock().await.enable_sink_path(self.port, false).await)
             .map_err(|e| {
                 error!("({}): Error disabling sink path", self.name);
                 power_policy_error_from_pd_error(e)
@@ -191,6 +280,25 @@ impl<
 
     async fn connect_provider(&mut self, capability: ProviderPowerCapability) -> Result<(), PsuError> {
         info!("({}): Connect as provider: {:#?}", self.name, capability);
+        if let Some(default_capability) = self.config.default_source_capability {
+            // Power policy decides what the port provides, the default only limits it
+            let advertised = PowerCapability {
+                voltage_mv: capability.capability.voltage_mv.min(default_capability.voltage_mv),
+                current_ma: capability.capability.current_ma.min(default_capability.current_ma),
+            };
+            debug!("({}): Advertising source capability: {:?}", self.name, advertised);
+            self.track(
+                self.controller
+                    .lock()
+                    .await
+                    .set_source_capability(self.port, advertised)
+                    .await,
+            )
+            .map_err(|e| {
+                error!("({}): Error setting source capability", self.name);
+                power_policy_error_from_pd_error(e)
+            })?;
+        }
         // TODO: Implement controller over provider enablement
         self.psu_state.connect_provider(capability).inspect_err(|e| {
             error!("({}): Failed to transition to provider state: {:#?}", self.name, e);
@@ -202,11 +310,7 @@ impl<
             "({}): Connect as consumer: {:?}, enable input switch",
             self.name, capability
         );
-        self.controller
-            .lock()
-            .await
-            .enable_sink_path(self.port, true)
-            .await
+        self.track(self.controller.lock().await.enable_sink_path(self.port, true).await)
             .map_err(|e| {
                 error!("({}): Error enabling sink path", self.name);
                 power_policy_error_from_pd_error(e)
@@ -237,10 +341,17 @@ impl<
         &mut self,
         state: type_c_interface::control::power::SystemPowerState,
     ) -> Result<(), PdError> {
-        self.controller
-            .lock()
-            .await
-            .set_system_power_state_status(self.port, state)
-            .await
+        // Tear down power paths before the system stops managing them
+        if matches!(state, SystemPowerState::S4 | SystemPowerState::S5) {
+            self.shutdown_power_paths().await?;
+        }
+
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_system_power_state_status(self.port, state)
+                .await,
+        )
     }
 }
@@ -0,0 +1,41 @@
+//! Controller recovery after repeated command timeouts
+use embedded_services::{event::NonBlockingSender, sync::Lockable};
+use embedded_usb_pd::PdError;
+use type_c_interface::controller::Controller;
+
+use super::*;
+use crate::controller::state::SharedState;
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd + Controller>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    /// Reset the controller and resync the port if too many consecutive commands have timed out
+    ///
+    /// Called by [`Self::process_event`] after each event. Returns true if recovery was attempted. The timeout count is
+    /// cleared before recovery, so a failed recovery isn't retried until the threshold is reached again.
+    pub async fn recover_if_needed(&mut self) -> Result<bool, PdError> {
+        if !self.recovery_needed() {
+            return Ok(false);
+        }
+
+        info!(
+            "({}): {} consecutive timeouts, resetting controller",
+            self.name,
+            self.consecutive_timeouts.get()
+        );
+        self.consecutive_timeouts.set(0);
+        if let Err(e) = self.controller.lock().await.reset_controller().await {
+            error!("({}): Controller reset failed: {:?}", self.name, e);
+            return Err(e);
+        }
+
+        self.sync_state().await?;
+        Ok(true)
+    }
+}
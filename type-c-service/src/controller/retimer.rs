@@ -1,10 +1,11 @@
-//! Retimer port trait implementation
+//! Retimer port trait implementation and controller-wide retimer FW update sequencing
 use embedded_services::{event::NonBlockingSender, sync::Lockable};
-use embedded_usb_pd::PdError;
+use embedded_usb_pd::{LocalPortId, PdError};
 use type_c_interface::control::retimer::RetimerFwUpdateState;
 use type_c_interface::controller::retimer::Retimer;
 
 use super::*;
+use crate::controller::config::Features;
 use crate::controller::state::SharedState;
 
 impl<
@@ -17,22 +18,84 @@ impl<
 > type_c_interface::port::retimer::Retimer for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn get_rt_fw_update_status(&mut self) -> Result<RetimerFwUpdateState, PdError> {
-        self.controller.lock().await.get_rt_fw_update_status(self.port).await
+        self.require_features(Features::RETIMER)?;
+        self.track(self.controller.lock().await.get_rt_fw_update_status(self.port).await)
     }
 
     async fn set_rt_fw_update_state(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.set_rt_fw_update_state(self.port).await
+        self.require_features(Features::RETIMER)?;
+        self.track(self.controller.lock().await.set_rt_fw_update_state(self.port).await)
     }
 
     async fn clear_rt_fw_update_state(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.clear_rt_fw_update_state(self.port).await
+        self.require_features(Features::RETIMER)?;
+        self.track(self.controller.lock().await.clear_rt_fw_update_state(self.port).await)
     }
 
     async fn set_rt_compliance(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.set_rt_compliance(self.port).await
+        self.require_features(Features::RETIMER)?;
+        self.track(self.controller.lock().await.set_rt_compliance(self.port).await)
     }
 
     async fn reconfigure_retimer(&mut self) -> Result<(), PdError> {
-        self.controller.lock().await.reconfigure_retimer(self.port).await
+        self.require_features(Features::RETIMER)?;
+        self.track(self.controller.lock().await.reconfigure_retimer(self.port).await)
+    }
+}
+
+/// Transition every port in `order` on `controller` into or out of retimer FW update mode as a single operation
+///
+/// Ports enter update mode in the given order and leave it in the reverse order. The controller stays locked for
+/// the whole sequence so no other command can observe a partially transitioned controller.
+///
+/// If a port fails to enter update mode, the ports that had already entered are returned to normal operation in
+/// reverse order and the original error is returned. Leaving update mode attempts every port and returns the first
+/// error encountered.
+pub async fn set_controller_rt_fw_update_state<C: Lockable<Inner: Retimer>>(
+    controller: &C,
+    order: &[LocalPortId],
+    state: RetimerFwUpdateState,
+) -> Result<(), PdError> {
+    let mut controller = controller.lock().await;
+    match state {
+        RetimerFwUpdateState::Active => {
+            for (i, port) in order.iter().enumerate() {
+                if let Err(e) = controller.set_rt_fw_update_state(*port).await {
+                    error!(
+                        "({}): Failed to enter retimer FW update on port {}: {:?}",
+                        controller.name(),
+                        port.0,
+                        e
+                    );
+                    for entered in order.iter().take(i).rev() {
+                        if let Err(e) = controller.clear_rt_fw_update_state(*entered).await {
+                            error!(
+                                "({}): Failed to restore port {} after retimer FW update failure: {:?}",
+                                controller.name(),
+                                entered.0,
+                                e
+                            );
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+            Ok(())
+        }
+        RetimerFwUpdateState::Inactive => {
+            let mut result = Ok(());
+            for port in order.iter().rev() {
+                if let Err(e) = controller.clear_rt_fw_update_state(*port).await {
+                    error!(
+                        "({}): Failed to exit retimer FW update on port {}: {:?}",
+                        controller.name(),
+                        port.0,
+                        e
+                    );
+                    result = result.and(Err(e));
+                }
+            }
+            result
+        }
     }
 }
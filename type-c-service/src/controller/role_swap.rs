@@ -0,0 +1,38 @@
+//! Role swap port trait implementation
+use embedded_services::{event::NonBlockingSender, sync::Lockable};
+use embedded_usb_pd::{PdError, PowerRole};
+use type_c_interface::controller::role_swap::RoleSwap;
+
+use super::*;
+use crate::controller::state::SharedState;
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd + RoleSwap>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> type_c_interface::port::role_swap::RoleSwap for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    async fn request_power_role_swap(&mut self, role: PowerRole) -> Result<(), PdError> {
+        if !self.status.is_connected() {
+            error!("({}): Cannot swap power role, port not connected", self.name);
+            return Err(PdError::InvalidMode);
+        }
+
+        if self.status.power_role == role {
+            debug!("({}): Already in power role {:?}, nothing to do", self.name, role);
+            return Ok(());
+        }
+
+        info!("({}): Requesting power role swap to {:?}", self.name, role);
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .request_power_role_swap(self.port, role)
+                .await,
+        )
+    }
+}
@@ -0,0 +1,38 @@
+//! Source PDP port trait implementation
+use embedded_services::{event::NonBlockingSender, sync::Lockable};
+use embedded_usb_pd::PdError;
+use type_c_interface::controller::source_pdp::SourcePdp;
+
+use super::*;
+use crate::controller::state::SharedState;
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd + SourcePdp>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> type_c_interface::port::source_pdp::SourcePdp for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    async fn get_source_pdp(&mut self) -> Result<u32, PdError> {
+        self.track(self.controller.lock().await.get_source_pdp(self.port).await)
+    }
+
+    async fn set_source_pdp(&mut self, pdp_mw: u32) -> Result<(), PdError> {
+        // An existing source contract was negotiated against the previous budget, so it must be renegotiated for the
+        // sink to see the new capabilities. Without a contract the new budget is picked up on the next attach.
+        let renegotiate = self.status.available_source_contract.is_some();
+        debug!(
+            "({}): Setting source PDP to {} mW, renegotiate: {}",
+            self.name, pdp_mw, renegotiate
+        );
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_source_pdp(self.port, pdp_mw, renegotiate)
+                .await,
+        )
+    }
+}
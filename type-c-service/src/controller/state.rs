@@ -1,4 +1,4 @@
-use embassy_time::Instant;
+use embassy_time::{Duration, Instant};
 
 /// State shared between the port and event receiver
 #[derive(Copy, Clone)]
@@ -19,6 +19,19 @@ impl SharedState {
     pub fn sink_ready_timeout(&self) -> Option<Instant> {
         self.sink_ready_timeout
     }
+
+    /// Get the time remaining until the pending sink ready timeout expires
+    ///
+    /// Returns zero if the deadline has passed but the timeout hasn't been processed yet.
+    pub fn sink_ready_timeout_remaining(&self) -> Option<Duration> {
+        self.sink_ready_timeout
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Clear the pending sink ready timeout, returns true if one was pending
+    pub fn clear_sink_ready_timeout(&mut self) -> bool {
+        self.sink_ready_timeout.take().is_some()
+    }
 }
 
 impl Default for SharedState {
@@ -0,0 +1,38 @@
+//! Per-port reliability statistics
+
+/// Counters of notable port events since the port was created or the counters were last reset
+///
+/// Counters saturate rather than wrap.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PortStats {
+    /// Number of times a partner has attached
+    pub attach_count: u32,
+    /// Number of power contracts negotiated, as either consumer or provider
+    pub contract_count: u32,
+    /// Number of faults reported by PD alerts
+    pub fault_count: u32,
+}
+
+impl PortStats {
+    /// Create a new instance with all counters at zero
+    pub const fn new() -> Self {
+        Self {
+            attach_count: 0,
+            contract_count: 0,
+            fault_count: 0,
+        }
+    }
+
+    pub(crate) fn record_attach(&mut self) {
+        self.attach_count = self.attach_count.saturating_add(1);
+    }
+
+    pub(crate) fn record_contract(&mut self) {
+        self.contract_count = self.contract_count.saturating_add(1);
+    }
+
+    pub(crate) fn record_fault(&mut self) {
+        self.fault_count = self.fault_count.saturating_add(1);
+    }
+}
@@ -0,0 +1,26 @@
+//! Controller die temperature
+use embedded_services::{event::NonBlockingSender, sync::Lockable};
+use embedded_usb_pd::PdError;
+use type_c_interface::controller::Controller;
+
+use super::*;
+use crate::controller::state::SharedState;
+
+impl<
+    'device,
+    C: Lockable<Inner: Pd + Controller>,
+    Shared: Lockable<Inner = SharedState>,
+    TypeCSender: NonBlockingSender<type_c_interface::service::event::PortEventData>,
+    PowerSender: NonBlockingSender<power_policy_interface::psu::event::EventData>,
+    LoopbackSender: NonBlockingSender<event::Loopback>,
+> Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
+{
+    /// Read the die temperature of the controller this port belongs to, in degrees Celsius
+    ///
+    /// Every port on a controller reports the same temperature, e.g. for a thermal sensor driver to sample.
+    pub async fn get_die_temperature(&self) -> Result<i16, PdError> {
+        let temperature = self.track(self.controller.lock().await.get_die_temperature().await)?;
+        debug!("({}): Controller die temperature: {} C", self.name, temperature);
+        Ok(temperature)
+    }
+}
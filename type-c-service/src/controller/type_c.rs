@@ -17,10 +17,12 @@ impl<
 > type_c_interface::port::type_c::StateMachine for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn set_type_c_state_machine_config(&mut self, state: TypeCStateMachineState) -> Result<(), PdError> {
-        self.controller
-            .lock()
-            .await
-            .set_type_c_state_machine_config(self.port, state)
-            .await
+        self.track(
+            self.controller
+                .lock()
+                .await
+                .set_type_c_state_machine_config(self.port, state)
+                .await,
+        )
     }
 }
@@ -16,6 +16,6 @@ impl<
 > type_c_interface::ucsi::Lpm for Port<'device, C, Shared, TypeCSender, PowerSender, LoopbackSender>
 {
     async fn execute_lpm_command(&mut self, command: lpm::LocalCommand) -> Result<Option<lpm::ResponseData>, PdError> {
-        self.controller.lock().await.execute_lpm_command(command).await
+        self.track(self.controller.lock().await.execute_lpm_command(command).await)
     }
 }
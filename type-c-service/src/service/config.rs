@@ -108,6 +108,52 @@ pub struct Config {
     pub ucsi_port_capabilities: Option<ucsi::lpm::get_connector_capability::ResponseData>,
     /// UCSI battery charging configuration
     pub ucsi_battery_charging_config: UcsiBatteryChargingThresholdConfig,
+    /// Sync every registered port with its controller when the service task starts
+    ///
+    /// Without this, cached port statuses are only populated as events arrive.
+    pub startup_sync: bool,
+    /// Reset a connector after this many consecutive invalid UCSI state machine transitions
+    ///
+    /// The reset targets the connector addressed by the command that reached the threshold. If `None`, invalid
+    /// transitions are only reported to the OPM as errors.
+    ///
+    /// The connector is reset with a USB PD Hard Reset, which returns VBUS to vSafe0V before renegotiating. A device
+    /// powered only from the connector loses power, and a system running from that connector's sink path must have
+    /// another source to survive the reset.
+    pub ucsi_invalid_transition_reset_threshold: Option<core::num::NonZeroU8>,
+}
+
+/// Type-c service configuration errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigError {
+    /// The UCSI capabilities advertise a different number of connectors than there are registered ports
+    ConnectorCountMismatch {
+        /// Number of connectors in [`Config::ucsi_capabilities`]
+        configured: u8,
+        /// Number of ports registered with the service
+        registered: usize,
+    },
+    /// The same port is registered under more than one global port ID, contains the later ID
+    DuplicatePort(embedded_usb_pd::GlobalPortId),
+}
+
+impl Config {
+    /// Check that the configuration is consistent with a service managing `num_ports` ports
+    ///
+    /// A `num_connectors` of zero in [`ucsi_capabilities`][`Self::ucsi_capabilities`] is treated as unspecified, since
+    /// the service always reports the number of registered ports to the OPM.
+    pub fn validate(&self, num_ports: usize) -> Result<(), ConfigError> {
+        let configured = self.ucsi_capabilities.num_connectors;
+        if configured != 0 && configured as usize != num_ports {
+            return Err(ConfigError::ConnectorCountMismatch {
+                configured,
+                registered: num_ports,
+            });
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -115,6 +161,45 @@ pub struct Config {
 mod tests {
     use super::*;
 
+    mod validate {
+        //! Tests for [`Config::validate`]
+
+        use super::*;
+
+        #[test]
+        fn default_is_valid() {
+            assert_eq!(Config::default().validate(0), Ok(()));
+            assert_eq!(Config::default().validate(2), Ok(()));
+        }
+
+        #[test]
+        fn matching_connector_count() {
+            let mut config = Config::default();
+            config.ucsi_capabilities.num_connectors = 2;
+            assert_eq!(config.validate(2), Ok(()));
+        }
+
+        #[test]
+        fn mismatched_connector_count() {
+            let mut config = Config::default();
+            config.ucsi_capabilities.num_connectors = 3;
+            assert_eq!(
+                config.validate(2),
+                Err(ConfigError::ConnectorCountMismatch {
+                    configured: 3,
+                    registered: 2,
+                })
+            );
+            assert_eq!(
+                config.validate(4),
+                Err(ConfigError::ConnectorCountMismatch {
+                    configured: 3,
+                    registered: 4,
+                })
+            );
+        }
+    }
+
     mod ucsi_battery_charging_threshold_config {
         //! Tests for [`UcsiBatteryChargingThresholdConfig`]
 
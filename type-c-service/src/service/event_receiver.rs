@@ -1,19 +1,113 @@
 use core::pin::pin;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 use crate::service::Event;
-use embassy_futures::select::{Either, select, select_slice};
-use embedded_services::{event::Receiver, sync::Lockable};
+use embassy_futures::select::{Either, Either3, select, select_slice, select3};
+use embassy_sync::pubsub::DynImmediatePublisher;
+use embassy_sync::signal::Signal;
+use embedded_services::event::{ImmediateEvent, Receiver};
+use embedded_services::{GlobalRawMutex, sync::Lockable};
 use power_policy_interface::service::event::EventData as PowerPolicyEventData;
+use power_policy_interface::service::{UnconstrainedState, UnconstrainedStateSource};
 use type_c_interface::{port::pd::Pd, service::event::PortEvent};
 
-struct PowerPolicySubscriber<PowerReceiver: Receiver<PowerPolicyEventData>> {
-    receiver: PowerReceiver,
+/// Event returned by a [`PowerPolicyReceiver`]
+#[derive(Clone)]
+pub enum PowerPolicyReceiverEvent {
+    /// Power policy event
+    Event(PowerPolicyEventData),
+    /// Power policy events were dropped before being received
+    Lagged {
+        /// Number of missed events
+        missed: u64,
+        /// Power policy unconstrained state, read after the events were dropped
+        state: UnconstrainedState,
+    },
 }
 
-impl<PowerReceiver: Receiver<PowerPolicyEventData>> PowerPolicySubscriber<PowerReceiver> {
-    /// Wait for a power policy event
-    async fn wait_next(&mut self) -> PowerPolicyEventData {
-        self.receiver.wait_next().await
+/// Source of power policy events for an [`ArrayEventReceiver`]
+///
+/// Implemented for every [`Receiver`] of power policy events, these never report missed events. Wrap a receiver that
+/// can fall behind, such as a `DynSubscriber`, in a [`LagDetectingReceiver`] so the service can resync after events
+/// are dropped.
+pub trait PowerPolicyReceiver {
+    /// Wait for the next power policy event, or for the power policy's current state after events were missed
+    ///
+    /// Implementations must be cancel-safe, see [`Receiver::wait_next`].
+    fn wait_event(&mut self) -> impl Future<Output = PowerPolicyReceiverEvent>;
+}
+
+impl<R: Receiver<PowerPolicyEventData>> PowerPolicyReceiver for R {
+    async fn wait_event(&mut self) -> PowerPolicyReceiverEvent {
+        PowerPolicyReceiverEvent::Event(Receiver::wait_next(self).await)
+    }
+}
+
+/// Power policy event receiver that reports dropped events instead of skipping them
+///
+/// When events are dropped the power policy's current unconstrained state is read so the service can resync, see
+/// [`Event::PowerPolicyLagged`].
+pub struct LagDetectingReceiver<
+    'a,
+    R: Receiver<ImmediateEvent<PowerPolicyEventData>>,
+    PowerPolicy: Lockable<Inner: UnconstrainedStateSource>,
+> {
+    receiver: R,
+    power_policy: &'a PowerPolicy,
+    /// Number of missed events not yet reported, kept so dropping [`PowerPolicyReceiver::wait_event`] doesn't lose it
+    pending_lag: Option<u64>,
+}
+
+impl<'a, R: Receiver<ImmediateEvent<PowerPolicyEventData>>, PowerPolicy: Lockable<Inner: UnconstrainedStateSource>>
+    LagDetectingReceiver<'a, R, PowerPolicy>
+{
+    /// Create a new instance, reading the current state from `power_policy` after events are dropped
+    pub fn new(receiver: R, power_policy: &'a PowerPolicy) -> Self {
+        Self {
+            receiver,
+            power_policy,
+            pending_lag: None,
+        }
+    }
+}
+
+impl<R: Receiver<ImmediateEvent<PowerPolicyEventData>>, PowerPolicy: Lockable<Inner: UnconstrainedStateSource>>
+    PowerPolicyReceiver for LagDetectingReceiver<'_, R, PowerPolicy>
+{
+    async fn wait_event(&mut self) -> PowerPolicyReceiverEvent {
+        let missed = match self.pending_lag {
+            Some(missed) => missed,
+            None => match self.receiver.wait_next().await {
+                ImmediateEvent::Event(event) => return PowerPolicyReceiverEvent::Event(event),
+                ImmediateEvent::Lagged(missed) => {
+                    self.pending_lag = Some(missed);
+                    missed
+                }
+            },
+        };
+
+        let state = self.power_policy.lock().await.unconstrained_state();
+        self.pending_lag = None;
+        PowerPolicyReceiverEvent::Lagged { missed, state }
+    }
+}
+
+struct PowerPolicySubscriber<PowerReceiver: PowerPolicyReceiver> {
+    /// Power policy event receiver, `None` while deregistered
+    receiver: Option<PowerReceiver>,
+}
+
+impl<PowerReceiver: PowerPolicyReceiver> PowerPolicySubscriber<PowerReceiver> {
+    /// Wait for a power policy event, never completes while no receiver is registered
+    async fn wait_next<'port, Port: Lockable<Inner: Pd>>(&mut self) -> Event<'port, Port> {
+        let Some(receiver) = &mut self.receiver else {
+            return core::future::pending().await;
+        };
+
+        match receiver.wait_event().await {
+            PowerPolicyReceiverEvent::Event(event) => Event::PowerPolicy(event),
+            PowerPolicyReceiverEvent::Lagged { missed, state } => Event::PowerPolicyLagged { missed, state },
+        }
     }
 }
 
@@ -34,7 +128,25 @@ impl<
     PortReceiver: Receiver<type_c_interface::service::event::PortEventData>,
 > ArrayPortReceivers<'port, N, Port, PortReceiver>
 {
+    /// Reorder ports so that higher priority ports come first, ports of equal priority keep their relative order
+    fn sort_by_priority(&mut self, mut priorities: [u8; N]) {
+        // Insertion sort since it's stable and N is small
+        for i in 1..N {
+            let mut j = i;
+            while j > 0 && priorities.get(j - 1) < priorities.get(j) {
+                priorities.swap(j - 1, j);
+                self.ports.swap(j - 1, j);
+                self.port_receivers.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
     /// Get the next pending PSU event
+    ///
+    /// When several ports have pending events, the event from the port that comes first is returned.
+    ///
+    /// This future is safe to drop, each per-port future completes as soon as its receiver yields an event.
     pub async fn wait_next(&mut self) -> Event<'port, Port> {
         let ((event, port), _) = {
             let mut futures = heapless::Vec::<_, N>::new();
@@ -52,18 +164,107 @@ impl<
     }
 }
 
+/// Pauses and resumes consumption of port events by an [`ArrayEventReceiver`]
+///
+/// While paused, port events stay queued in their per-port receivers and are returned in order once
+/// processing resumes. Events beyond the receivers' capacity are dropped by the sender, so pauses should be
+/// kept short. Power policy events and commands issued directly to ports are unaffected.
+pub struct PortEventGate {
+    /// Whether port event processing is paused
+    paused: AtomicBool,
+    /// Signaled whenever the paused state changes
+    changed: Signal<GlobalRawMutex, ()>,
+}
+
+impl PortEventGate {
+    /// Create a new instance, port events are initially processed
+    pub const fn new() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            changed: Signal::new(),
+        }
+    }
+
+    /// Stop consuming port events, pending and new events are buffered until [`Self::resume_events`]
+    pub fn pause_events(&self) {
+        self.paused.store(true, Ordering::Release);
+        self.changed.signal(());
+    }
+
+    /// Resume consuming port events, starting with any that were buffered while paused
+    pub fn resume_events(&self) {
+        self.paused.store(false, Ordering::Release);
+        self.changed.signal(());
+    }
+
+    /// Returns true if port event processing is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+}
+
+impl Default for PortEventGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lets an [`ArrayEventReceiver`] stop waiting for events as soon as the service is shut down
+///
+/// Give the same signal to the service, see [`crate::service::Service::with_shutdown_signal`], which sets and clears
+/// it on shutdown and reinitialization.
+pub struct ShutdownSignal {
+    /// Whether the service is shut down
+    shut_down: AtomicBool,
+    /// Signaled whenever the shut down state changes
+    changed: Signal<GlobalRawMutex, ()>,
+}
+
+impl ShutdownSignal {
+    /// Create a new instance, the service is initially running
+    pub const fn new() -> Self {
+        Self {
+            shut_down: AtomicBool::new(false),
+            changed: Signal::new(),
+        }
+    }
+
+    /// Record whether the service is shut down
+    pub(crate) fn set_shut_down(&self, shut_down: bool) {
+        self.shut_down.store(shut_down, Ordering::Release);
+        self.changed.signal(());
+    }
+
+    /// Returns true if the service is shut down
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down.load(Ordering::Acquire)
+    }
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Struct used to contain port event receivers and manage mapping from a receiver to its corresponding device.
 pub struct ArrayEventReceiver<
     'a,
     const N: usize,
     Port: Lockable<Inner: Pd>,
     PortReceiver: Receiver<type_c_interface::service::event::PortEventData>,
-    PowerReceiver: Receiver<PowerPolicyEventData>,
+    PowerReceiver: PowerPolicyReceiver,
 > {
     /// Power policy event subscriber
     power_policy_event_subscriber: PowerPolicySubscriber<PowerReceiver>,
     /// Port event receivers and corresponding ports
     port_receivers: ArrayPortReceivers<'a, N, Port, PortReceiver>,
+    /// Optional gate used to pause port event processing
+    gate: Option<&'a PortEventGate>,
+    /// Optional signal used to stop waiting for events once the service is shut down
+    shutdown: Option<&'a ShutdownSignal>,
+    /// Optional publisher used to share the event stream with read-only observers
+    observer: Option<DynImmediatePublisher<'a, Event<'a, Port>>>,
 }
 
 impl<
@@ -71,7 +272,7 @@ impl<
     const N: usize,
     Port: Lockable<Inner: Pd>,
     PortReceiver: Receiver<type_c_interface::service::event::PortEventData>,
-    PowerReceiver: Receiver<PowerPolicyEventData>,
+    PowerReceiver: PowerPolicyReceiver,
 > ArrayEventReceiver<'port, N, Port, PortReceiver, PowerReceiver>
 {
     /// Create a new instance
@@ -83,21 +284,135 @@ impl<
         Self {
             port_receivers: ArrayPortReceivers { ports, port_receivers },
             power_policy_event_subscriber: PowerPolicySubscriber {
-                receiver: power_policy_event_receiver,
+                receiver: Some(power_policy_event_receiver),
             },
+            gate: None,
+            shutdown: None,
+            observer: None,
+        }
+    }
+
+    /// Allow port event processing to be paused and resumed through the given gate
+    pub fn with_gate(mut self, gate: &'port PortEventGate) -> Self {
+        self.gate = Some(gate);
+        self
+    }
+
+    /// Stop waiting for events in [`Self::wait_next_or_shutdown`] once the given signal reports a shutdown
+    pub fn with_shutdown_signal(mut self, signal: &'port ShutdownSignal) -> Self {
+        self.shutdown = Some(signal);
+        self
+    }
+
+    /// Service ports in order of the given priorities, one per port in the same order as the ports
+    ///
+    /// When events are pending on several ports, the event from the port with the highest priority is returned first.
+    /// Ports of equal priority are serviced in the order they were given. By default all ports have the same priority.
+    /// A high priority port that continuously generates events will starve lower priority ports.
+    pub fn with_port_priorities(mut self, priorities: [u8; N]) -> Self {
+        self.port_receivers.sort_by_priority(priorities);
+        self
+    }
+
+    /// Publish a copy of every event returned by [`Self::wait_next`] through the given publisher
+    ///
+    /// Any number of read-only observers, such as a logging task, can subscribe to the underlying channel. Events are
+    /// published immediately, so an observer that falls behind misses events rather than stalling the service.
+    pub fn with_observer(mut self, observer: DynImmediatePublisher<'port, Event<'port, Port>>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Replace the power policy event receiver, returning the previous one if any
+    ///
+    /// Power policy events are only delivered through the receiver given at construction. If the power policy
+    /// service is reset, or the sender side is otherwise recreated, call this with a receiver for the new sender to
+    /// restore power policy event flow. Events still queued in the previous receiver aren't processed.
+    pub fn reregister_power_policy(&mut self, receiver: PowerReceiver) -> Option<PowerReceiver> {
+        self.power_policy_event_subscriber.receiver.replace(receiver)
+    }
+
+    /// Remove the power policy event receiver, returning it if one was registered
+    ///
+    /// No power policy events are received until [`Self::reregister_power_policy`] is called. Dropping the returned
+    /// receiver releases it from its sender, e.g. unsubscribing from a pub/sub channel.
+    pub fn deregister_power_policy(&mut self) -> Option<PowerReceiver> {
+        self.power_policy_event_subscriber.receiver.take()
+    }
+
+    /// Wait until the paused state of the gate changes, never completes if there's no gate
+    async fn wait_gate_changed(gate: Option<&PortEventGate>) {
+        match gate {
+            Some(gate) => gate.changed.wait().await,
+            None => core::future::pending().await,
         }
     }
 
     /// Wait for the next event, whether it's a port event or a power policy event
+    ///
+    /// If the power policy receiver reports dropped events, [`Event::PowerPolicyLagged`] is returned with the power
+    /// policy's current state so the service can resync. Port and power policy events that arrive together are never lost. The event that isn't selected stays
+    /// queued in its receiver and is returned by a later call. This future is also safe to drop, so callers
+    /// may poll it inside their own `select`.
+    ///
+    /// While the [`PortEventGate`] is paused only power policy events are returned.
     pub async fn wait_next(&mut self) -> Event<'port, Port> {
-        match select(
-            self.port_receivers.wait_next(),
-            self.power_policy_event_subscriber.wait_next(),
-        )
-        .await
-        {
-            Either::First(event) => event,
-            Either::Second(event) => Event::PowerPolicy(event),
+        let event = self.wait_next_event().await;
+        if let Some(observer) = &self.observer {
+            observer.publish_immediate(event.clone());
+        }
+        event
+    }
+
+    /// Wait for the next event like [`Self::wait_next`], or until the service is shut down
+    ///
+    /// Returns `None` as soon as the [`ShutdownSignal`] reports a shutdown, without taking an event from any receiver.
+    /// Without a signal this never returns `None`.
+    pub async fn wait_next_or_shutdown(&mut self) -> Option<Event<'port, Port>> {
+        let Some(shutdown) = self.shutdown else {
+            return Some(self.wait_next().await);
+        };
+
+        loop {
+            if shutdown.is_shut_down() {
+                return None;
+            }
+
+            // Dropping `wait_next` can't lose an event, see its docs
+            match select(shutdown.changed.wait(), self.wait_next()).await {
+                // Re-check the shut down state
+                Either::First(()) => continue,
+                Either::Second(event) => return Some(event),
+            }
+        }
+    }
+
+    async fn wait_next_event(&mut self) -> Event<'port, Port> {
+        loop {
+            if self.gate.is_some_and(PortEventGate::is_paused) {
+                match select(
+                    Self::wait_gate_changed(self.gate),
+                    self.power_policy_event_subscriber.wait_next(),
+                )
+                .await
+                {
+                    Either::First(()) => continue,
+                    Either::Second(event) => return event,
+                }
+            }
+
+            match select3(
+                self.port_receivers.wait_next(),
+                self.power_policy_event_subscriber.wait_next(),
+                Self::wait_gate_changed(self.gate),
+            )
+            .await
+            {
+                Either3::First(event) => return event,
+                Either3::Second(event) => return event,
+                // Re-check the paused state, port events not yet received stay queued
+                Either3::Third(()) => continue,
+            }
         }
     }
 }
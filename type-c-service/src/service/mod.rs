@@ -4,17 +4,19 @@ use core::ptr;
 use embedded_services::event::NonBlockingSender as _;
 use embedded_services::named::Named as _;
 use embedded_services::sync::Lockable;
-use embedded_services::{debug, error, info, trace};
+use embedded_services::{debug, error, info, trace, warn};
 use embedded_usb_pd::GlobalPortId;
 use embedded_usb_pd::PdError as Error;
+use power_policy_interface::service::UnconstrainedState;
 use power_policy_interface::service::event::EventData as PowerPolicyEventData;
 use type_c_interface::control::pd::PortStatus;
 use type_c_interface::port::pd::Pd;
-use type_c_interface::service::event::{DebugAccessoryData, EventData, PortEvent, PortEventData};
+use type_c_interface::service::event::{DebugAccessoryData, EventData, PortEvent, PortEventData, PortFault};
 
 use type_c_interface::port::event::PortStatusEventBitfield;
 use type_c_interface::service::event::Event as ServiceEvent;
 
+use crate::service::event_receiver::ShutdownSignal;
 use crate::service::registration::Registration;
 
 pub mod config;
@@ -23,6 +25,11 @@ mod power;
 pub mod registration;
 mod ucsi;
 
+pub use ucsi::UcsiSpecVersions;
+
+/// Maximum number of ports supported by the service
+pub const MAX_SUPPORTED_PORTS: usize = 4;
+
 /// Type-C service
 ///
 /// Constructing a Service is the first step in using the Type-C service.
@@ -34,27 +41,150 @@ pub struct Service<'port, Reg: Registration<'port>> {
     config: config::Config,
     /// Service registration
     registration: Reg,
+    /// Whether the service has been shut down, events are dropped while set
+    shut_down: bool,
+    /// Optional signal used to stop the event loop as soon as the service is shut down
+    shutdown_signal: Option<&'port ShutdownSignal>,
     _phantom: PhantomData<&'port ()>,
 }
 
+/// Type-C service errors
+///
+/// Returned from the externally facing service API so callers can tell service-level failures apart from errors
+/// reported by a port or its controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ServiceError {
+    /// No port is registered under the given global port ID
+    InvalidPort(GlobalPortId),
+    /// The port is registered but has no local port ID on its controller
+    PortNotMapped(GlobalPortId),
+    /// More ports are registered than [`MAX_SUPPORTED_PORTS`]
+    TooManyPorts,
+    /// The PPM state machine rejected the UCSI command in its current state
+    InvalidUcsiTransition,
+    /// The port or its controller reported an error
+    Pd(Error),
+}
+
+impl From<Error> for ServiceError {
+    fn from(error: Error) -> Self {
+        ServiceError::Pd(error)
+    }
+}
+
 /// Type-C service events
-#[derive(Clone)]
 pub enum Event<'port, Port: Lockable<Inner: Pd>> {
     /// Port event
     PortEvent(PortEvent<'port, Port>),
     /// Power policy event
     PowerPolicy(PowerPolicyEventData),
+    /// Power policy events were dropped before being received
+    ///
+    /// The service resyncs every port with the given state, as if it had received it in
+    /// [`PowerPolicyEventData::Unconstrained`].
+    PowerPolicyLagged {
+        /// Number of missed events
+        missed: u64,
+        /// Power policy unconstrained state, read after the events were dropped
+        state: UnconstrainedState,
+    },
+}
+
+// Manual impl so cloning doesn't require `Port: Clone`
+impl<'port, Port: Lockable<Inner: Pd>> Clone for Event<'port, Port> {
+    fn clone(&self) -> Self {
+        match self {
+            Event::PortEvent(event) => Event::PortEvent(*event),
+            Event::PowerPolicy(event) => Event::PowerPolicy(event.clone()),
+            Event::PowerPolicyLagged { missed, state } => Event::PowerPolicyLagged {
+                missed: *missed,
+                state: *state,
+            },
+        }
+    }
 }
 
 impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
     /// Create a new service the given configuration
-    pub fn new(config: config::Config, registration: Reg) -> Self {
-        Self {
+    ///
+    /// Returns an error if the configuration is inconsistent with the registered ports, or if a port is registered
+    /// more than once.
+    pub fn new(config: config::Config, registration: Reg) -> Result<Self, config::ConfigError> {
+        Self::validate_registration(&registration).inspect_err(|e| {
+            error!("Invalid type-C service registration: {:?}", e);
+        })?;
+        config.validate(registration.ports().len()).inspect_err(|e| {
+            error!("Invalid type-C service config: {:?}", e);
+        })?;
+
+        Ok(Self {
             ucsi: ucsi::State::default(),
             config,
             registration,
+            shut_down: false,
+            shutdown_signal: None,
             _phantom: PhantomData,
+        })
+    }
+
+    /// Report shutdown and reinitialization through the given signal
+    ///
+    /// Give the same signal to the service's [`event_receiver::ArrayEventReceiver`] so [`crate::task::task`] stops as
+    /// soon as the service is shut down.
+    pub fn with_shutdown_signal(mut self, signal: &'port ShutdownSignal) -> Self {
+        self.shutdown_signal = Some(signal);
+        self
+    }
+
+    /// Stop processing events, for example to reconfigure the service
+    ///
+    /// Events given to [`Self::process_event`] afterwards are dropped. UCSI state is cleared. Call [`Self::reinit`] to
+    /// start processing events again.
+    ///
+    /// [`crate::task::task`] returns once the service is shut down so its receivers can be dropped or reused. With a
+    /// [`ShutdownSignal`] it returns immediately, otherwise it only notices after the next event, which is dropped.
+    pub fn shutdown(&mut self) {
+        info!("Shutting down type-C service");
+        self.shut_down = true;
+        self.ucsi = ucsi::State::default();
+        if let Some(signal) = self.shutdown_signal {
+            signal.set_shut_down(true);
+        }
+    }
+
+    /// Returns true if the service has been shut down and not yet reinitialized
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down
+    }
+
+    /// Apply a new configuration and resume processing events after [`Self::shutdown`]
+    ///
+    /// The service stays shut down if the configuration is invalid.
+    pub fn reinit(&mut self, config: config::Config) -> Result<(), config::ConfigError> {
+        config.validate(self.registration.ports().len()).inspect_err(|e| {
+            error!("Invalid type-C service config: {:?}", e);
+        })?;
+
+        info!("Reinitializing type-C service");
+        self.config = config;
+        self.ucsi = ucsi::State::default();
+        self.shut_down = false;
+        if let Some(signal) = self.shutdown_signal {
+            signal.set_shut_down(false);
+        }
+        Ok(())
+    }
+
+    /// Check that every global port ID refers to a distinct port, otherwise port lookups would be ambiguous
+    fn validate_registration(registration: &Reg) -> Result<(), config::ConfigError> {
+        let ports = registration.ports();
+        for (i, port) in ports.iter().enumerate() {
+            if ports.iter().take(i).any(|p| ptr::eq(*p, *port)) {
+                return Err(config::ConfigError::DuplicatePort(GlobalPortId(i as u8)));
+            }
         }
+        Ok(())
     }
 
     fn get_port_index(&self, port: &'port Reg::Port) -> Result<usize, Error> {
@@ -66,14 +196,66 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
     }
 
     /// Look up the port for a given global port ID
-    fn lookup_port(&self, port_id: GlobalPortId) -> Result<&'port Reg::Port, Error> {
+    fn lookup_port(&self, port_id: GlobalPortId) -> Result<&'port Reg::Port, ServiceError> {
         self.registration
             .ports()
             .get(port_id.0 as usize)
-            .ok_or(Error::InvalidPort)
+            .ok_or(ServiceError::InvalidPort(port_id))
             .copied()
     }
 
+    /// Get the cached status of the port with the given global port ID
+    pub async fn get_port_status(&self, port_id: GlobalPortId) -> Result<PortStatus, ServiceError> {
+        Ok(self.lookup_port(port_id)?.lock().await.get_cached_port_status())
+    }
+
+    /// Take a snapshot of the cached status of every registered port, in registration order
+    ///
+    /// All ports are locked before any status is read so the snapshot is consistent across ports.
+    pub async fn snapshot_all_ports(&self) -> Result<heapless::Vec<PortStatus, MAX_SUPPORTED_PORTS>, ServiceError> {
+        let mut ports = heapless::Vec::<_, MAX_SUPPORTED_PORTS>::new();
+        for port in self.registration.ports() {
+            ports.push(port.lock().await).map_err(|_| ServiceError::TooManyPorts)?;
+        }
+
+        Ok(ports.iter().map(|port| port.get_cached_port_status()).collect())
+    }
+
+    /// Number of registered ports that currently have something connected, based on their cached statuses
+    pub async fn get_num_connected_ports(&self) -> usize {
+        let mut count = 0;
+        for port in self.registration.ports() {
+            if port.lock().await.get_cached_port_status().is_connected() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Sync every registered port with its controller so cached statuses are valid without waiting for events
+    ///
+    /// Every port is synced even if an earlier one fails, the first error is returned.
+    pub async fn sync_all_ports(&self) -> Result<(), ServiceError> {
+        let mut result = Ok(());
+        for port in self.registration.ports() {
+            let mut port = port.lock().await;
+            if let Err(e) = port.sync_status().await {
+                error!("({}): Failed to sync port: {:?}", port.name(), e);
+                result = result.and(Err(e.into()));
+            }
+        }
+        result
+    }
+
+    /// Run the configured startup routine
+    pub async fn startup(&self) -> Result<(), ServiceError> {
+        if self.config.startup_sync {
+            info!("Syncing all ports on startup");
+            self.sync_all_ports().await?;
+        }
+        Ok(())
+    }
+
     /// Send an event to all registered listeners
     fn broadcast_event(&mut self, event: ServiceEvent<'port, Reg::Port>) {
         for sender in self.registration.event_senders() {
@@ -131,6 +313,16 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
                 )
                 .await
             }
+            PortEventData::Alert(ado) => {
+                if let Some(fault) = PortFault::from_ado(*ado) {
+                    warn!("({}): Port fault: {:?}", event.port.lock().await.name(), fault);
+                    self.broadcast_event(ServiceEvent {
+                        port: event.port,
+                        event: EventData::PortFault(fault),
+                    });
+                }
+                Ok(())
+            }
             unhandled => {
                 // Currently just log notifications, but may want to do more in the future
                 debug!(
@@ -144,7 +336,14 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
     }
 
     /// Process the given event
+    ///
+    /// Events are dropped while the service is shut down.
     pub async fn process_event(&mut self, event: Event<'port, Reg::Port>) -> Result<(), Error> {
+        if self.shut_down {
+            trace!("Service shut down, dropping event");
+            return Ok(());
+        }
+
         match event {
             Event::PortEvent(event) => {
                 trace!("({}): Processing port event", event.port.lock().await.name());
@@ -154,6 +353,7 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
                 trace!("Processing power policy event");
                 self.process_power_policy_event(&event).await
             }
+            Event::PowerPolicyLagged { missed, state } => self.process_power_policy_lagged(missed, &state).await,
         }
     }
 }
@@ -70,6 +70,23 @@ impl<'a, Reg: Registration<'a>> Service<'a, Reg> {
         Ok(())
     }
 
+    /// Resync with the power policy after missed power policy events
+    ///
+    /// An unconstrained state change may have been dropped, so the power policy's current state is re-applied to every
+    /// port. The OPM is also notified since the battery charging capability status may be stale.
+    pub(super) async fn process_power_policy_lagged(
+        &mut self,
+        missed: u64,
+        state: &power_policy::UnconstrainedState,
+    ) -> Result<(), Error> {
+        warn!("Missed {} power policy events, resyncing unconstrained state", missed);
+        self.process_unconstrained_state_change(state).await?;
+        if self.ucsi.notifications_enabled.battery_charge_change() {
+            self.pend_ucsi_connected_ports().await;
+        }
+        Ok(())
+    }
+
     /// Process power policy events
     pub(super) async fn process_power_policy_event(&mut self, message: &PowerPolicyEventData) -> Result<(), Error> {
         match message {
@@ -1,3 +1,5 @@
+use core::num::NonZeroU8;
+
 use embedded_services::sync::Lockable;
 use embedded_services::warn;
 use embedded_usb_pd::ucsi::cci::{Cci, GlobalCci};
@@ -13,8 +15,6 @@ use type_c_interface::ucsi::Lpm as _;
 
 use super::*;
 
-const MAX_SUPPORTED_PORTS: usize = 4;
-
 /// UCSI command response
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -24,7 +24,17 @@ pub struct UcsiResponse {
     /// Response CCI
     pub cci: GlobalCci,
     /// UCSI response data
-    pub data: Result<Option<ucsi::ResponseData>, PdError>,
+    pub data: Result<Option<ucsi::ResponseData>, ServiceError>,
+}
+
+/// Spec versions advertised through UCSI, in BCD
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UcsiSpecVersions {
+    /// USB PD spec version
+    pub bcd_usb_pd_spec: u16,
+    /// Type-C spec version
+    pub bcd_type_c_spec: u16,
 }
 
 /// UCSI state
@@ -43,6 +53,39 @@ pub(super) struct State {
     pub valid_battery_charging_capability: heapless::index_set::FnvIndexSet<GlobalPortId, MAX_SUPPORTED_PORTS>,
     /// PSU connected
     pub psu_connected: bool,
+    /// Consecutive invalid PPM state machine transitions
+    pub invalid_transitions: InvalidTransitionCounter,
+}
+
+/// Counts consecutive invalid PPM state machine transitions to decide when to recover
+#[derive(Default)]
+pub(super) struct InvalidTransitionCounter {
+    count: u8,
+}
+
+impl InvalidTransitionCounter {
+    /// Record an invalid transition
+    ///
+    /// Returns true and restarts the count once `threshold` consecutive invalid transitions have been recorded.
+    /// Never returns true if there's no threshold.
+    fn record_invalid(&mut self, threshold: Option<NonZeroU8>) -> bool {
+        let Some(threshold) = threshold else {
+            return false;
+        };
+
+        self.count = self.count.saturating_add(1);
+        if self.count >= threshold.get() {
+            self.count = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Record a valid transition, restarting the count
+    fn record_valid(&mut self) {
+        self.count = 0;
+    }
 }
 
 impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
@@ -68,7 +111,7 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
         ppm::ResponseData::GetCapability(capabilities)
     }
 
-    fn process_ppm_command(&mut self, command: &ucsi::ppm::Command) -> Result<Option<ppm::ResponseData>, PdError> {
+    fn process_ppm_command(&mut self, command: &ucsi::ppm::Command) -> Result<Option<ppm::ResponseData>, ServiceError> {
         match command {
             ppm::Command::SetNotificationEnable(enable) => {
                 self.process_set_notification_enable(enable.notification_enable);
@@ -108,13 +151,13 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
     async fn process_lpm_command(
         &mut self,
         command: &ucsi::lpm::GlobalCommand,
-    ) -> Result<Option<lpm::ResponseData>, PdError> {
+    ) -> Result<Option<lpm::ResponseData>, ServiceError> {
         debug!("Processing LPM command: {:?}", command);
         let mut port = self.lookup_port(command.port())?.lock().await;
         let local_port_id = self
             .registration
             .ucsi_local_port_id(command.port())
-            .ok_or(PdError::InvalidPort)?;
+            .ok_or(ServiceError::PortNotMapped(command.port()))?;
         let local_command = ucsi::lpm::LocalCommand::new(local_port_id, command.operation());
 
         match command.operation() {
@@ -123,7 +166,7 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
                 if let Some(capabilities) = &self.config.ucsi_port_capabilities {
                     Ok(Some(lpm::ResponseData::GetConnectorCapability(*capabilities)))
                 } else {
-                    port.execute_lpm_command(local_command).await
+                    Ok(port.execute_lpm_command(local_command).await?)
                 }
             }
             lpm::CommandData::GetConnectorStatus => {
@@ -144,9 +187,9 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
                     states_change.set_battery_charging_status_change(battery_charging_status.is_some());
                 }
 
-                response
+                Ok(response?)
             }
-            _ => port.execute_lpm_command(local_command).await,
+            _ => Ok(port.execute_lpm_command(local_command).await?),
         }
     }
 
@@ -192,6 +235,38 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
         self.set_cci_connector_change(cci);
     }
 
+    /// Recover from repeated invalid transitions by resetting the connector addressed by `command`
+    ///
+    /// This issues a PD Hard Reset, which drops VBUS on the connector. See
+    /// `Config::ucsi_invalid_transition_reset_threshold`.
+    async fn recover_from_invalid_transitions(&mut self, command: &GlobalCommand) {
+        let ucsi::GlobalCommand::LpmCommand(lpm_command) = command else {
+            warn!("Repeated invalid UCSI transitions on a PPM command, no connector to reset");
+            return;
+        };
+
+        let port_id = lpm_command.port();
+        warn!("Repeated invalid UCSI transitions, resetting connector {:?}", port_id);
+        let Ok(port) = self.lookup_port(port_id) else {
+            error!("Invalid port ID in UCSI command: {:?}", port_id);
+            return;
+        };
+
+        if let Err(e) = port.lock().await.hard_reset().await {
+            error!("Failed to reset connector {:?}: {:?}", port_id, e);
+        }
+    }
+
+    /// Get the USB PD and Type-C spec versions from the configured UCSI capabilities
+    ///
+    /// These are the raw BCD values reported to the OPM in the `GET_CAPABILITY` response.
+    pub fn ucsi_spec_versions(&self) -> UcsiSpecVersions {
+        UcsiSpecVersions {
+            bcd_usb_pd_spec: self.config.ucsi_capabilities.bcd_usb_pd_spec,
+            bcd_type_c_spec: self.config.ucsi_capabilities.bcd_type_c_spec,
+        }
+    }
+
     /// Process a UCSI command
     pub async fn process_ucsi_command(&mut self, command: &GlobalCommand) -> UcsiResponse {
         let mut next_input = Some(PpmInput::Command(command));
@@ -212,18 +287,25 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
                 return UcsiResponse {
                     notify_opm: true,
                     cci: Cci::new_error(),
-                    data: Err(PdError::InvalidMode),
+                    data: Err(ServiceError::Pd(PdError::InvalidMode)),
                 };
             };
 
             let output = match &output {
-                Ok(output) => output,
+                Ok(output) => {
+                    self.ucsi.invalid_transitions.record_valid();
+                    output
+                }
                 Err(e @ InvalidTransition { .. }) => {
                     error!("PPM state machine transition failed: {:#?}", e);
+                    let threshold = self.config.ucsi_invalid_transition_reset_threshold;
+                    if self.ucsi.invalid_transitions.record_invalid(threshold) {
+                        self.recover_from_invalid_transitions(command).await;
+                    }
                     return UcsiResponse {
                         notify_opm: true,
                         cci: Cci::new_error(),
-                        data: Err(PdError::Failed),
+                        data: Err(ServiceError::InvalidUcsiTransition),
                     };
                 }
             };
@@ -395,3 +477,58 @@ impl<'port, Reg: Registration<'port>> Service<'port, Reg> {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    mod invalid_transition_counter {
+        //! Tests for [`InvalidTransitionCounter`]
+
+        use super::*;
+
+        /// Test that recovery is triggered on reaching the threshold and the count restarts afterwards
+        #[test]
+        fn threshold() {
+            let threshold = NonZeroU8::new(3);
+            let mut counter = InvalidTransitionCounter::default();
+
+            for _ in 0..2 {
+                assert!(!counter.record_invalid(threshold));
+                assert!(!counter.record_invalid(threshold));
+                assert!(counter.record_invalid(threshold));
+            }
+        }
+
+        /// Test that a valid transition restarts the count
+        #[test]
+        fn valid_restarts() {
+            let threshold = NonZeroU8::new(2);
+            let mut counter = InvalidTransitionCounter::default();
+
+            assert!(!counter.record_invalid(threshold));
+            counter.record_valid();
+            assert!(!counter.record_invalid(threshold));
+            assert!(counter.record_invalid(threshold));
+        }
+
+        /// Test that recovery is never triggered without a threshold
+        #[test]
+        fn disabled() {
+            let mut counter = InvalidTransitionCounter::default();
+            for _ in 0..=u8::MAX {
+                assert!(!counter.record_invalid(None));
+            }
+        }
+
+        /// Test that a threshold of one triggers recovery on every invalid transition
+        #[test]
+        fn threshold_one() {
+            let threshold = NonZeroU8::new(1);
+            let mut counter = InvalidTransitionCounter::default();
+            assert!(counter.record_invalid(threshold));
+            assert!(counter.record_invalid(threshold));
+        }
+    }
+}
@@ -1,25 +1,42 @@
 use embedded_services::{error, event::Receiver, info, sync::Lockable};
-use power_policy_interface::service::event::EventData as PowerPolicyEventData;
 use type_c_interface::port::pd::Pd;
 
-use crate::service::{Service, event_receiver::ArrayEventReceiver, registration::Registration};
+use crate::service::event_receiver::{ArrayEventReceiver, PowerPolicyReceiver};
+use crate::service::{Service, registration::Registration};
 
 /// Task to run the Type-C service, running the default event loop
+///
+/// Returns the event receiver once the service has been shut down, see [`Service::shutdown`]. The power policy
+/// receiver is deregistered first, register a new one with [`ArrayEventReceiver::reregister_power_policy`] before
+/// reusing the event receiver.
 pub async fn task<
     const N: usize,
     Port: Lockable<Inner: Pd>,
     PortReceiver: Receiver<type_c_interface::service::event::PortEventData>,
-    PowerReceiver: Receiver<PowerPolicyEventData>,
+    PowerReceiver: PowerPolicyReceiver,
 >(
     service: &'static impl Lockable<Inner = Service<'static, impl Registration<'static, Port = Port>>>,
     mut event_receiver: ArrayEventReceiver<'static, N, Port, PortReceiver, PowerReceiver>,
-) {
+) -> ArrayEventReceiver<'static, N, Port, PortReceiver, PowerReceiver> {
     info!("Starting type-c task");
 
-    loop {
-        let event = event_receiver.wait_next().await;
-        if let Err(e) = service.lock().await.process_event(event).await {
+    if let Err(e) = service.lock().await.startup().await {
+        error!("Type-C service startup error: {:#?}", e);
+    }
+
+    while let Some(event) = event_receiver.wait_next_or_shutdown().await {
+        let mut service = service.lock().await;
+        // Without a shutdown signal, shutdown is only noticed once an event arrives
+        if service.is_shut_down() {
+            break;
+        }
+
+        if let Err(e) = service.process_event(event).await {
             error!("Type-C service processing error: {:#?}", e);
         }
     }
+
+    info!("Type-C service shut down, stopping task");
+    drop(event_receiver.deregister_power_policy());
+    event_receiver
 }
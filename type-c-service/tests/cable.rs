@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::PdError;
+use embedded_usb_pd::vdm::structured::command::discover_identity::sop_prime::{
+    MaximumVbusVoltage, PassiveCableVdo, ProductTypeVdos, ResponseVdos, VbusCurrentHandlingCapability,
+};
+use type_c_interface::{
+    control::cable::{CableCurrent, CableIdentity, CableType},
+    port::cable::Cable,
+};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test retrieving the cable identity.
+///
+/// The port should identify the cable from the controller's SOP' Discover Identity response, report `None` when the
+/// response doesn't describe a cable and pass on any error retrieving it.
+struct TestGetCableIdentity;
+
+impl Test for TestGetCableIdentity {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            let responses = &mut mock0.next_result_get_discover_identity_sop_prime_response;
+            // Passive, 3A, 20V cable
+            responses.push_back(Ok(ResponseVdos {
+                product_type_vdos: ProductTypeVdos::PassiveCable(PassiveCableVdo {
+                    vbus_current_handling_capability: VbusCurrentHandlingCapability::ThreeAmps,
+                    maximum_vbus_voltage: MaximumVbusVoltage::TwentyVolts,
+                    epr_capable: false,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+            // VCONN-powered USB device, not a cable
+            responses.push_back(Ok(ResponseVdos {
+                product_type_vdos: ProductTypeVdos::Vpd(Default::default()),
+                ..Default::default()
+            }));
+            // No SOP' response, e.g. the cable isn't e-marked
+            responses.push_back(Err(PdError::Failed));
+        }
+
+        let mut port = port0.port.lock().await;
+        let identity = port.get_cable_identity().await.unwrap().unwrap();
+        assert_eq!(
+            identity,
+            CableIdentity {
+                cable_type: CableType::Passive,
+                current: CableCurrent::Current3A,
+                max_voltage_mv: 20000,
+                epr_capable: false,
+            }
+        );
+        assert_eq!(identity.current.max_current_ma(), 3000);
+
+        assert_eq!(port.get_cable_identity().await.unwrap(), None);
+        assert_eq!(port.get_cable_identity().await, Err(PdError::Failed));
+
+        let mut mock0 = port0.mock.lock().await;
+        for _ in 0..3 {
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetDiscoverIdentitySopPrimeResponse(_)))
+            ));
+        }
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_get_cable_identity() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestGetCableIdentity,
+    )
+    .await;
+}
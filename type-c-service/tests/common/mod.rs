@@ -8,6 +8,7 @@ use embassy_sync::{
     channel::{Channel, DynamicReceiver, DynamicSender},
     mutex::Mutex,
     once_lock::OnceLock,
+    pubsub::PubSubChannel,
     watch,
 };
 use embassy_time::{Duration, with_timeout};
@@ -15,7 +16,7 @@ use embedded_services::{GlobalRawMutex, event::NonBlockingSender};
 use embedded_usb_pd::LocalPortId;
 use paste::paste;
 use power_policy_interface::charger::mock::NoopCharger;
-use type_c_service::service::registration::PortData;
+use type_c_service::service::{event_receiver::PortEventGate, registration::PortData};
 
 pub const DEFAULT_TEST_DURATION: Duration = Duration::from_secs(5);
 
@@ -124,6 +125,17 @@ pub type TypeCServiceMutexType<'port, 'ch> =
 
 /// Default channel size to use
 pub const CHANNEL_SIZE: usize = 4;
+/// Maximum number of observers of the type-C service event stream
+pub const TYPE_C_EVENT_OBSERVER_COUNT: usize = 2;
+
+/// Channel used to observe the event stream processed by the type-C service
+pub type TypeCEventObserverChannel<'port, 'ch> = PubSubChannel<
+    GlobalRawMutex,
+    type_c_service::service::Event<'port, PortMutexType<'port, 'ch>>,
+    CHANNEL_SIZE,
+    TYPE_C_EVENT_OBSERVER_COUNT,
+    0,
+>;
 
 /// Struct to pass port components to a test implementation.
 pub struct TestPort<'port, 'ch> {
@@ -137,6 +149,10 @@ pub struct TestPort<'port, 'ch> {
     pub interrupt_sender: PortInterruptSender<'ch>,
     /// Controller-side event receiver, drives software sink-ready timeouts
     pub event_receiver: PortEventReceiverType<'port, 'ch>,
+    /// Gate to pause and resume port event processing by the type-C service, shared by all ports
+    pub event_gate: &'port PortEventGate,
+    /// Channel to observe the type-C service event stream, shared by all ports
+    pub event_observer: &'port TypeCEventObserverChannel<'port, 'ch>,
 }
 
 /// Integration test trait
@@ -298,6 +314,8 @@ pub async fn run_test(
     // Initialize logging, ignore the error if the logger was already initialized by another test.
     let _ = env_logger::builder().filter_level(log::LevelFilter::Info).try_init();
 
+    let event_gate = PortEventGate::new();
+
     define_port!(port0, "mock0", "port0", port_config[0], LocalPortId(0));
     let PortComponents {
         port: port0,
@@ -338,24 +356,27 @@ pub async fn run_test(
     let type_c_service_sender = type_c_service_channel.dyn_sender();
     let type_c_service_receiver = type_c_service_channel.dyn_receiver();
 
-    let type_c_service = Mutex::new(type_c_service::service::Service::new(
-        type_c_service_config,
-        TypeCRegistrationType {
-            ports: [&port0, &port1, &port2],
-            port_data: [
-                PortData {
-                    local_port: Some(LocalPortId(0)),
-                },
-                PortData {
-                    local_port: Some(LocalPortId(0)),
-                },
-                PortData {
-                    local_port: Some(LocalPortId(0)),
-                },
-            ],
-            service_senders: [type_c_service_sender],
-        },
-    ));
+    let type_c_service = Mutex::new(
+        type_c_service::service::Service::new(
+            type_c_service_config,
+            TypeCRegistrationType {
+                ports: [&port0, &port1, &port2],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [type_c_service_sender],
+            },
+        )
+        .unwrap(),
+    );
 
     // Channel for events from the power policy service to the type-C service
     let type_c_power_policy_events: ManuallyDrop<
@@ -364,11 +385,17 @@ pub async fn run_test(
     let type_c_power_policy_sender = type_c_power_policy_events.dyn_sender();
     let type_c_power_policy_receiver = type_c_power_policy_events.dyn_receiver();
 
+    // Channel to observe the events processed by the type-C service
+    let type_c_event_observer: ManuallyDrop<TypeCEventObserverChannel<'_, '_>> =
+        ManuallyDrop::new(PubSubChannel::new());
+
     let type_c_service_event_receivers = type_c_service::service::event_receiver::ArrayEventReceiver::new(
         [&port0, &port1, &port2],
         [port0_type_c_receiver, port1_type_c_receiver, port2_type_c_receiver],
         type_c_power_policy_receiver,
-    );
+    )
+    .with_gate(&event_gate)
+    .with_observer(type_c_event_observer.dyn_immediate_publisher());
 
     // Channel for events from the power policy service to the test
     let power_policy_service_channel: ManuallyDrop<
@@ -427,6 +454,8 @@ pub async fn run_test(
                         shared_state: port0_shared_state,
                         interrupt_sender: port0_interrupt_sender,
                         event_receiver: port0_event_receiver,
+                        event_gate: &event_gate,
+                        event_observer: &type_c_event_observer,
                     },
                     TestPort {
                         port: &port1,
@@ -434,6 +463,8 @@ pub async fn run_test(
                         shared_state: port1_shared_state,
                         interrupt_sender: port1_interrupt_sender,
                         event_receiver: port1_event_receiver,
+                        event_gate: &event_gate,
+                        event_observer: &type_c_event_observer,
                     },
                     TestPort {
                         port: &port2,
@@ -441,6 +472,8 @@ pub async fn run_test(
                         shared_state: port2_shared_state,
                         interrupt_sender: port2_interrupt_sender,
                         event_receiver: port2_event_receiver,
+                        event_gate: &event_gate,
+                        event_observer: &type_c_event_observer,
                     },
                 )
                 .await;
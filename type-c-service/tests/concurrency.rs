@@ -0,0 +1,269 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_futures::{
+    join::join,
+    select::{Either, select},
+};
+use embassy_time::{Duration, Timer, with_timeout};
+use embedded_usb_pd::{PdError, PowerRole, type_c::ConnectionState};
+use type_c_interface::{
+    control::pd::PortStatus,
+    port::{
+        event::{PortEvent, PortNotificationEventBitfield, PortStatusEventBitfield},
+        max_sink_voltage::MaxSinkVoltage,
+        pd::Pd,
+    },
+    util::POWER_CAPABILITY_5V_1A5,
+};
+use type_c_interface_test_mocks::controller::{
+    FnCall as ControllerFnCall, max_sink_voltage::FnCall as MaxSinkVoltageFnCall, pd::FnCall as PdFnCall,
+};
+use type_c_service::controller::event::Event;
+
+use crate::common::{
+    DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that a power command and a PD command issued concurrently are both processed
+struct TestConcurrentCommands;
+
+impl Test for TestConcurrentCommands {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_set_max_sink_voltage.push_back(Ok(()));
+            mock0.next_result_hard_reset.push_back(Ok(()));
+        }
+
+        let (power_result, pd_result) = with_timeout(
+            DEFAULT_PER_CALL_TIMEOUT,
+            join(
+                async { port0.port.lock().await.set_max_sink_voltage(Some(5000)).await },
+                async { port0.port.lock().await.hard_reset().await },
+            ),
+        )
+        .await
+        .unwrap();
+        power_result.unwrap();
+        pd_result.unwrap();
+
+        let mock0 = port0.mock.lock().await;
+        assert_eq!(mock0.fn_calls.len(), 2);
+        assert!(mock0.fn_calls.iter().any(|call| matches!(
+            call,
+            ControllerFnCall::MaxSinkVoltage(MaxSinkVoltageFnCall::SetMaxSinkVoltage(_, Some(5000)))
+        )));
+        assert!(
+            mock0
+                .fn_calls
+                .iter()
+                .any(|call| matches!(call, ControllerFnCall::Pd(PdFnCall::HardReset(_))))
+        );
+    }
+}
+
+/// Test that software and hardware events arriving together are both delivered, even if a pending
+/// [`type_c_service::controller::event_receiver::EventReceiver::wait_event`] future was dropped
+struct TestSimultaneousEvents;
+
+impl Test for TestSimultaneousEvents {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        mut port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // Start waiting with nothing pending and drop the future partway through
+        assert!(matches!(
+            select(
+                port0.event_receiver.wait_event(),
+                Timer::after(Duration::from_millis(10))
+            )
+            .await,
+            Either::Second(_)
+        ));
+
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Ok(PortStatus {
+                available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                ..Default::default()
+            }));
+
+        // Generate a software event through the loopback and a hardware event through the interrupt
+        let mut notification = PortNotificationEventBitfield::none();
+        notification.set_alert(true);
+        let (sync_result, interrupt_result) = join(async { port0.port.lock().await.sync_state().await }, async {
+            port0.interrupt_sender.try_send(notification.into())
+        })
+        .await;
+        sync_result.unwrap();
+        interrupt_result.unwrap();
+
+        let mut status_changed = false;
+        let mut alert = false;
+        for _ in 0..2 {
+            match with_timeout(DEFAULT_PER_CALL_TIMEOUT, port0.event_receiver.wait_event())
+                .await
+                .unwrap()
+            {
+                Event::PortEvent(PortEvent::StatusChanged(event)) => {
+                    assert!(event.plug_inserted_or_removed());
+                    assert!(event.new_power_contract_as_consumer());
+                    status_changed = true;
+                }
+                Event::PortEvent(PortEvent::Alert) => alert = true,
+                event => panic!("Unexpected event: {event:?}"),
+            }
+        }
+
+        assert!(status_changed);
+        assert!(alert);
+    }
+}
+
+/// Process a plug event on `port`
+async fn process_plug_event(port: &TestPort<'_, '_>) -> Result<(), PdError> {
+    let mut status_event = PortStatusEventBitfield::none();
+    status_event.set_plug_inserted_or_removed(true);
+    port.port
+        .lock()
+        .await
+        .process_event(Event::PortEvent(PortEvent::StatusChanged(status_event)))
+        .await
+        .map(|_| ())
+}
+
+/// Test that a command in progress on a port excludes event processing on that port but not on other ports
+struct TestPerPortExclusion;
+
+impl Test for TestPerPortExclusion {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_hard_reset.extend((0..2).map(|_| Ok(())));
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus::default()));
+        }
+        port1
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Ok(PortStatus::default()));
+
+        // Keep port 0's controller busy so a command on port 0 stays in progress
+        let mock0_guard = port0.mock.lock().await;
+        let (command_result, ()) = with_timeout(
+            DEFAULT_PER_CALL_TIMEOUT,
+            join(async { port0.port.lock().await.hard_reset().await }, async {
+                // Let the command take the port and wait on the controller
+                Timer::after(Duration::from_millis(10)).await;
+
+                // Events on other ports are unaffected
+                with_timeout(DEFAULT_PER_CALL_TIMEOUT, process_plug_event(&port1))
+                    .await
+                    .unwrap()
+                    .unwrap();
+
+                // Event processing on port 0 must wait for the command to finish
+                assert!(
+                    with_timeout(Duration::from_millis(50), process_plug_event(&port0))
+                        .await
+                        .is_err()
+                );
+                drop(mock0_guard);
+            }),
+        )
+        .await
+        .unwrap();
+        command_result.unwrap();
+
+        // A command and an event on the same port are processed one after the other in the order they acquired the port
+        let (command_result, event_result) = with_timeout(
+            DEFAULT_PER_CALL_TIMEOUT,
+            join(
+                async { port0.port.lock().await.hard_reset().await },
+                process_plug_event(&port0),
+            ),
+        )
+        .await
+        .unwrap();
+        command_result.unwrap();
+        event_result.unwrap();
+
+        let mut mock0 = port0.mock.lock().await;
+        for _ in 0..2 {
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::HardReset(_)))
+            ));
+        }
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+        ));
+
+        assert!(matches!(
+            port1.mock.lock().await.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_commands() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestConcurrentCommands,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_simultaneous_events() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestSimultaneousEvents,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_per_port_exclusion() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPerPortExclusion,
+    )
+    .await;
+}
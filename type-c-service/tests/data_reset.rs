@@ -0,0 +1,97 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_time::Duration;
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::control::pd::DataResetStatus;
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+const DATA_RESET_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Test that data reset completion and controller failures are mapped to a status
+struct TestDataReset;
+
+impl Test for TestDataReset {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_execute_drst.push_back(Ok(()));
+            mock0
+                .next_result_execute_drst
+                .push_back(Err(PdError::UnrecognizedCommand));
+            mock0.next_result_execute_drst.push_back(Err(PdError::Failed));
+        }
+
+        let mut port = port0.port.lock().await;
+        assert_eq!(port.data_reset(DATA_RESET_TIMEOUT).await, DataResetStatus::Completed);
+        assert_eq!(port.data_reset(DATA_RESET_TIMEOUT).await, DataResetStatus::NotSupported);
+        assert_eq!(
+            port.data_reset(DATA_RESET_TIMEOUT).await,
+            DataResetStatus::Failed(PdError::Failed)
+        );
+
+        let mut mock0 = port0.mock.lock().await;
+        for _ in 0..3 {
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::ExecuteDrst(LocalPortId(0))))
+            ));
+        }
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+/// Test that a data reset which doesn't complete in time reports a timeout
+struct TestDataResetTimeout;
+
+impl Test for TestDataResetTimeout {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // Hold the controller so the data reset can't make progress
+        let mock0 = port0.mock.lock().await;
+        assert_eq!(
+            port0.port.lock().await.data_reset(DATA_RESET_TIMEOUT).await,
+            DataResetStatus::TimedOut
+        );
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_data_reset() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDataReset,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_data_reset_timeout() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDataResetTimeout,
+    )
+    .await;
+}
@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::PdError;
+use type_c_interface_test_mocks::controller::FnCall as ControllerFnCall;
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test reading the controller die temperature through a port
+struct TestDieTemperature;
+
+impl Test for TestDieTemperature {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_die_temperature
+            .push_back(Ok(47));
+        assert_eq!(port0.port.lock().await.get_die_temperature().await, Ok(47));
+        {
+            let mut mock0 = port0.mock.lock().await;
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::GetDieTemperature)
+            ));
+            assert!(mock0.fn_calls.is_empty());
+        }
+
+        // Controllers without a temperature sensor
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_die_temperature
+            .push_back(Err(PdError::UnrecognizedCommand));
+        assert_eq!(
+            port0.port.lock().await.get_die_temperature().await,
+            Err(PdError::UnrecognizedCommand)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_die_temperature() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDieTemperature,
+    )
+    .await;
+}
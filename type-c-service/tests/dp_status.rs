@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::control::dp::{DpStatus, DpStatusQuery};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that a controller without DP support is reported distinctly from other DP status failures
+struct TestDpStatus;
+
+impl Test for TestDpStatus {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let status = DpStatus {
+            alt_mode_entered: true,
+            ..Default::default()
+        };
+
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_dp_status.push_back(Ok(status));
+            mock0
+                .next_result_get_dp_status
+                .push_back(Err(PdError::UnrecognizedCommand));
+            mock0.next_result_get_dp_status.push_back(Err(PdError::InvalidMode));
+            mock0.next_result_get_dp_status.push_back(Err(PdError::Failed));
+        }
+
+        let mut port = port0.port.lock().await;
+        assert_eq!(port.dp_status().await, DpStatusQuery::Status(status));
+        assert_eq!(port.dp_status().await, DpStatusQuery::NotSupported);
+        assert_eq!(port.dp_status().await, DpStatusQuery::NotSupported);
+        assert_eq!(port.dp_status().await, DpStatusQuery::Failed(PdError::Failed));
+
+        let mut mock0 = port0.mock.lock().await;
+        for _ in 0..4 {
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetDpStatus(LocalPortId(0))))
+            ));
+        }
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_dp_status() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDpStatus,
+    )
+    .await;
+}
@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embedded_usb_pd::LocalPortId;
+use type_c_interface::port::{
+    event::{PortEventBitfield, PortNotificationEventBitfield, PortStatusEventBitfield},
+    pd::Pd,
+};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that an event mask written through the port is passed to the controller and reads back unchanged
+struct TestEventMaskRoundTrip;
+
+impl Test for TestEventMaskRoundTrip {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let mut status = PortStatusEventBitfield::none();
+        status.set_plug_inserted_or_removed(true);
+        status.set_sink_ready(true);
+        let mut notification = PortNotificationEventBitfield::none();
+        notification.set_alert(true);
+        let mask = PortEventBitfield { status, notification };
+
+        port0.mock.lock().await.next_result_set_event_mask.push_back(Ok(()));
+        port0.port.lock().await.set_event_mask(mask).await.unwrap();
+
+        // Feed the mask the controller received back as its current configuration
+        {
+            let mut mock0 = port0.mock.lock().await;
+            let written = match mock0.fn_calls.pop_front() {
+                Some(ControllerFnCall::Pd(PdFnCall::SetEventMask(LocalPortId(0), written))) => written,
+                _ => panic!("Expected set event mask call"),
+            };
+            mock0.next_result_get_event_mask.push_back(Ok(written));
+        }
+
+        assert_eq!(port0.port.lock().await.get_event_mask().await.unwrap(), mask);
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetEventMask(LocalPortId(0))))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_event_mask_round_trip() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestEventMaskRoundTrip,
+    )
+    .await;
+}
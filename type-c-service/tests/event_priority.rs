@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_sync::channel::Channel;
+use embassy_time::with_timeout;
+use embedded_services::GlobalRawMutex;
+use embedded_usb_pd::ado::Ado;
+use type_c_interface::service::event::PortEventData;
+use type_c_service::service::{Event, event_receiver::ArrayEventReceiver};
+
+use crate::common::{
+    CHANNEL_SIZE, DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort,
+    TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that events from a high priority port are returned before pending events from lower priority ports,
+/// and that ports of equal priority keep their registration order
+struct TestPortPriority;
+
+impl Test for TestPortPriority {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let channels: [Channel<GlobalRawMutex, PortEventData, CHANNEL_SIZE>; 3] =
+            [Channel::new(), Channel::new(), Channel::new()];
+        let power_policy_channel: Channel<
+            GlobalRawMutex,
+            power_policy_interface::service::event::EventData,
+            CHANNEL_SIZE,
+        > = Channel::new();
+
+        let mut receiver = ArrayEventReceiver::new(
+            [port0.port, port1.port, port2.port],
+            [
+                channels[0].dyn_receiver(),
+                channels[1].dyn_receiver(),
+                channels[2].dyn_receiver(),
+            ],
+            power_policy_channel.dyn_receiver(),
+        )
+        .with_port_priorities([0, 0, 1]);
+
+        // Queue an event on every port before receiving any of them
+        channels[0].try_send(PortEventData::Alert(Ado::Ovp)).unwrap();
+        channels[1].try_send(PortEventData::Alert(Ado::Ocp)).unwrap();
+        channels[2].try_send(PortEventData::Alert(Ado::Ovp)).unwrap();
+
+        // The high priority port comes first, followed by the others in registration order
+        for expected in [port2.port, port0.port, port1.port] {
+            let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next())
+                .await
+                .unwrap();
+            let Event::PortEvent(event) = event else {
+                panic!("Expected port event");
+            };
+            assert!(core::ptr::eq(event.port, expected));
+        }
+
+        // A high priority event queued behind a pending low priority event is still returned first
+        channels[0].try_send(PortEventData::Alert(Ado::Ocp)).unwrap();
+        channels[2].try_send(PortEventData::Alert(Ado::Ocp)).unwrap();
+        for expected in [port2.port, port0.port] {
+            let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next())
+                .await
+                .unwrap();
+            let Event::PortEvent(event) = event else {
+                panic!("Expected port event");
+            };
+            assert!(core::ptr::eq(event.port, expected));
+            assert!(matches!(event.event, PortEventData::Alert(Ado::Ocp)));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_port_priority() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPortPriority,
+    )
+    .await;
+}
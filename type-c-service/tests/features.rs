@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::control::dp::DpStatusQuery;
+use type_c_interface::control::vdm::OtherVdm;
+use type_c_interface::port::{pd::Pd, retimer::Retimer};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::controller::config::{Config, Features};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that commands needing an unsupported feature are rejected without reaching the controller
+struct TestUnsupportedFeatures;
+
+impl Test for TestUnsupportedFeatures {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // Port 0 only supports VDMs
+        {
+            let mut port = port0.port.lock().await;
+            assert!(port.supports(Features::VDM));
+            assert!(!port.supports(Features::DP));
+            assert!(!port.supports(Features::RETIMER));
+            assert!(!port.supports(Features::VDM.union(Features::DP)));
+
+            assert_eq!(port.get_dp_status().await.err(), Some(PdError::UnrecognizedCommand));
+            assert_eq!(port.dp_status().await, DpStatusQuery::NotSupported);
+            assert_eq!(port.set_rt_compliance().await, Err(PdError::UnrecognizedCommand));
+            assert_eq!(port.reconfigure_retimer().await, Err(PdError::UnrecognizedCommand));
+        }
+        assert!(port0.mock.lock().await.fn_calls.is_empty());
+
+        // Supported features still reach the controller
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_other_vdm
+            .push_back(Ok(OtherVdm::default()));
+        port0.port.lock().await.get_other_vdm().await.unwrap();
+        {
+            let mut mock0 = port0.mock.lock().await;
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetOtherVdm(LocalPortId(0))))
+            ));
+            assert!(mock0.fn_calls.is_empty());
+        }
+
+        // Ports default to supporting every feature
+        let status = Default::default();
+        port1.mock.lock().await.next_result_get_dp_status.push_back(Ok(status));
+        {
+            let mut port = port1.port.lock().await;
+            assert!(port.supports(Features::all()));
+            assert_eq!(port.dp_status().await, DpStatusQuery::Status(status));
+        }
+        let mut mock1 = port1.mock.lock().await;
+        assert!(matches!(
+            mock1.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetDpStatus(LocalPortId(0))))
+        ));
+        assert!(mock1.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_unsupported_features() {
+    let mut port_config: [Config; 3] = Default::default();
+    port_config[0].features = Features::VDM;
+
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestUnsupportedFeatures,
+    )
+    .await;
+}
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_time::with_timeout;
+use embedded_usb_pd::type_c::ConnectionState;
+use type_c_interface::{
+    control::pd::PortStatus,
+    port::event::PortStatusEventBitfield,
+    service::event::{DebugAccessoryData, EventData, PortEventData},
+};
+
+use crate::common::{
+    DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that an injected status change runs the full processing path without querying the controller
+struct TestInjectPortEvent;
+
+impl Test for TestInjectPortEvent {
+    async fn run<'port, 'ch>(
+        &mut self,
+        type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let status = PortStatus {
+            connection_state: Some(ConnectionState::DebugAccessory),
+            ..Default::default()
+        };
+        let mut status_event = PortStatusEventBitfield::none();
+        status_event.set_plug_inserted_or_removed(true);
+
+        let result = port0
+            .port
+            .lock()
+            .await
+            .inject_port_event(status_event, status)
+            .await
+            .unwrap();
+        match result {
+            PortEventData::StatusChanged(data) => {
+                assert_eq!(data.status_event, status_event);
+                assert_eq!(data.previous_status, PortStatus::default());
+                assert_eq!(data.current_status, status);
+            }
+            other => panic!("Expected PortEventData::StatusChanged, got {other:?}"),
+        }
+
+        // The cache reflects the injected status
+        assert_eq!(port0.port.lock().await.get_cached_port_status(), status);
+
+        // The type-C service processes the event as if the controller raised it
+        let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, type_c_receiver.receive())
+            .await
+            .unwrap();
+        assert!(core::ptr::eq(event.port, port0.port));
+        assert_eq!(
+            event.event,
+            EventData::DebugAccessory(DebugAccessoryData { connected: true })
+        );
+
+        // The controller was never queried
+        assert!(port0.mock.lock().await.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_inject_port_event() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestInjectPortEvent,
+    )
+    .await;
+}
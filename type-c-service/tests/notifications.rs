@@ -10,7 +10,7 @@ use type_c_interface::{
     control::pd::PortStatus,
     control::vdm::{ATTN_VDM_LEN, AttnVdm, OTHER_VDM_LEN, OtherVdm},
     port::event::{PortEvent, PortStatusEventBitfield, VdmData, VdmNotification},
-    service::event::PortEventData,
+    service::event::{EventData, PortEventData, PortFault},
 };
 use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
 use type_c_service::controller::event::Event;
@@ -23,8 +23,8 @@ mod common;
 
 /// Assert that neither the type-C service nor the power policy service broadcast an event.
 ///
-/// PD alerts, VDMs, and DP status updates are purely informational at the port level, so they
-/// must never leak out as a type-C service or power policy service broadcast.
+/// Non-fault PD alerts, VDMs, and DP status updates are purely informational at the port level, so
+/// they must never leak out as a type-C service or power policy service broadcast.
 async fn assert_no_service_broadcast(
     type_c_receiver: &TypeCServiceReceiver<'_, '_>,
     power_policy_receiver: &PowerPolicyServiceReceiver<'_, '_>,
@@ -382,6 +382,52 @@ impl Test for TestHardReset {
     }
 }
 
+/// Test the PD fault alert flow.
+///
+/// A fault-type ADO should still be surfaced as a [`PortEventData::Alert`] event, and the type-C
+/// service should broadcast the parsed [`PortFault`] so that safety logic can react.
+struct TestPortFault;
+
+impl Test for TestPortFault {
+    async fn run<'port, 'ch>(
+        &mut self,
+        type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // The controller reports an over-voltage alert.
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_pd_alert.push_back(Ok(Some(Ado::Ovp)));
+        }
+
+        let result = port0
+            .port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::Alert))
+            .await
+            .unwrap();
+
+        match result {
+            Some(PortEventData::Alert(ado)) => assert_eq!(ado, Ado::Ovp),
+            other => panic!("Expected PortEventData::Alert, got {other:?}"),
+        }
+
+        // The type-C service should broadcast the structured fault for this port.
+        let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, type_c_receiver.receive())
+            .await
+            .unwrap();
+        assert!(core::ptr::eq(event.port, port0.port));
+        assert_eq!(event.event, EventData::PortFault(PortFault::OverVoltage));
+
+        // Nothing else should be broadcast.
+        assert_no_service_broadcast(&type_c_receiver, &power_policy_receiver).await;
+    }
+}
+
 #[tokio::test]
 async fn test_pd_alert() {
     common::run_test(
@@ -393,6 +439,17 @@ async fn test_pd_alert() {
     .await;
 }
 
+#[tokio::test]
+async fn test_port_fault() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPortFault,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn test_vdm() {
     common::run_test(DEFAULT_TEST_DURATION, Default::default(), Default::default(), TestVdm).await;
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_time::with_timeout;
+use embedded_usb_pd::{PowerRole, type_c::ConnectionState};
+use power_policy_interface::service::event::EventData as PowerPolicyEventData;
+use type_c_interface::{
+    control::pd::PortStatus, port::event::PortStatusEventBitfield, service::event::PortEventData,
+    util::POWER_CAPABILITY_5V_3A0,
+};
+use type_c_service::service::Event;
+
+use crate::common::{
+    DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that every observer sees the port and power policy events processed by the type-C service
+struct TestEventObservers;
+
+impl Test for TestEventObservers {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let mut observers = [
+            port0.event_observer.dyn_subscriber().unwrap(),
+            port0.event_observer.dyn_subscriber().unwrap(),
+        ];
+
+        port0.mock.lock().await.next_result_enable_sink_path.push_back(Ok(()));
+
+        let status = PortStatus {
+            available_sink_contract: Some(POWER_CAPABILITY_5V_3A0),
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        };
+        let mut status_event = PortStatusEventBitfield::none();
+        status_event.set_plug_inserted_or_removed(true);
+        status_event.set_new_power_contract_as_consumer(true);
+        status_event.set_sink_ready(true);
+        port0
+            .port
+            .lock()
+            .await
+            .inject_port_event(status_event, status)
+            .await
+            .unwrap();
+
+        for observer in observers.iter_mut() {
+            match with_timeout(DEFAULT_PER_CALL_TIMEOUT, observer.next_message_pure())
+                .await
+                .unwrap()
+            {
+                Event::PortEvent(event) => {
+                    assert!(core::ptr::eq(event.port, port0.port));
+                    match event.event {
+                        PortEventData::StatusChanged(data) => {
+                            assert_eq!(data.status_event, status_event);
+                            assert_eq!(data.current_status, status);
+                        }
+                        other => panic!("Expected PortEventData::StatusChanged, got {other:?}"),
+                    }
+                }
+                Event::PowerPolicy(event) => panic!("Expected a port event, got {event:?}"),
+                Event::PowerPolicyLagged { missed, .. } => panic!("Expected a port event, got lag of {missed}"),
+            }
+
+            // The power policy connects the consumer and notifies the type-C service
+            assert!(matches!(
+                with_timeout(DEFAULT_PER_CALL_TIMEOUT, observer.next_message_pure())
+                    .await
+                    .unwrap(),
+                Event::PowerPolicy(PowerPolicyEventData::ConsumerConnected(_))
+            ));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_event_observers() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestEventObservers,
+    )
+    .await;
+}
@@ -0,0 +1,108 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_time::{Duration, TimeoutError, with_timeout};
+use embedded_usb_pd::ado::Ado;
+use type_c_interface::{
+    port::{event::PortEvent, pd::Pd},
+    service::event::{EventData, PortFault},
+};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::controller::event::Event;
+
+use crate::common::{
+    DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that port events generated while paused are buffered, commands are still accepted,
+/// and the buffered events are processed in order on resume
+struct TestPauseResumeEvents;
+
+impl Test for TestPauseResumeEvents {
+    async fn run<'port, 'ch>(
+        &mut self,
+        type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_pd_alert.push_back(Ok(Some(Ado::Ovp)));
+            mock0.next_result_get_pd_alert.push_back(Ok(Some(Ado::Ocp)));
+            mock0.next_result_hard_reset.push_back(Ok(()));
+        }
+
+        port0.event_gate.pause_events();
+        assert!(port0.event_gate.is_paused());
+
+        // Generate two fault events, the port forwards both to the type-C service
+        for _ in 0..2 {
+            port0
+                .port
+                .lock()
+                .await
+                .process_event(Event::PortEvent(PortEvent::Alert))
+                .await
+                .unwrap();
+        }
+
+        // Neither event should be processed while paused
+        assert_eq!(
+            with_timeout(Duration::from_millis(100), type_c_receiver.receive())
+                .await
+                .err(),
+            Some(TimeoutError)
+        );
+
+        // Commands are still accepted
+        with_timeout(DEFAULT_PER_CALL_TIMEOUT, async {
+            port0.port.lock().await.hard_reset().await
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        port0.event_gate.resume_events();
+        assert!(!port0.event_gate.is_paused());
+
+        // Buffered events are processed in the order they were generated
+        for expected in [PortFault::OverVoltage, PortFault::OverCurrent] {
+            let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, type_c_receiver.receive())
+                .await
+                .unwrap();
+            assert!(core::ptr::eq(event.port, port0.port));
+            assert_eq!(event.event, EventData::PortFault(expected));
+        }
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPdAlert(_)))
+        ));
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPdAlert(_)))
+        ));
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::HardReset(_)))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_pause_resume_events() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPauseResumeEvents,
+    )
+    .await;
+}
@@ -0,0 +1,132 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_time::{Duration, with_timeout};
+use embedded_usb_pd::{PowerRole, type_c::ConnectionState};
+use power_policy_interface::{psu::PsuState, service::event::Event as PowerPolicyEvent};
+use type_c_interface::{
+    control::pd::{PdRevision, PortStatus},
+    port::event::{PortEvent, PortStatusEventBitfield},
+    util::POWER_CAPABILITY_5V_3A0,
+};
+use type_c_service::controller::event::Event;
+
+use crate::common::{
+    DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that a consumer contract is accepted or refused based on the partner's negotiated PD revision
+struct TestPdRevisionPolicy {
+    /// PD revision reported by the controller
+    revision: Option<PdRevision>,
+    /// Whether the contract is expected to be accepted
+    expect_connected: bool,
+}
+
+impl Test for TestPdRevisionPolicy {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus {
+                available_sink_contract: Some(POWER_CAPABILITY_5V_3A0),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                pd_revision: self.revision,
+                ..Default::default()
+            }));
+            if self.expect_connected {
+                mock0.next_result_enable_sink_path.push_back(Ok(()));
+            }
+        }
+
+        let mut port_event = PortStatusEventBitfield::none();
+        port_event.set_plug_inserted_or_removed(true);
+        port_event.set_new_power_contract_as_consumer(true);
+        port_event.set_sink_ready(true);
+        port0
+            .port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::StatusChanged(port_event)))
+            .await
+            .unwrap();
+
+        // The negotiated revision is surfaced in the port status regardless of the policy decision
+        assert_eq!(
+            port0.port.lock().await.get_cached_port_status().pd_revision,
+            self.revision
+        );
+
+        if self.expect_connected {
+            assert!(matches!(
+                with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+                Ok(PowerPolicyEvent::ConsumerConnected(_, _))
+            ));
+            assert!(matches!(
+                port0.port.lock().await.state().psu_state,
+                PsuState::ConnectedConsumer(_)
+            ));
+        } else {
+            assert!(!matches!(
+                with_timeout(Duration::from_millis(100), power_policy_receiver.receive()).await,
+                Ok(PowerPolicyEvent::ConsumerConnected(_, _))
+            ));
+            assert_eq!(port0.port.lock().await.state().psu_state, PsuState::Idle);
+        }
+    }
+}
+
+/// Run [`TestPdRevisionPolicy`] with port 0 requiring PD 3.x
+async fn run_pd_revision_test(revision: Option<PdRevision>, expect_connected: bool) {
+    let mut port_config: [type_c_service::controller::config::Config; common::TYPE_C_PORT_COUNT] = Default::default();
+    port_config[0].min_pd_revision = Some(PdRevision::Rev3);
+
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestPdRevisionPolicy {
+            revision,
+            expect_connected,
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_pd_revision_at_minimum_accepted() {
+    run_pd_revision_test(Some(PdRevision::Rev3), true).await;
+}
+
+#[tokio::test]
+async fn test_pd_revision_below_minimum_refused() {
+    run_pd_revision_test(Some(PdRevision::Rev2), false).await;
+}
+
+#[tokio::test]
+async fn test_pd_revision_unknown_accepted() {
+    run_pd_revision_test(None, true).await;
+}
+
+#[tokio::test]
+async fn test_pd_revision_no_policy() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPdRevisionPolicy {
+            revision: Some(PdRevision::Rev1),
+            expect_connected: true,
+        },
+    )
+    .await;
+}
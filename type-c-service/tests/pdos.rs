@@ -0,0 +1,152 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PdError, PowerRole};
+use type_c_interface::{
+    control::pdo::{Pdo, PdoOwner, PdoQuery, Pdos},
+    port::pd::Pd,
+};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// 5 V, 3 A fixed supply
+const FIXED_5V_3A: u32 = 0x0001_912c;
+/// 5-20 V, 3 A variable supply
+const VARIABLE_5V_20V_3A: u32 = 0x9901_912c;
+/// 5-20 V, 60 W battery supply
+const BATTERY_5V_20V_60W: u32 = 0x5901_90f0;
+/// 3.3-21 V, 3 A programmable power supply
+const PPS_3V3_21V_3A: u32 = 0xc1a4_213c;
+/// EPR adjustable voltage supply, not parsed
+const EPR_AVS: u32 = 0xd000_0000;
+
+/// Test that partner source PDOs reported by the controller are parsed into their typed fields
+struct TestGetPartnerSourcePdos;
+
+impl Test for TestGetPartnerSourcePdos {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let query = PdoQuery {
+            owner: PdoOwner::Partner,
+            role: PowerRole::Source,
+        };
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_pdos
+            .push_back(Ok(Pdos::from_raw(&[
+                FIXED_5V_3A,
+                VARIABLE_5V_20V_3A,
+                BATTERY_5V_20V_60W,
+                PPS_3V3_21V_3A,
+                EPR_AVS,
+            ])));
+
+        let pdos = port0.port.lock().await.get_pdos(query).await.unwrap();
+        assert_eq!(pdos.len(), 5);
+
+        let mut parsed = pdos.iter();
+        assert_eq!(
+            parsed.next(),
+            Some(Pdo::Fixed {
+                voltage_mv: 5000,
+                current_ma: 3000,
+            })
+        );
+        assert_eq!(
+            parsed.next(),
+            Some(Pdo::Variable {
+                min_voltage_mv: 5000,
+                max_voltage_mv: 20000,
+                current_ma: 3000,
+            })
+        );
+        assert_eq!(
+            parsed.next(),
+            Some(Pdo::Battery {
+                min_voltage_mv: 5000,
+                max_voltage_mv: 20000,
+                power_mw: 60000,
+            })
+        );
+        assert_eq!(
+            parsed.next(),
+            Some(Pdo::Pps {
+                min_voltage_mv: 3300,
+                max_voltage_mv: 21000,
+                current_ma: 3000,
+            })
+        );
+        assert_eq!(parsed.next(), Some(Pdo::OtherAugmented(EPR_AVS)));
+        assert_eq!(parsed.next(), None);
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPdos(LocalPortId(0), call_query))) if call_query == query
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+/// Test that controller failures and empty PDO lists are reported unchanged
+struct TestGetPdosEmptyAndError;
+
+impl Test for TestGetPdosEmptyAndError {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let query = PdoQuery {
+            owner: PdoOwner::Local,
+            role: PowerRole::Sink,
+        };
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_pdos.push_back(Ok(Pdos::default()));
+            mock0.next_result_get_pdos.push_back(Err(PdError::Failed));
+        }
+
+        let pdos = port0.port.lock().await.get_pdos(query).await.unwrap();
+        assert!(pdos.is_empty());
+        assert_eq!(pdos.iter().next(), None);
+
+        assert_eq!(port0.port.lock().await.get_pdos(query).await, Err(PdError::Failed));
+    }
+}
+
+#[tokio::test]
+async fn test_get_partner_source_pdos() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestGetPartnerSourcePdos,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_get_pdos_empty_and_error() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestGetPdosEmptyAndError,
+    )
+    .await;
+}
@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PowerRole, type_c::ConnectionState};
+use power_policy_interface::psu::{Psu, PsuState};
+use type_c_interface::{
+    control::pd::PortStatus,
+    port::{
+        event::{PortEvent, PortStatusEventBitfield},
+        port_enable::PortEnable,
+    },
+    service::event::PortEventData,
+};
+use type_c_interface_test_mocks::controller::{
+    FnCall as ControllerFnCall, pd::FnCall as PdFnCall, port_enable::FnCall as PortEnableFnCall,
+};
+use type_c_service::controller::event::Event;
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that disabling a port detaches its partner and ignores events until the port is enabled again
+struct TestDisablePort;
+
+impl Test for TestDisablePort {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let attached = PortStatus {
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        };
+        let mut plug_event = PortStatusEventBitfield::none();
+        plug_event.set_plug_inserted_or_removed(true);
+
+        let mut port = port0.port.lock().await;
+        port.inject_port_event(plug_event, attached).await.unwrap();
+        assert_eq!(port.state().psu_state, PsuState::Idle);
+
+        // Disabling the port detaches the partner
+        port0.mock.lock().await.next_result_set_port_enabled.push_back(Ok(()));
+        port.set_port_enabled(false).await.unwrap();
+        assert!(!port.is_port_enabled());
+        assert!(!port.get_cached_port_status().is_connected());
+        assert_eq!(port.state().psu_state, PsuState::Detached);
+        {
+            let mut mock0 = port0.mock.lock().await;
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::PortEnable(PortEnableFnCall::SetPortEnabled(
+                    LocalPortId(0),
+                    false
+                )))
+            ));
+            assert!(mock0.fn_calls.is_empty());
+        }
+
+        // Events are ignored without querying the controller or changing power state
+        assert!(matches!(
+            port.process_event(Event::PortEvent(PortEvent::StatusChanged(plug_event)))
+                .await,
+            Ok(None)
+        ));
+        assert!(port0.mock.lock().await.fn_calls.is_empty());
+        assert_eq!(port.state().psu_state, PsuState::Detached);
+
+        // Events are processed again once re-enabled
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_set_port_enabled.push_back(Ok(()));
+            mock0.next_result_get_port_status.push_back(Ok(attached));
+        }
+        port.set_port_enabled(true).await.unwrap();
+        assert!(port.is_port_enabled());
+        assert!(matches!(
+            port.process_event(Event::PortEvent(PortEvent::StatusChanged(plug_event)))
+                .await,
+            Ok(Some(PortEventData::StatusChanged(_)))
+        ));
+        assert_eq!(port.state().psu_state, PsuState::Idle);
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::PortEnable(PortEnableFnCall::SetPortEnabled(
+                LocalPortId(0),
+                true
+            )))
+        ));
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_disable_port() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDisablePort,
+    )
+    .await;
+}
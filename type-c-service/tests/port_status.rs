@@ -0,0 +1,338 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_sync::channel::Channel;
+use embedded_services::GlobalRawMutex;
+use embedded_usb_pd::{LocalPortId, PowerRole, type_c::ConnectionState};
+use power_policy_interface::psu::{Psu, PsuState};
+use type_c_interface::{
+    control::pd::PortStatus,
+    util::{POWER_CAPABILITY_5V_1A5, POWER_CAPABILITY_5V_3A0},
+};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::service::{
+    Service,
+    registration::{ArrayRegistration, PortData},
+};
+
+use crate::common::{
+    CHANNEL_SIZE, DEFAULT_TEST_DURATION, PortMutexType, PowerPolicyServiceReceiver, Test, TestPort,
+    TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test refreshing the cached port status.
+///
+/// The cached status should remain stale while the controller's status changes underneath it, until
+/// [`type_c_service::controller::Port::refresh_port_status`] re-reads it from the controller.
+struct TestRefreshPortStatus;
+
+impl Test for TestRefreshPortStatus {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let new_status = PortStatus {
+            available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        };
+
+        // Simulate the hardware changing without a notification
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Ok(new_status));
+
+        let mut port = port0.port.lock().await;
+
+        // Cache hasn't been updated yet
+        assert_eq!(port.get_cached_port_status(), PortStatus::default());
+        assert!(port0.mock.lock().await.fn_calls.is_empty());
+
+        // Refresh should read from the controller and return the new status
+        assert_eq!(port.refresh_port_status().await.unwrap(), new_status);
+        {
+            let mut mock0 = port0.mock.lock().await;
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+            ));
+            assert!(mock0.fn_calls.is_empty());
+        }
+
+        // Cache should now reflect the new status
+        assert_eq!(port.get_cached_port_status(), new_status);
+    }
+}
+
+/// Test that [`Service::snapshot_all_ports`] returns the cached status of every port in registration order.
+struct TestSnapshotAllPorts;
+
+impl Test for TestSnapshotAllPorts {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let statuses = [
+            PortStatus {
+                available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                ..Default::default()
+            },
+            PortStatus {
+                available_source_contract: Some(POWER_CAPABILITY_5V_3A0),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Source,
+                ..Default::default()
+            },
+            PortStatus::default(),
+        ];
+
+        // Give each port a distinct cached status
+        for (port, status) in [&port0, &port1, &port2].into_iter().zip(statuses) {
+            port.mock.lock().await.next_result_get_port_status.push_back(Ok(status));
+            port.port.lock().await.refresh_port_status().await.unwrap();
+        }
+
+        let service_channel: Channel<
+            GlobalRawMutex,
+            type_c_interface::service::event::Event<'port, PortMutexType<'port, 'ch>>,
+            CHANNEL_SIZE,
+        > = Channel::new();
+        let service = Service::new(
+            Default::default(),
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [service_channel.dyn_sender()],
+            },
+        )
+        .unwrap();
+
+        let snapshot = service.snapshot_all_ports().await.unwrap();
+        assert_eq!(snapshot.as_slice(), statuses.as_slice());
+    }
+}
+
+/// Test that [`Service::get_num_connected_ports`] counts the ports whose cached status is connected.
+struct TestNumConnectedPorts;
+
+impl Test for TestNumConnectedPorts {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let service_channel: Channel<
+            GlobalRawMutex,
+            type_c_interface::service::event::Event<'port, PortMutexType<'port, 'ch>>,
+            CHANNEL_SIZE,
+        > = Channel::new();
+        let service = Service::new(
+            Default::default(),
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [service_channel.dyn_sender()],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(service.get_num_connected_ports().await, 0);
+
+        let sink = PortStatus {
+            available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        };
+        let debug_accessory = PortStatus {
+            connection_state: Some(ConnectionState::DebugAccessory),
+            ..Default::default()
+        };
+
+        // Connect two of the three ports
+        for (port, status) in [(&port0, sink), (&port2, debug_accessory)] {
+            port.mock.lock().await.next_result_get_port_status.push_back(Ok(status));
+            port.port.lock().await.refresh_port_status().await.unwrap();
+        }
+        assert_eq!(service.get_num_connected_ports().await, 2);
+
+        // Disconnect one of them again
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Ok(PortStatus::default()));
+        port0.port.lock().await.refresh_port_status().await.unwrap();
+        assert_eq!(service.get_num_connected_ports().await, 1);
+    }
+}
+
+/// Test that the startup sync caches the controllers' initial status on every port, and only runs when configured.
+struct TestStartupSync;
+
+impl Test for TestStartupSync {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let statuses = [
+            PortStatus {
+                available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                ..Default::default()
+            },
+            PortStatus {
+                connection_state: Some(ConnectionState::DebugAccessory),
+                ..Default::default()
+            },
+            PortStatus::default(),
+        ];
+
+        for (port, status) in [&port0, &port1, &port2].into_iter().zip(statuses) {
+            port.mock.lock().await.next_result_get_port_status.push_back(Ok(status));
+        }
+
+        let service_channel: Channel<
+            GlobalRawMutex,
+            type_c_interface::service::event::Event<'port, PortMutexType<'port, 'ch>>,
+            CHANNEL_SIZE,
+        > = Channel::new();
+        let registration = || ArrayRegistration {
+            ports: [port0.port, port1.port, port2.port],
+            port_data: [
+                PortData {
+                    local_port: Some(LocalPortId(0)),
+                },
+                PortData {
+                    local_port: Some(LocalPortId(0)),
+                },
+                PortData {
+                    local_port: Some(LocalPortId(0)),
+                },
+            ],
+            service_senders: [service_channel.dyn_sender()],
+        };
+
+        // Startup sync is off by default, so no controller is queried
+        let service = Service::new(Default::default(), registration()).unwrap();
+        service.startup().await.unwrap();
+        for port in [&port0, &port1, &port2] {
+            assert!(port.mock.lock().await.fn_calls.is_empty());
+        }
+
+        let service = Service::new(
+            type_c_service::service::config::Config {
+                startup_sync: true,
+                ..Default::default()
+            },
+            registration(),
+        )
+        .unwrap();
+        service.startup().await.unwrap();
+
+        // Every controller was queried once and the cache reflects its initial state without waiting for events
+        for port in [&port0, &port1, &port2] {
+            let mut mock = port.mock.lock().await;
+            assert!(matches!(
+                mock.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+            ));
+            assert!(mock.fn_calls.is_empty());
+        }
+        let snapshot = service.snapshot_all_ports().await.unwrap();
+        assert_eq!(snapshot.as_slice(), statuses.as_slice());
+
+        // The connected sink went through the normal attach flow
+        assert_eq!(port0.port.lock().await.state().psu_state, PsuState::Idle);
+        assert!(port0.shared_state.lock().await.sink_ready_timeout().is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_refresh_port_status() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestRefreshPortStatus,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_snapshot_all_ports() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestSnapshotAllPorts,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_num_connected_ports() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestNumConnectedPorts,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_startup_sync() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestStartupSync,
+    )
+    .await;
+}
@@ -3,25 +3,28 @@
 use std::ptr;
 
 use embassy_futures::join::join;
-use embassy_time::{Duration, Instant, TimeoutError, with_timeout};
-use embedded_usb_pd::{PowerRole, constants::T_PS_TRANSITION_SPR_MS, type_c::ConnectionState};
+use embassy_time::{Duration, Instant, TimeoutError, Timer, with_timeout};
+use embedded_usb_pd::{LocalPortId, PowerRole, constants::T_PS_TRANSITION_SPR_MS, type_c::ConnectionState};
 use power_policy_interface::{
     capability::{
-        ConsumerDisconnect, ConsumerFlags, ConsumerPowerCapability, ProviderFlags, ProviderPowerCapability, PsuType,
+        ConsumerDisconnect, ConsumerFlags, ConsumerPowerCapability, PowerCapability, ProviderFlags,
+        ProviderPowerCapability, PsuType,
     },
     psu::{Psu, PsuState},
     service::event::Event as PowerPolicyEvent,
 };
 use type_c_interface::{
-    control::pd::PortStatus,
+    control::{pd::PortStatus, power::SystemPowerState},
     port::event::{PortEvent, PortEventBitfield, PortStatusEventBitfield},
     port::max_sink_voltage::MaxSinkVoltage,
-    util::POWER_CAPABILITY_5V_1A5,
+    port::power::SystemPowerStateStatus,
+    util::{POWER_CAPABILITY_5V_1A5, POWER_CAPABILITY_5V_3A0, POWER_CAPABILITY_USB_DEFAULT_USB3},
 };
 use type_c_interface_test_mocks::controller::{
     FnCall as ControllerFnCall, max_sink_voltage::FnCall as MaxSinkVoltageFnCall, pd::FnCall as PdFnCall,
+    power::FnCall as PowerFnCall,
 };
-use type_c_service::controller::event::Event;
+use type_c_service::controller::{config::DeadBatteryClear, event::Event};
 
 use crate::common::{
     DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
@@ -252,6 +255,323 @@ impl Test for TestBasicProviderFlow {
     }
 }
 
+/// Test that the capability chosen by the power policy is advertised to the controller on provider attach, capped to
+/// the configured default source capability
+struct TestDefaultSourceCapability {
+    /// Capability expected to be advertised, the power policy chooses 5V@1.5A
+    expected: PowerCapability,
+}
+
+impl Test for TestDefaultSourceCapability {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            // Set up the mock to report a source connection and accept the advertised source capability
+            let mut mock0 = port0.mock.lock().await;
+
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus {
+                available_source_contract: Some(POWER_CAPABILITY_5V_1A5),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Source,
+                ..Default::default()
+            }));
+            mock0.next_result_set_source_capability.push_back(Ok(()));
+        }
+
+        // Simulate a plug event and a new provider contract
+        let mut port_event = PortStatusEventBitfield::none();
+        port_event.set_plug_inserted_or_removed(true);
+        port_event.set_new_power_contract_as_provider(true);
+
+        port0
+            .port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::StatusChanged(port_event)))
+            .await
+            .unwrap();
+
+        // Wait for the power policy to connect the provider
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+            Ok(PowerPolicyEvent::ProviderConnected(_, _))
+        ));
+
+        let mock0 = port0.mock.lock().await;
+        assert!(mock0.fn_calls.iter().any(|call| matches!(
+            call,
+            ControllerFnCall::Pd(PdFnCall::SetSourceCapability(LocalPortId(0), capability))
+                if *capability == self.expected
+        )));
+        assert!(mock0.next_result_set_source_capability.is_empty());
+    }
+}
+
+/// Test automatic clearing of the dead battery flag when a consumer contract is established
+struct TestDeadBatteryClear {
+    /// Sink contract reported by the controller
+    contract: PowerCapability,
+    /// Whether the dead battery flag is expected to be cleared
+    expect_clear: bool,
+}
+
+impl Test for TestDeadBatteryClear {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            // Set up the mock to report a sink connection and allow enabling the sink path
+            let mut mock0 = port0.mock.lock().await;
+
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus {
+                available_sink_contract: Some(self.contract),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                ..Default::default()
+            }));
+            mock0.next_result_enable_sink_path.push_back(Ok(()));
+            if self.expect_clear {
+                mock0.next_result_clear_dead_battery_flag.push_back(Ok(()));
+            }
+        }
+
+        // Simulate a plug event and a new consumer contract
+        let mut port_event = PortStatusEventBitfield::none();
+        port_event.set_plug_inserted_or_removed(true);
+        port_event.set_new_power_contract_as_consumer(true);
+        port_event.set_sink_ready(true);
+
+        port0
+            .port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::StatusChanged(port_event)))
+            .await
+            .unwrap();
+
+        // Wait for the power policy to connect the consumer
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+            Ok(PowerPolicyEvent::ConsumerConnected(_, _))
+        ));
+
+        let mock0 = port0.mock.lock().await;
+        assert_eq!(
+            mock0.fn_calls.iter().any(|call| matches!(
+                call,
+                ControllerFnCall::Pd(PdFnCall::ClearDeadBatteryFlag(LocalPortId(0)))
+            )),
+            self.expect_clear
+        );
+        assert!(mock0.next_result_clear_dead_battery_flag.is_empty());
+    }
+}
+
+/// Process a status change on `port` after queueing `status` as the controller's port status
+async fn process_status_change(port: &TestPort<'_, '_>, status: PortStatus, event: PortStatusEventBitfield) {
+    port.mock.lock().await.next_result_get_port_status.push_back(Ok(status));
+    port.port
+        .lock()
+        .await
+        .process_event(Event::PortEvent(PortEvent::StatusChanged(event)))
+        .await
+        .unwrap();
+    // Let the power policy catch up before the next change
+    Timer::after_millis(10).await;
+}
+
+/// Returns the number of times the dead battery flag has been cleared on `port`
+async fn dead_battery_clears(port: &TestPort<'_, '_>) -> usize {
+    port.mock
+        .lock()
+        .await
+        .fn_calls
+        .iter()
+        .filter(|call| {
+            matches!(
+                call,
+                ControllerFnCall::Pd(PdFnCall::ClearDeadBatteryFlag(LocalPortId(0)))
+            )
+        })
+        .count()
+}
+
+/// Test that the dead battery flag is cleared once per attach rather than on every contract
+struct TestDeadBatteryClearOncePerAttach;
+
+impl Test for TestDeadBatteryClearOncePerAttach {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let attached = PortStatus {
+            available_sink_contract: Some(POWER_CAPABILITY_5V_3A0),
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        };
+
+        let mut attach = PortStatusEventBitfield::none();
+        attach.set_plug_inserted_or_removed(true);
+        attach.set_new_power_contract_as_consumer(true);
+        attach.set_sink_ready(true);
+
+        let mut renegotiate = PortStatusEventBitfield::none();
+        renegotiate.set_new_power_contract_as_consumer(true);
+        renegotiate.set_sink_ready(true);
+
+        let mut detach = PortStatusEventBitfield::none();
+        detach.set_plug_inserted_or_removed(true);
+
+        {
+            let mut mock0 = port0.mock.lock().await;
+            // Enough for the power policy to connect and disconnect the sink path each time
+            mock0.next_result_enable_sink_path.extend((0..6).map(|_| Ok(())));
+            mock0.next_result_clear_dead_battery_flag.extend((0..2).map(|_| Ok(())));
+        }
+
+        process_status_change(&port0, attached, attach).await;
+        assert_eq!(dead_battery_clears(&port0).await, 1);
+
+        // Later contracts with the same partner leave the flag alone
+        process_status_change(&port0, attached, renegotiate).await;
+        process_status_change(&port0, attached, renegotiate).await;
+        assert_eq!(dead_battery_clears(&port0).await, 1);
+
+        // A new attach clears it again
+        process_status_change(&port0, PortStatus::default(), detach).await;
+        process_status_change(&port0, attached, attach).await;
+        assert_eq!(dead_battery_clears(&port0).await, 2);
+        assert!(port0.mock.lock().await.next_result_clear_dead_battery_flag.is_empty());
+    }
+}
+
+/// Test that entering S5 disables the sink path on every port and detaches connected power devices
+struct TestShutdownOnS5;
+
+impl Test for TestShutdownOnS5 {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            // Connect port 0 as a consumer
+            let mut mock0 = port0.mock.lock().await;
+
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus {
+                available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                ..Default::default()
+            }));
+            mock0.next_result_enable_sink_path.push_back(Ok(()));
+        }
+
+        let mut port_event = PortStatusEventBitfield::none();
+        port_event.set_plug_inserted_or_removed(true);
+        port_event.set_new_power_contract_as_consumer(true);
+        port_event.set_sink_ready(true);
+
+        port0
+            .port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::StatusChanged(port_event)))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+            Ok(PowerPolicyEvent::ConsumerConnected(_, _))
+        ));
+
+        for port in [&port0, &port1, &port2] {
+            let mut mock = port.mock.lock().await;
+            mock.fn_calls.clear();
+            mock.next_result_enable_sink_path.push_back(Ok(()));
+            mock.next_result_set_system_power_state_status.push_back(Ok(()));
+        }
+
+        for port in [&port0, &port1, &port2] {
+            port.port
+                .lock()
+                .await
+                .set_system_power_state_status(SystemPowerState::S5)
+                .await
+                .unwrap();
+
+            // The sink path must be disabled before the controller is notified of the new power state
+            let mut mock = port.mock.lock().await;
+            assert!(matches!(
+                mock.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::EnableSinkPath(_, false)))
+            ));
+            assert!(matches!(
+                mock.fn_calls.pop_front(),
+                Some(ControllerFnCall::Power(PowerFnCall::SetSystemPowerStateStatus(
+                    _,
+                    SystemPowerState::S5
+                )))
+            ));
+            assert!(mock.fn_calls.is_empty());
+        }
+
+        // Only the connected consumer should be detached from the power policy
+        match with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await {
+            Ok(PowerPolicyEvent::ConsumerDisconnected(psu, _)) => {
+                assert!(ptr::eq(psu, port0.port));
+            }
+            _ => panic!("Did not receive consumer disconnected event"),
+        }
+        assert_eq!(port0.port.lock().await.state().psu_state, PsuState::Idle);
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+            Err(TimeoutError)
+        ));
+
+        // Other power states are passed straight through to the controller
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_set_system_power_state_status.push_back(Ok(()));
+        }
+        port0
+            .port
+            .lock()
+            .await
+            .set_system_power_state_status(SystemPowerState::S3)
+            .await
+            .unwrap();
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Power(PowerFnCall::SetSystemPowerStateStatus(
+                _,
+                SystemPowerState::S3
+            )))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
 /// End-to-end test of the software sink-ready timeout that drives the real `EventReceiver` and
 /// exercises every internal state transition along with the power-policy broadcasts.
 ///
@@ -282,6 +602,7 @@ impl Test for TestConsumerFlowTimerSinkReady {
             shared_state,
             interrupt_sender,
             mut event_receiver,
+            ..
         } = port0;
 
         {
@@ -829,6 +1150,113 @@ async fn test_basic_provider_flow() {
     .await;
 }
 
+#[tokio::test]
+async fn test_default_source_capability_above_policy() {
+    let mut port_config: [type_c_service::controller::config::Config; common::TYPE_C_PORT_COUNT] = Default::default();
+    port_config[0].default_source_capability = Some(POWER_CAPABILITY_5V_3A0);
+
+    // The power policy's choice is advertised as is
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestDefaultSourceCapability {
+            expected: POWER_CAPABILITY_5V_1A5,
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_default_source_capability_below_policy() {
+    let mut port_config: [type_c_service::controller::config::Config; common::TYPE_C_PORT_COUNT] = Default::default();
+    port_config[0].default_source_capability = Some(POWER_CAPABILITY_USB_DEFAULT_USB3);
+
+    // The power policy's choice is capped to the default
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestDefaultSourceCapability {
+            expected: POWER_CAPABILITY_USB_DEFAULT_USB3,
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dead_battery_clear_sufficient_contract() {
+    let mut port_config: [type_c_service::controller::config::Config; common::TYPE_C_PORT_COUNT] = Default::default();
+    port_config[0].dead_battery_clear = DeadBatteryClear::ConsumerPowerThresholdMilliwatts(10000);
+
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestDeadBatteryClear {
+            contract: POWER_CAPABILITY_5V_3A0,
+            expect_clear: true,
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dead_battery_clear_insufficient_contract() {
+    let mut port_config: [type_c_service::controller::config::Config; common::TYPE_C_PORT_COUNT] = Default::default();
+    port_config[0].dead_battery_clear = DeadBatteryClear::ConsumerPowerThresholdMilliwatts(10000);
+
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestDeadBatteryClear {
+            contract: POWER_CAPABILITY_5V_1A5,
+            expect_clear: false,
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dead_battery_clear_disabled_by_default() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDeadBatteryClear {
+            contract: POWER_CAPABILITY_5V_3A0,
+            expect_clear: false,
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dead_battery_clear_once_per_attach() {
+    let mut port_config: [type_c_service::controller::config::Config; common::TYPE_C_PORT_COUNT] = Default::default();
+    port_config[0].dead_battery_clear = DeadBatteryClear::ConsumerPowerThresholdMilliwatts(10000);
+
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestDeadBatteryClearOncePerAttach,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_shutdown_on_s5() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestShutdownOnS5,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn test_consumer_flow_timer_sink_ready() {
     common::run_test(
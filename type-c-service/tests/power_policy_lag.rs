@@ -0,0 +1,163 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_sync::channel::Channel;
+use embassy_sync::mutex::Mutex;
+use embassy_sync::pubsub::PubSubChannel;
+use embassy_time::with_timeout;
+use embedded_services::GlobalRawMutex;
+use embedded_services::event::NoopSender;
+use embedded_usb_pd::LocalPortId;
+use power_policy_interface::service::event::EventData as PowerPolicyEventData;
+use power_policy_interface::service::{UnconstrainedState, UnconstrainedStateSource};
+use type_c_interface::service::event::PortEventData;
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::service::event_receiver::{ArrayEventReceiver, LagDetectingReceiver};
+use type_c_service::service::registration::{ArrayRegistration, PortData};
+use type_c_service::service::{Event, Service};
+
+use crate::common::{
+    CHANNEL_SIZE, DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort,
+    TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Number of power policy events that can be queued before the subscriber lags
+const POWER_POLICY_CAPACITY: usize = 2;
+
+/// Power policy stand-in that reports a fixed unconstrained state
+struct PowerPolicyState(UnconstrainedState);
+
+impl UnconstrainedStateSource for PowerPolicyState {
+    fn unconstrained_state(&self) -> UnconstrainedState {
+        self.0
+    }
+}
+
+/// Test that dropped power policy events are reported and every port is resynced to the power policy's state
+struct TestPowerPolicyLag {
+    state: UnconstrainedState,
+}
+
+impl Test for TestPowerPolicyLag {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let channels: [Channel<GlobalRawMutex, PortEventData, CHANNEL_SIZE>; 3] =
+            [Channel::new(), Channel::new(), Channel::new()];
+        let power_policy_channel: PubSubChannel<GlobalRawMutex, PowerPolicyEventData, POWER_POLICY_CAPACITY, 1, 1> =
+            PubSubChannel::new();
+        let power_policy_publisher = power_policy_channel.dyn_immediate_publisher();
+        let power_policy: Mutex<GlobalRawMutex, _> = Mutex::new(PowerPolicyState(self.state));
+
+        let mut receiver = ArrayEventReceiver::new(
+            [port0.port, port1.port, port2.port],
+            [
+                channels[0].dyn_receiver(),
+                channels[1].dyn_receiver(),
+                channels[2].dyn_receiver(),
+            ],
+            LagDetectingReceiver::new(power_policy_channel.dyn_subscriber().unwrap(), &power_policy),
+        );
+
+        // Service separate from the one driven by the test harness so the lag event can be processed directly
+        let mut service = Service::new(
+            Default::default(),
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [NoopSender],
+            },
+        )
+        .unwrap();
+
+        // Overflow the subscriber, dropping the oldest unconstrained state change
+        for _ in 0..=POWER_POLICY_CAPACITY {
+            power_policy_publisher.publish_immediate(PowerPolicyEventData::Unconstrained(self.state));
+        }
+
+        let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next())
+            .await
+            .unwrap();
+        let Event::PowerPolicyLagged { missed, state } = event else {
+            panic!("Expected power policy lag");
+        };
+        assert_eq!(missed, 1);
+        assert_eq!(state, self.state);
+
+        for port in [&port0, &port1, &port2] {
+            port.mock
+                .lock()
+                .await
+                .next_result_set_unconstrained_power
+                .push_back(Ok(()));
+        }
+        service.process_event(event).await.unwrap();
+
+        // Every port is resynced to the power policy's state
+        for port in [&port0, &port1, &port2] {
+            let mut mock = port.mock.lock().await;
+            assert!(matches!(
+                mock.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::SetUnconstrainedPower(LocalPortId(0), unconstrained)))
+                    if unconstrained == self.state.unconstrained
+            ));
+            assert!(mock.fn_calls.is_empty());
+        }
+
+        // Events that weren't dropped are still delivered
+        for _ in 0..POWER_POLICY_CAPACITY {
+            let event = with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next())
+                .await
+                .unwrap();
+            assert!(matches!(
+                event,
+                Event::PowerPolicy(PowerPolicyEventData::Unconstrained(state)) if state == self.state
+            ));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_power_policy_lag_constrained() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPowerPolicyLag {
+            state: UnconstrainedState::new(false, 0),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_power_policy_lag_unconstrained() {
+    // Multiple unconstrained consumers, so every port is unconstrained without querying port status
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPowerPolicyLag {
+            state: UnconstrainedState::new(true, 2),
+        },
+    )
+    .await;
+}
@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::control::pd::PortStatus;
+use type_c_interface::port::event::PortEvent;
+use type_c_interface::port::pd::Pd;
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::controller::config::Config;
+use type_c_service::controller::event::Event;
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Number of consecutive timeouts before port0's controller is reset
+const THRESHOLD: u8 = 2;
+
+/// Process an alert that times out on the controller
+async fn time_out_alert(port: &TestPort<'_, '_>) {
+    port.mock
+        .lock()
+        .await
+        .next_result_get_pd_alert
+        .push_back(Err(PdError::Timeout));
+    assert!(matches!(
+        port.port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::Alert))
+            .await,
+        Err(PdError::Timeout)
+    ));
+}
+
+/// Returns true if the controller behind `port` has been reset since its calls were last cleared
+async fn was_reset(port: &TestPort<'_, '_>) -> bool {
+    port.mock
+        .lock()
+        .await
+        .fn_calls
+        .iter()
+        .any(|call| matches!(call, ControllerFnCall::ResetController))
+}
+
+/// Test that repeated controller timeouts reset the controller and resync the port
+struct TestTimeoutRecovery;
+
+impl Test for TestTimeoutRecovery {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // A successful command breaks the streak
+        time_out_alert(&port0).await;
+        port0.mock.lock().await.next_result_get_pd_alert.push_back(Ok(None));
+        port0
+            .port
+            .lock()
+            .await
+            .process_event(Event::PortEvent(PortEvent::Alert))
+            .await
+            .unwrap();
+        assert_eq!(port0.port.lock().await.consecutive_timeouts(), 0);
+
+        // No recovery below the threshold
+        port0.mock.lock().await.fn_calls.clear();
+        for _ in 0..THRESHOLD - 1 {
+            time_out_alert(&port0).await;
+        }
+        assert!(!was_reset(&port0).await);
+
+        // Reaching the threshold resets the controller then resyncs the port while processing the event
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.fn_calls.clear();
+            mock0.next_result_reset_controller.push_back(Ok(()));
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus::default()));
+        }
+        time_out_alert(&port0).await;
+        {
+            let mut mock0 = port0.mock.lock().await;
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetPdAlert(LocalPortId(0))))
+            ));
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::ResetController)
+            ));
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(LocalPortId(0))))
+            ));
+            assert!(mock0.fn_calls.is_empty());
+        }
+
+        // The streak starts over after recovery
+        assert_eq!(port0.port.lock().await.consecutive_timeouts(), 0);
+        assert!(!port0.port.lock().await.recover_if_needed().await.unwrap());
+
+        // Timed out commands count towards the threshold along with events
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.fn_calls.clear();
+            for _ in 0..THRESHOLD - 1 {
+                mock0.next_result_get_port_status.push_back(Err(PdError::Timeout));
+            }
+            mock0.next_result_reset_controller.push_back(Ok(()));
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus::default()));
+        }
+        for _ in 0..THRESHOLD - 1 {
+            assert!(matches!(
+                port0.port.lock().await.get_port_status().await,
+                Err(PdError::Timeout)
+            ));
+        }
+        assert!(!was_reset(&port0).await);
+        time_out_alert(&port0).await;
+        assert!(was_reset(&port0).await);
+        assert_eq!(port0.port.lock().await.consecutive_timeouts(), 0);
+
+        // Recovery is disabled by default
+        port1.mock.lock().await.fn_calls.clear();
+        for _ in 0..THRESHOLD {
+            time_out_alert(&port1).await;
+        }
+        assert_eq!(port1.port.lock().await.consecutive_timeouts(), THRESHOLD);
+        assert!(!was_reset(&port1).await);
+        assert!(!port1.port.lock().await.recover_if_needed().await.unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_timeout_recovery() {
+    let mut port_config: [Config; 3] = Default::default();
+    port_config[0].timeout_recovery_threshold = Some(THRESHOLD);
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        port_config,
+        TestTimeoutRecovery,
+    )
+    .await;
+}
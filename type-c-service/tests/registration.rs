@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_services::event::NoopSender;
+use embedded_usb_pd::{GlobalPortId, LocalPortId};
+use type_c_service::service::Service;
+use type_c_service::service::config::ConfigError;
+use type_c_service::service::registration::{ArrayRegistration, PortData};
+
+use crate::common::{
+    DEFAULT_TEST_DURATION, PortMutexType, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that a port can't be registered under more than one global port ID
+struct TestDuplicatePort;
+
+impl Test for TestDuplicatePort {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let registration = |ports: [&'port PortMutexType<'port, 'ch>; 3]| ArrayRegistration {
+            ports,
+            port_data: [
+                PortData {
+                    local_port: Some(LocalPortId(0)),
+                },
+                PortData {
+                    local_port: Some(LocalPortId(0)),
+                },
+                PortData {
+                    local_port: Some(LocalPortId(0)),
+                },
+            ],
+            service_senders: [NoopSender],
+        };
+
+        assert_eq!(
+            Service::new(Default::default(), registration([port0.port, port1.port, port0.port])).err(),
+            Some(ConfigError::DuplicatePort(GlobalPortId(2)))
+        );
+        assert_eq!(
+            Service::new(Default::default(), registration([port1.port, port1.port, port2.port])).err(),
+            Some(ConfigError::DuplicatePort(GlobalPortId(1)))
+        );
+
+        // Distinct ports are accepted
+        assert!(Service::new(Default::default(), registration([port0.port, port1.port, port2.port])).is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_duplicate_port() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestDuplicatePort,
+    )
+    .await;
+}
@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, with_timeout};
+use embedded_services::GlobalRawMutex;
+use power_policy_interface::service::event::EventData as PowerPolicyEventData;
+use type_c_interface::service::event::PortEventData;
+use type_c_service::service::{Event, event_receiver::ArrayEventReceiver};
+
+use crate::common::{
+    CHANNEL_SIZE, DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort,
+    TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that re-registering the power policy receiver restores power policy event flow after a reset
+struct TestReregisterPowerPolicy;
+
+impl Test for TestReregisterPowerPolicy {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let port_events: Channel<GlobalRawMutex, PortEventData, CHANNEL_SIZE> = Channel::new();
+        let original: Channel<GlobalRawMutex, PowerPolicyEventData, CHANNEL_SIZE> = Channel::new();
+        let after_reset: Channel<GlobalRawMutex, PowerPolicyEventData, CHANNEL_SIZE> = Channel::new();
+
+        let mut receiver = ArrayEventReceiver::new([port0.port], [port_events.dyn_receiver()], original.dyn_receiver());
+
+        original.send(PowerPolicyEventData::ProviderDisconnected).await;
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next())
+                .await
+                .unwrap(),
+            Event::PowerPolicy(PowerPolicyEventData::ProviderDisconnected)
+        ));
+
+        // After a power policy reset events arrive through a new sender, which the original receiver never sees
+        after_reset.send(PowerPolicyEventData::ProviderDisconnected).await;
+        assert!(
+            with_timeout(Duration::from_millis(100), receiver.wait_next())
+                .await
+                .is_err()
+        );
+
+        let previous = receiver.reregister_power_policy(after_reset.dyn_receiver());
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next())
+                .await
+                .unwrap(),
+            Event::PowerPolicy(PowerPolicyEventData::ProviderDisconnected)
+        ));
+
+        // The previous receiver is handed back unchanged and is no longer polled
+        drop(previous);
+        original.send(PowerPolicyEventData::ProviderDisconnected).await;
+        assert!(
+            with_timeout(Duration::from_millis(100), receiver.wait_next())
+                .await
+                .is_err()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_reregister_power_policy() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestReregisterPowerPolicy,
+    )
+    .await;
+}
@@ -0,0 +1,107 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use embassy_sync::mutex::Mutex;
+use embedded_services::GlobalRawMutex;
+use embedded_usb_pd::{LocalPortId, PdError};
+use type_c_interface::control::retimer::RetimerFwUpdateState;
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, Mock, retimer::FnCall as RetimerFnCall};
+use type_c_service::controller::retimer::set_controller_rt_fw_update_state;
+
+/// Update ordering used by the tests, deliberately not in port index order
+const ORDER: [LocalPortId; 2] = [LocalPortId(1), LocalPortId(0)];
+
+/// Drain the recorded controller calls, all of which are expected to be retimer calls
+async fn retimer_calls(controller: &Mutex<GlobalRawMutex, Mock>) -> Vec<RetimerFnCall> {
+    controller
+        .lock()
+        .await
+        .fn_calls
+        .drain(..)
+        .map(|call| match call {
+            ControllerFnCall::Retimer(call) => call,
+            _ => panic!("Expected a retimer call"),
+        })
+        .collect()
+}
+
+/// Test that ports enter update mode in the configured order and leave it in reverse
+#[tokio::test]
+async fn test_retimer_fw_update_ordering() {
+    let controller = Mutex::new(Mock::new("mock"));
+    {
+        let mut mock = controller.lock().await;
+        mock.next_result_set_rt_fw_update_state.extend([Ok(()), Ok(())]);
+        mock.next_result_clear_rt_fw_update_state.extend([Ok(()), Ok(())]);
+    }
+
+    set_controller_rt_fw_update_state(&controller, &ORDER, RetimerFwUpdateState::Active)
+        .await
+        .unwrap();
+    assert_eq!(
+        retimer_calls(&controller).await,
+        [
+            RetimerFnCall::SetRtFwUpdateState(LocalPortId(1)),
+            RetimerFnCall::SetRtFwUpdateState(LocalPortId(0)),
+        ]
+    );
+
+    set_controller_rt_fw_update_state(&controller, &ORDER, RetimerFwUpdateState::Inactive)
+        .await
+        .unwrap();
+    assert_eq!(
+        retimer_calls(&controller).await,
+        [
+            RetimerFnCall::ClearRtFwUpdateState(LocalPortId(0)),
+            RetimerFnCall::ClearRtFwUpdateState(LocalPortId(1)),
+        ]
+    );
+}
+
+/// Test that a failure entering update mode restores the ports that had already entered
+#[tokio::test]
+async fn test_retimer_fw_update_enter_failure_rolls_back() {
+    let controller = Mutex::new(Mock::new("mock"));
+    {
+        let mut mock = controller.lock().await;
+        mock.next_result_set_rt_fw_update_state
+            .extend([Ok(()), Err(PdError::Failed)]);
+        mock.next_result_clear_rt_fw_update_state.push_back(Ok(()));
+    }
+
+    assert_eq!(
+        set_controller_rt_fw_update_state(&controller, &ORDER, RetimerFwUpdateState::Active).await,
+        Err(PdError::Failed)
+    );
+    assert_eq!(
+        retimer_calls(&controller).await,
+        [
+            RetimerFnCall::SetRtFwUpdateState(LocalPortId(1)),
+            RetimerFnCall::SetRtFwUpdateState(LocalPortId(0)),
+            RetimerFnCall::ClearRtFwUpdateState(LocalPortId(1)),
+        ]
+    );
+}
+
+/// Test that a failure leaving update mode doesn't prevent the remaining ports from leaving
+#[tokio::test]
+async fn test_retimer_fw_update_exit_failure_continues() {
+    let controller = Mutex::new(Mock::new("mock"));
+    controller
+        .lock()
+        .await
+        .next_result_clear_rt_fw_update_state
+        .extend([Err(PdError::Timeout), Ok(())]);
+
+    assert_eq!(
+        set_controller_rt_fw_update_state(&controller, &ORDER, RetimerFwUpdateState::Inactive).await,
+        Err(PdError::Timeout)
+    );
+    assert_eq!(
+        retimer_calls(&controller).await,
+        [
+            RetimerFnCall::ClearRtFwUpdateState(LocalPortId(0)),
+            RetimerFnCall::ClearRtFwUpdateState(LocalPortId(1)),
+        ]
+    );
+}
@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PdError, PowerRole, type_c::ConnectionState};
+use type_c_interface::{control::pd::PortStatus, port::role_swap::RoleSwap};
+use type_c_interface_test_mocks::controller::{
+    FnCall as ControllerFnCall, pd::FnCall as PdFnCall, role_swap::FnCall as RoleSwapFnCall,
+};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test initiating a power role swap.
+///
+/// A swap request on a connected port should reach the controller and report the controller's result.
+/// A request on a disconnected port or towards the current role should not reach the controller.
+struct TestRequestPowerRoleSwap;
+
+impl Test for TestRequestPowerRoleSwap {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // Port 0 is connected as a sink
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus {
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Sink,
+                ..Default::default()
+            }));
+            mock0.next_result_request_power_role_swap.push_back(Ok(()));
+            mock0
+                .next_result_request_power_role_swap
+                .push_back(Err(PdError::Failed));
+        }
+
+        let mut port = port0.port.lock().await;
+        port.refresh_port_status().await.unwrap();
+        assert!(matches!(
+            port0.mock.lock().await.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+        ));
+
+        // Successful swap
+        assert_eq!(port.request_power_role_swap(PowerRole::Source).await, Ok(()));
+        assert!(matches!(
+            port0.mock.lock().await.fn_calls.pop_front(),
+            Some(ControllerFnCall::RoleSwap(RoleSwapFnCall::RequestPowerRoleSwap(
+                LocalPortId(0),
+                PowerRole::Source
+            )))
+        ));
+
+        // Controller rejects the swap
+        assert_eq!(
+            port.request_power_role_swap(PowerRole::Source).await,
+            Err(PdError::Failed)
+        );
+        assert!(matches!(
+            port0.mock.lock().await.fn_calls.pop_front(),
+            Some(ControllerFnCall::RoleSwap(RoleSwapFnCall::RequestPowerRoleSwap(
+                _,
+                PowerRole::Source
+            )))
+        ));
+
+        // Already in the requested role
+        assert_eq!(port.request_power_role_swap(PowerRole::Sink).await, Ok(()));
+        assert!(port0.mock.lock().await.fn_calls.is_empty());
+
+        // Port 1 is not connected
+        assert_eq!(
+            port1.port.lock().await.request_power_role_swap(PowerRole::Source).await,
+            Err(PdError::InvalidMode)
+        );
+        assert!(port1.mock.lock().await.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_request_power_role_swap() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestRequestPowerRoleSwap,
+    )
+    .await;
+}
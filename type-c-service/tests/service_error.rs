@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_sync::channel::Channel;
+use embedded_services::GlobalRawMutex;
+use embedded_usb_pd::{GlobalPortId, LocalPortId, PdError};
+use type_c_interface::control::pd::PortStatus;
+use type_c_service::service::{
+    Service, ServiceError,
+    registration::{ArrayRegistration, PortData},
+};
+
+use crate::common::{
+    CHANNEL_SIZE, DEFAULT_TEST_DURATION, PortMutexType, PowerPolicyServiceReceiver, Test, TestPort,
+    TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that distinct failure causes are reported as distinct service errors
+struct TestServiceErrors;
+
+impl Test for TestServiceErrors {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let service_channel: Channel<
+            GlobalRawMutex,
+            type_c_interface::service::event::Event<'port, PortMutexType<'port, 'ch>>,
+            CHANNEL_SIZE,
+        > = Channel::new();
+        let service = Service::new(
+            Default::default(),
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [service_channel.dyn_sender()],
+            },
+        )
+        .unwrap();
+
+        // Out of range port
+        assert_eq!(
+            service.get_port_status(GlobalPortId(3)).await,
+            Err(ServiceError::InvalidPort(GlobalPortId(3)))
+        );
+        assert_eq!(
+            service.get_port_status(GlobalPortId(1)).await,
+            Ok(PortStatus::default())
+        );
+
+        // Controller failure
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Ok(PortStatus::default()));
+        port1
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Err(PdError::Timeout));
+        port2
+            .mock
+            .lock()
+            .await
+            .next_result_get_port_status
+            .push_back(Ok(PortStatus::default()));
+        assert_eq!(service.sync_all_ports().await, Err(ServiceError::Pd(PdError::Timeout)));
+    }
+}
+
+#[tokio::test]
+async fn test_service_errors() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestServiceErrors,
+    )
+    .await;
+}
@@ -0,0 +1,222 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_futures::join::join;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Timer, with_timeout};
+use embedded_services::GlobalRawMutex;
+use embedded_services::event::NoopSender;
+use embedded_usb_pd::LocalPortId;
+use power_policy_interface::service::UnconstrainedState;
+use power_policy_interface::service::event::EventData as PowerPolicyEventData;
+use type_c_interface::service::event::PortEventData;
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::service::config::{Config, ConfigError};
+use type_c_service::service::event_receiver::{ArrayEventReceiver, ShutdownSignal};
+use type_c_service::service::registration::{ArrayRegistration, PortData};
+use type_c_service::service::{Event, Service};
+
+use crate::common::{
+    CHANNEL_SIZE, DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort,
+    TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Test that a shut down service drops events until it's reinitialized
+struct TestShutdown;
+
+impl Test for TestShutdown {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        // Service separate from the one driven by the test harness so events can be processed directly
+        let mut service = Service::new(
+            Default::default(),
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [NoopSender],
+            },
+        )
+        .unwrap();
+        let constrained = || Event::PowerPolicy(PowerPolicyEventData::Unconstrained(UnconstrainedState::default()));
+
+        assert!(!service.is_shut_down());
+        service.shutdown();
+        assert!(service.is_shut_down());
+
+        // Events are dropped without reaching any port
+        service.process_event(constrained()).await.unwrap();
+        service
+            .process_event(Event::PowerPolicyLagged {
+                missed: 1,
+                state: UnconstrainedState::default(),
+            })
+            .await
+            .unwrap();
+        for port in [&port0, &port1, &port2] {
+            assert!(port.mock.lock().await.fn_calls.is_empty());
+        }
+
+        // An invalid config leaves the service shut down
+        let mut config = Config::default();
+        config.ucsi_capabilities.num_connectors = 2;
+        assert_eq!(
+            service.reinit(config),
+            Err(ConfigError::ConnectorCountMismatch {
+                configured: 2,
+                registered: 3,
+            })
+        );
+        assert!(service.is_shut_down());
+
+        // Events are processed again after reinitializing
+        config.ucsi_capabilities.num_connectors = 3;
+        service.reinit(config).unwrap();
+        assert!(!service.is_shut_down());
+
+        for port in [&port0, &port1, &port2] {
+            port.mock
+                .lock()
+                .await
+                .next_result_set_unconstrained_power
+                .push_back(Ok(()));
+        }
+        service.process_event(constrained()).await.unwrap();
+        for port in [&port0, &port1, &port2] {
+            let mut mock = port.mock.lock().await;
+            assert!(matches!(
+                mock.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::SetUnconstrainedPower(
+                    LocalPortId(0),
+                    false
+                )))
+            ));
+            assert!(mock.fn_calls.is_empty());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestShutdown,
+    )
+    .await;
+}
+
+/// Test that a shutdown signal stops the event receiver immediately without taking an event, and that the power policy
+/// receiver can be deregistered and registered again
+struct TestShutdownSignal;
+
+impl Test for TestShutdownSignal {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let signal = ShutdownSignal::new();
+        let mut config = Config::default();
+        config.ucsi_capabilities.num_connectors = 1;
+        let mut service = Service::new(
+            config,
+            ArrayRegistration {
+                ports: [port0.port],
+                port_data: [PortData {
+                    local_port: Some(LocalPortId(0)),
+                }],
+                service_senders: [NoopSender],
+            },
+        )
+        .unwrap()
+        .with_shutdown_signal(&signal);
+
+        let port_events: Channel<GlobalRawMutex, PortEventData, CHANNEL_SIZE> = Channel::new();
+        let power_events: Channel<GlobalRawMutex, PowerPolicyEventData, CHANNEL_SIZE> = Channel::new();
+        let mut receiver =
+            ArrayEventReceiver::new([port0.port], [port_events.dyn_receiver()], power_events.dyn_receiver())
+                .with_shutdown_signal(&signal);
+
+        // A receiver already waiting stops as soon as the service shuts down
+        let (received, ()) = join(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next_or_shutdown()),
+            async {
+                Timer::after(Duration::from_millis(10)).await;
+                service.shutdown();
+            },
+        )
+        .await;
+        assert!(received.unwrap().is_none());
+        assert!(signal.is_shut_down());
+
+        // Pending events stay queued while shut down
+        power_events.send(PowerPolicyEventData::ProviderDisconnected).await;
+        assert!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next_or_shutdown())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // And are received once the service is reinitialized
+        service.reinit(config).unwrap();
+        assert!(!signal.is_shut_down());
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next_or_shutdown())
+                .await
+                .unwrap(),
+            Some(Event::PowerPolicy(PowerPolicyEventData::ProviderDisconnected))
+        ));
+
+        // No power policy events are received while deregistered
+        assert!(receiver.deregister_power_policy().is_some());
+        power_events.send(PowerPolicyEventData::ProviderDisconnected).await;
+        assert!(
+            with_timeout(Duration::from_millis(100), receiver.wait_next_or_shutdown())
+                .await
+                .is_err()
+        );
+        assert!(receiver.deregister_power_policy().is_none());
+
+        assert!(receiver.reregister_power_policy(power_events.dyn_receiver()).is_none());
+        assert!(matches!(
+            with_timeout(DEFAULT_PER_CALL_TIMEOUT, receiver.wait_next_or_shutdown())
+                .await
+                .unwrap(),
+            Some(Event::PowerPolicy(PowerPolicyEventData::ProviderDisconnected))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_signal() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestShutdownSignal,
+    )
+    .await;
+}
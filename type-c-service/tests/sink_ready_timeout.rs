@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_time::{Duration, with_timeout};
+use embedded_usb_pd::{PowerRole, constants::T_PS_TRANSITION_SPR_MS, type_c::ConnectionState};
+use power_policy_interface::psu::PsuState;
+use type_c_interface::{control::pd::PortStatus, port::event::PortEventBitfield, util::POWER_CAPABILITY_5V_1A5};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that a pending sink ready timeout is reported per port and can be cleared without generating a sink ready event
+struct TestQueryClearSinkReadyTimeout;
+
+impl Test for TestQueryClearSinkReadyTimeout {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let TestPort {
+            port,
+            mock,
+            interrupt_sender,
+            mut event_receiver,
+            ..
+        } = port0;
+
+        mock.lock().await.next_result_get_port_status.push_back(Ok(PortStatus {
+            available_sink_contract: Some(POWER_CAPABILITY_5V_1A5),
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        }));
+
+        // No timeouts pending on any port
+        for port in [port, port1.port, port2.port] {
+            assert!(port.lock().await.sink_ready_timeout_remaining().await.is_none());
+        }
+
+        // A new consumer contract without a sink ready event arms the timeout
+        let mut interrupt = PortEventBitfield::none();
+        interrupt.status.set_plug_inserted_or_removed(true);
+        interrupt.status.set_new_power_contract_as_consumer(true);
+        interrupt_sender.send(interrupt).await;
+        let event = event_receiver.wait_event().await;
+        port.lock().await.process_event(event).await.unwrap();
+        assert_eq!(port.lock().await.state().psu_state, PsuState::Idle);
+
+        // Only port 0 reports a pending timeout, with no more than the doubled transition time remaining
+        let max_timeout = Duration::from_millis(T_PS_TRANSITION_SPR_MS.maximum.0 as u64 * 2);
+        let remaining = port.lock().await.sink_ready_timeout_remaining().await.unwrap();
+        assert!(remaining > Duration::from_ticks(0));
+        assert!(remaining <= max_timeout);
+        for port in [port1.port, port2.port] {
+            assert!(port.lock().await.sink_ready_timeout_remaining().await.is_none());
+        }
+
+        assert!(port.lock().await.clear_sink_ready_timeout().await);
+        assert!(port.lock().await.sink_ready_timeout_remaining().await.is_none());
+        // Nothing left to clear
+        assert!(!port.lock().await.clear_sink_ready_timeout().await);
+
+        // The cleared timeout never synthesizes a sink ready event
+        assert!(
+            with_timeout(max_timeout + Duration::from_millis(100), event_receiver.wait_event())
+                .await
+                .is_err()
+        );
+        assert_eq!(port.lock().await.state().psu_state, PsuState::Idle);
+        assert!(mock.lock().await.next_result_get_port_status.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_query_clear_sink_ready_timeout() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestQueryClearSinkReadyTimeout,
+    )
+    .await;
+}
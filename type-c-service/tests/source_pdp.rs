@@ -0,0 +1,131 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::{LocalPortId, PdError, PowerRole, type_c::ConnectionState};
+use power_policy_interface::capability::PowerCapability;
+use type_c_interface::{control::pd::PortStatus, port::source_pdp::SourcePdp};
+use type_c_interface_test_mocks::controller::{
+    FnCall as ControllerFnCall, pd::FnCall as PdFnCall, source_pdp::FnCall as SourcePdpFnCall,
+};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that reducing the source PDP of a sourcing port asks the controller to renegotiate
+struct TestReduceSourcePdp;
+
+impl Test for TestReduceSourcePdp {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        // Port 0 is sourcing 15 W
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_port_status.push_back(Ok(PortStatus {
+                connection_state: Some(ConnectionState::Attached),
+                power_role: PowerRole::Source,
+                available_source_contract: Some(PowerCapability {
+                    voltage_mv: 5000,
+                    current_ma: 3000,
+                }),
+                ..Default::default()
+            }));
+            mock0.next_result_get_source_pdp.push_back(Ok(15000));
+            mock0.next_result_set_source_pdp.push_back(Ok(()));
+        }
+
+        let mut port = port0.port.lock().await;
+        port.refresh_port_status().await.unwrap();
+        assert!(matches!(
+            port0.mock.lock().await.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetPortStatus(_)))
+        ));
+
+        assert_eq!(port.get_source_pdp().await, Ok(15000));
+        assert!(matches!(
+            port0.mock.lock().await.fn_calls.pop_front(),
+            Some(ControllerFnCall::SourcePdp(SourcePdpFnCall::GetSourcePdp(LocalPortId(
+                0
+            ))))
+        ));
+
+        port.set_source_pdp(7500).await.unwrap();
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::SourcePdp(SourcePdpFnCall::SetSourcePdp(
+                LocalPortId(0),
+                7500,
+                true
+            )))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+/// Test that setting the source PDP of a port without a source contract doesn't renegotiate
+struct TestSetSourcePdpDisconnected;
+
+impl Test for TestSetSourcePdpDisconnected {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_set_source_pdp.push_back(Ok(()));
+            mock0.next_result_set_source_pdp.push_back(Err(PdError::Failed));
+        }
+
+        let mut port = port0.port.lock().await;
+        port.set_source_pdp(45000).await.unwrap();
+        assert_eq!(port.set_source_pdp(0).await, Err(PdError::Failed));
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::SourcePdp(SourcePdpFnCall::SetSourcePdp(
+                _,
+                45000,
+                false
+            )))
+        ));
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::SourcePdp(SourcePdpFnCall::SetSourcePdp(_, 0, false)))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_reduce_source_pdp() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestReduceSourcePdp,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_source_pdp_disconnected() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestSetSourcePdpDisconnected,
+    )
+    .await;
+}
@@ -0,0 +1,124 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embassy_time::with_timeout;
+use embedded_usb_pd::{PowerRole, ado::Ado, type_c::ConnectionState};
+use power_policy_interface::service::event::Event as PowerPolicyEvent;
+use type_c_interface::{
+    control::pd::PortStatus,
+    port::event::{PortEvent, PortStatusEventBitfield},
+    util::POWER_CAPABILITY_5V_3A0,
+};
+use type_c_service::controller::{event::Event, stats::PortStats};
+
+use crate::common::{
+    DEFAULT_PER_CALL_TIMEOUT, DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver,
+};
+
+mod common;
+
+/// Number of attach and contract cycles to drive
+const CYCLES: u32 = 3;
+
+/// Test that attaches, contracts and faults are counted and that the counters can be reset
+struct TestPortStats;
+
+impl Test for TestPortStats {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        assert_eq!(port0.port.lock().await.stats(), PortStats::default());
+
+        let attached = PortStatus {
+            available_sink_contract: Some(POWER_CAPABILITY_5V_3A0),
+            connection_state: Some(ConnectionState::Attached),
+            power_role: PowerRole::Sink,
+            ..Default::default()
+        };
+        let mut attach_event = PortStatusEventBitfield::none();
+        attach_event.set_plug_inserted_or_removed(true);
+        attach_event.set_new_power_contract_as_consumer(true);
+        attach_event.set_sink_ready(true);
+        let mut detach_event = PortStatusEventBitfield::none();
+        detach_event.set_plug_inserted_or_removed(true);
+
+        for _ in 0..CYCLES {
+            // Enabling then disabling the sink path
+            {
+                let mut mock0 = port0.mock.lock().await;
+                mock0.next_result_enable_sink_path.push_back(Ok(()));
+                mock0.next_result_enable_sink_path.push_back(Ok(()));
+            }
+
+            port0
+                .port
+                .lock()
+                .await
+                .inject_port_event(attach_event, attached)
+                .await
+                .unwrap();
+            assert!(matches!(
+                with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+                Ok(PowerPolicyEvent::ConsumerConnected(_, _))
+            ));
+
+            port0
+                .port
+                .lock()
+                .await
+                .inject_port_event(detach_event, PortStatus::default())
+                .await
+                .unwrap();
+            assert!(matches!(
+                with_timeout(DEFAULT_PER_CALL_TIMEOUT, power_policy_receiver.receive()).await,
+                Ok(PowerPolicyEvent::ConsumerDisconnected(_, _))
+            ));
+        }
+
+        // Only fault alerts are counted
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_get_pd_alert.push_back(Ok(Some(Ado::Ocp)));
+            mock0
+                .next_result_get_pd_alert
+                .push_back(Ok(Some(Ado::PowerButtonPress)));
+        }
+        for _ in 0..2 {
+            port0
+                .port
+                .lock()
+                .await
+                .process_event(Event::PortEvent(PortEvent::Alert))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            port0.port.lock().await.stats(),
+            PortStats {
+                attach_count: CYCLES,
+                contract_count: CYCLES,
+                fault_count: 1,
+            }
+        );
+
+        port0.port.lock().await.reset_stats();
+        assert_eq!(port0.port.lock().await.stats(), PortStats::default());
+    }
+}
+
+#[tokio::test]
+async fn test_port_stats() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestPortStats,
+    )
+    .await;
+}
@@ -0,0 +1,143 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use core::num::NonZeroU8;
+
+use embedded_services::event::NoopSender;
+use embedded_usb_pd::ucsi::{GlobalCommand, lpm};
+use embedded_usb_pd::{GlobalPortId, LocalPortId};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+use type_c_service::service::config::Config;
+use type_c_service::service::registration::{ArrayRegistration, PortData};
+use type_c_service::service::{Service, ServiceError};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Number of consecutive invalid transitions before the connector is reset
+const THRESHOLD: u8 = 3;
+
+/// A reset received by a mock controller
+#[derive(Debug, PartialEq)]
+enum Reset {
+    Hard(LocalPortId),
+    Data(LocalPortId),
+    Controller,
+}
+
+/// Returns every reset, of any kind, the controller behind `port` has received
+async fn resets(port: &TestPort<'_, '_>) -> Vec<Reset> {
+    port.mock
+        .lock()
+        .await
+        .fn_calls
+        .iter()
+        .filter_map(|call| match call {
+            ControllerFnCall::Pd(PdFnCall::HardReset(port)) => Some(Reset::Hard(*port)),
+            ControllerFnCall::Pd(PdFnCall::ExecuteDrst(port)) => Some(Reset::Data(*port)),
+            ControllerFnCall::ResetController => Some(Reset::Controller),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Test that repeated out-of-sequence LPM commands hard reset the addressed connector, and issue no other reset
+struct TestInvalidTransitionReset {
+    threshold: Option<NonZeroU8>,
+}
+
+impl Test for TestInvalidTransitionReset {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let config = Config {
+            ucsi_invalid_transition_reset_threshold: self.threshold,
+            ..Default::default()
+        };
+
+        let mut service = Service::new(
+            config,
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [NoopSender],
+            },
+        )
+        .unwrap();
+
+        port0.mock.lock().await.next_result_hard_reset.push_back(Ok(()));
+
+        // Notifications haven't been enabled, so the PPM rejects every LPM command
+        let command = GlobalCommand::LpmCommand(lpm::GlobalCommand::new(
+            GlobalPortId(0),
+            lpm::CommandData::GetConnectorStatus,
+        ));
+
+        for _ in 0..THRESHOLD - 1 {
+            let response = service.process_ucsi_command(&command).await;
+            assert!(response.notify_opm);
+            assert!(matches!(response.data, Err(ServiceError::InvalidUcsiTransition)));
+        }
+        assert_eq!(resets(&port0).await, []);
+
+        let response = service.process_ucsi_command(&command).await;
+        assert!(matches!(response.data, Err(ServiceError::InvalidUcsiTransition)));
+        // Only a PD hard reset is issued, on the addressed connector
+        let expected = if self.threshold.is_some() {
+            vec![Reset::Hard(LocalPortId(0))]
+        } else {
+            vec![]
+        };
+        assert_eq!(resets(&port0).await, expected);
+
+        // The count restarts after the reset
+        for _ in 0..THRESHOLD - 1 {
+            service.process_ucsi_command(&command).await;
+        }
+        assert_eq!(resets(&port0).await, expected);
+
+        // Other connectors are left alone
+        assert_eq!(resets(&port1).await, []);
+        assert_eq!(resets(&port2).await, []);
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_transition_reset() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestInvalidTransitionReset {
+            threshold: NonZeroU8::new(THRESHOLD),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_invalid_transition_no_threshold() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestInvalidTransitionReset { threshold: None },
+    )
+    .await;
+}
@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_services::event::NoopSender;
+use embedded_usb_pd::LocalPortId;
+use type_c_service::service::config::Config;
+use type_c_service::service::registration::{ArrayRegistration, PortData};
+use type_c_service::service::{Service, UcsiSpecVersions};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that the configured UCSI spec versions are reported
+struct TestUcsiSpecVersions;
+
+impl Test for TestUcsiSpecVersions {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        port1: TestPort<'port, 'ch>,
+        port2: TestPort<'port, 'ch>,
+    ) {
+        let mut config = Config::default();
+        // USB PD 3.1 and Type-C 2.2
+        config.ucsi_capabilities.bcd_usb_pd_spec = 0x0310;
+        config.ucsi_capabilities.bcd_type_c_spec = 0x0220;
+
+        let service = Service::new(
+            config,
+            ArrayRegistration {
+                ports: [port0.port, port1.port, port2.port],
+                port_data: [
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                    PortData {
+                        local_port: Some(LocalPortId(0)),
+                    },
+                ],
+                service_senders: [NoopSender],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            service.ucsi_spec_versions(),
+            UcsiSpecVersions {
+                bcd_usb_pd_spec: 0x0310,
+                bcd_type_c_spec: 0x0220,
+            }
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_ucsi_spec_versions() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestUcsiSpecVersions,
+    )
+    .await;
+}
@@ -0,0 +1,236 @@
+#![allow(dead_code)]
+#![allow(clippy::unwrap_used)]
+
+use embedded_usb_pd::PdError;
+use type_c_interface::{
+    control::{
+        mode::{BillboardIndication, DataMode, LaneConfig, ModeEntryFailure, ModeEntryStatus},
+        usb::{UsbDataMode, UsbDataStatus},
+    },
+    port::pd::Pd,
+};
+use type_c_interface_test_mocks::controller::{FnCall as ControllerFnCall, pd::FnCall as PdFnCall};
+
+use crate::common::{DEFAULT_TEST_DURATION, PowerPolicyServiceReceiver, Test, TestPort, TypeCServiceReceiver};
+
+mod common;
+
+/// Test that the negotiated USB data status reported by the controller is returned for the port
+struct TestGetUsbDataStatus;
+
+impl Test for TestGetUsbDataStatus {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        let expected_status = UsbDataStatus {
+            mode: UsbDataMode::Usb4Gen3,
+        };
+        port0
+            .mock
+            .lock()
+            .await
+            .next_result_get_usb_data_status
+            .push_back(Ok(expected_status));
+
+        let status = port0.port.lock().await.get_usb_data_status().await.unwrap();
+        assert_eq!(status, expected_status);
+        assert!(status.mode.is_usb4());
+        assert_eq!(status.mode.max_rate_mbps(), 40_000);
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::GetUsbDataStatus(_)))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+/// Test that a mode entry request reaches the controller and its result is returned for the port
+///
+/// Failed entries must also request a billboard indication with the failure reason.
+struct TestEnterDataMode {
+    /// Mode to request
+    mode: DataMode,
+    /// Result reported by the controller
+    result: Result<ModeEntryStatus, PdError>,
+}
+
+impl Test for TestEnterDataMode {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_enter_data_mode.push_back(self.result);
+            mock0.next_result_set_billboard_indication.push_back(Ok(()));
+        }
+
+        let result = port0.port.lock().await.enter_data_mode(self.mode).await;
+        assert_eq!(result, self.result);
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::EnterDataMode(_, mode))) if mode == self.mode
+        ));
+        if let Err(e) = self.result {
+            let expected = BillboardIndication {
+                mode: self.mode,
+                reason: e.into(),
+            };
+            assert!(matches!(
+                mock0.fn_calls.pop_front(),
+                Some(ControllerFnCall::Pd(PdFnCall::SetBillboardIndication(_, indication))) if indication == expected
+            ));
+        }
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+/// Test that an unsupported mode is reported as such, and a billboard failure doesn't replace the entry error
+struct TestBillboardUnsupportedMode;
+
+impl Test for TestBillboardUnsupportedMode {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        {
+            let mut mock0 = port0.mock.lock().await;
+            mock0.next_result_enter_data_mode.push_back(Err(PdError::InvalidMode));
+            mock0
+                .next_result_set_billboard_indication
+                .push_back(Err(PdError::Timeout));
+        }
+
+        let result = port0.port.lock().await.enter_data_mode(DataMode::Usb4).await;
+        assert_eq!(result, Err(PdError::InvalidMode));
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::EnterDataMode(_, DataMode::Usb4)))
+        ));
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::SetBillboardIndication(
+                _,
+                BillboardIndication {
+                    mode: DataMode::Usb4,
+                    reason: ModeEntryFailure::Unsupported,
+                }
+            )))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+/// Test that a mode exit request reaches the controller
+struct TestExitDataMode;
+
+impl Test for TestExitDataMode {
+    async fn run<'port, 'ch>(
+        &mut self,
+        _type_c_receiver: TypeCServiceReceiver<'port, 'ch>,
+        _power_policy_receiver: PowerPolicyServiceReceiver<'port, 'ch>,
+        port0: TestPort<'port, 'ch>,
+        _port1: TestPort<'port, 'ch>,
+        _port2: TestPort<'port, 'ch>,
+    ) {
+        port0.mock.lock().await.next_result_exit_data_mode.push_back(Ok(()));
+
+        port0
+            .port
+            .lock()
+            .await
+            .exit_data_mode(DataMode::Thunderbolt)
+            .await
+            .unwrap();
+
+        let mut mock0 = port0.mock.lock().await;
+        assert!(matches!(
+            mock0.fn_calls.pop_front(),
+            Some(ControllerFnCall::Pd(PdFnCall::ExitDataMode(_, DataMode::Thunderbolt)))
+        ));
+        assert!(mock0.fn_calls.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_get_usb_data_status() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestGetUsbDataStatus,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_enter_usb4_mode() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestEnterDataMode {
+            mode: DataMode::Usb4,
+            result: Ok(ModeEntryStatus {
+                mode: DataMode::Usb4,
+                lanes: LaneConfig::X2,
+            }),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_enter_tbt_mode_failure() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestEnterDataMode {
+            mode: DataMode::Thunderbolt,
+            result: Err(PdError::Failed),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_billboard_unsupported_mode() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestBillboardUnsupportedMode,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_exit_data_mode() {
+    common::run_test(
+        DEFAULT_TEST_DURATION,
+        Default::default(),
+        Default::default(),
+        TestExitDataMode,
+    )
+    .await;
+}